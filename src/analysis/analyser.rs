@@ -1,30 +1,138 @@
-use crate::analysis::{Action, Table};
+use crate::analysis::{Action, StateIndex, Table};
 use crate::common::{FirstSet, Grammar, Item, Symbol, Terminal};
 
-use std::collections::{HashMap, HashSet};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 
 use std::hash::Hash;
 
-trait ItemSet<'r, T, NT> {
-    fn active_symbols(&mut self) -> HashSet<Symbol<T, NT>>;
+pub type ItemSet<'r, T, NT> = HashSet<Item<'r, T, NT>>;
+pub type Transitions<T, NT> = HashMap<Symbol<T, NT>, StateIndex>;
+
+/// The canonical LR(1) automaton of a grammar, the state at index 0 is the start state.
+pub struct Automaton<'g, T, NT> {
+    pub states: Vec<ItemSet<'g, T, NT>>,
+    pub transitions: Vec<Transitions<T, NT>>,
+    grammar: &'g Grammar<T, NT>,
 }
 
-impl<'r, A, T, NT> ItemSet<'r, T, NT> for A
+impl<'g, T, NT> Automaton<'g, T, NT>
 where
-    A: Iterator<Item = &'r Item<'r, T, NT>>,
-    T: 'static + Copy + Eq + Hash,
-    NT: 'static + Copy + Eq + Hash,
+    T: Terminal,
+    NT: Eq + Hash + Copy + std::fmt::Debug,
 {
-    fn active_symbols(&mut self) -> HashSet<Symbol<T, NT>> {
-        self.filter_map(|i| i.active_symbol()).collect()
+    pub fn grammar(&self) -> &'g Grammar<T, NT> {
+        self.grammar
+    }
+
+    /// The items of a state in a stable order, by rule, dot position and look ahead.
+    pub fn items(&self, state: StateIndex) -> Vec<Item<'g, T, NT>> {
+        let mut items = self.states[state].iter().copied().collect::<Vec<_>>();
+        items.sort_by_cached_key(|i| {
+            (
+                self.grammar.rule_index(i.rule()),
+                i.index(),
+                format!("{:?}", i.look_ahead()),
+            )
+        });
+        items
+    }
+
+    /// The items a state was created from, as opposed to the items added by closing over them.
+    pub fn kernel(&self, state: StateIndex) -> Vec<Item<'g, T, NT>> {
+        self.items(state)
+            .into_iter()
+            .filter(|i| i.index() > 0 || i.rule() == self.grammar.start_rule())
+            .collect()
+    }
+
+    /// The transitions of a state ordered by the state they lead to.
+    pub fn edges(&self, state: StateIndex) -> Vec<(Symbol<T, NT>, StateIndex)> {
+        let mut edges = self.transitions[state]
+            .iter()
+            .map(|(&sym, &to_state)| (sym, to_state))
+            .collect::<Vec<_>>();
+        edges.sort_by_key(|&(_, to_state)| to_state);
+        edges
+    }
+
+    /// A shortest sequence of symbols that leads from the start state to `state`.
+    pub fn shortest_path(&self, state: StateIndex) -> Vec<Symbol<T, NT>> {
+        let mut previous = vec![None; self.states.len()];
+        let mut queue = VecDeque::new();
+        queue.push_back(0);
+
+        while let Some(current) = queue.pop_front() {
+            if current == state {
+                break;
+            }
+
+            for (sym, to_state) in self.edges(current) {
+                if to_state != 0 && previous[to_state].is_none() {
+                    previous[to_state] = Some((current, sym));
+                    queue.push_back(to_state);
+                }
+            }
+        }
+
+        let mut path = Vec::new();
+        let mut current = state;
+        while let Some((from, sym)) = previous[current] {
+            path.push(sym);
+            current = from;
+        }
+
+        path.reverse();
+        path
+    }
+
+    /// A shortest sentence of terminals after which the parser is in each state, when the last symbol
+    /// leading to the state is a terminal. After a sentence leading to a state entered on a
+    /// non-terminal, the parser has not reduced to that non-terminal yet. States that no sentence
+    /// leads to, because they are only entered through the error terminal, have none.
+    pub fn shortest_sentences(&self) -> Vec<Option<Vec<T>>> {
+        let yields = self.grammar.shortest_yields();
+        let mut sentences = vec![None; self.states.len()];
+        let mut queue = BinaryHeap::new();
+        sentences[0] = Some(Vec::new());
+        queue.push(Reverse((0, 0)));
+
+        // Dijkstra's algorithm, where every symbol weighs the length of its shortest sentence.
+        while let Some(Reverse((len, state))) = queue.pop() {
+            let sentence = match &sentences[state] {
+                Some(sentence) if sentence.len() == len => sentence.clone(),
+                _ => continue,
+            };
+
+            for (sym, to_state) in self.edges(state) {
+                let derived = match sym {
+                    Symbol::Terminal(term) if term.is_error() => continue,
+                    Symbol::Terminal(term) => vec![term],
+                    Symbol::NonTerminal(nt) => match yields.get(&nt) {
+                        Some(derived) => derived.clone(),
+                        None => continue,
+                    },
+                };
+
+                let len = len + derived.len();
+                if sentences[to_state]
+                    .as_ref()
+                    .is_none_or(|shortest: &Vec<T>| len < shortest.len())
+                {
+                    sentences[to_state] = Some(sentence.iter().copied().chain(derived).collect());
+                    queue.push(Reverse((len, to_state)));
+                }
+            }
+        }
+
+        sentences
     }
 }
 
 pub struct Analyser<'g, T, NT> {
-    //table: Table<T, NT>,
-    //states: Vec<ItemSet<'g, T, NT>>,
     grammar: &'g Grammar<T, NT>,
     first_set: FirstSet<T, NT>,
+    nullable: HashSet<NT>,
 }
 
 impl<'g, T, NT> Analyser<'g, T, NT>
@@ -36,108 +144,118 @@ where
         Self {
             grammar,
             first_set: grammar.first_set(),
-            //table: Table::new(),
-            //states: vec![start_set],
+            nullable: grammar.nullable(),
         }
+    }
+
+    pub fn grammar(&self) -> &'g Grammar<T, NT> {
+        self.grammar
+    }
 
-        //analyser.generate_states();
+    pub fn states(&self) -> Vec<ItemSet<'g, T, NT>> {
+        self.automaton().states
     }
 
-    pub fn states(&self) -> Vec<HashSet<Item<'g, T, NT>>> {
+    /// Builds the canonical LR(1) automaton, containing every state together with its outgoing transitions.
+    pub fn automaton(&self) -> Automaton<'g, T, NT> {
         let start_item = Item::new(self.grammar.start_rule(), 0, T::eof());
-        let mut kernels = vec![vec![start_item].into_iter().collect::<HashSet<_>>()];
-        let mut closures = vec![];
+        let mut kernels = vec![vec![start_item].into_iter().collect::<ItemSet<_, _>>()];
         let mut itemsets = vec![];
+        let mut transitions = vec![];
 
-        while kernels.len() > closures.len() {
-            for i in 0..kernels.len() {
-                closures.push(self.close(&kernels[i]));
+        // Every kernel that is discovered gets closed exactly once, in order of discovery,
+        // which keeps the numbering of states stable between runs.
+        while itemsets.len() < kernels.len() {
+            let state = self.close(&kernels[itemsets.len()]);
+            let mut edges = HashMap::new();
 
-                let state = kernels[i]
-                    .union(&closures[i])
-                    .cloned()
-                    .collect::<HashSet<_>>();
+            for sym in self.active_symbols(&state) {
+                let new_kernel = self.goto_symbol(&state, sym);
 
-                for sym in state.iter().active_symbols() {
-                    let new_kernel = self.goto_symbol(&state, sym);
-                    if let Some(i) = kernels.iter().position(|k| k == &kernels[i]) {
-                    } else {
+                let to_state = match kernels.iter().position(|k| k == &new_kernel) {
+                    Some(index) => index,
+                    None => {
                         kernels.push(new_kernel);
+                        kernels.len() - 1
                     }
-                }
+                };
 
-                itemsets.push(state);
+                edges.insert(sym, to_state);
             }
+
+            itemsets.push(state);
+            transitions.push(edges);
         }
 
-        itemsets
+        Automaton {
+            states: itemsets,
+            transitions,
+            grammar: self.grammar,
+        }
     }
 
     pub fn table(grammar: &'g Grammar<T, NT>) -> Table<T, NT> {
-        let analyzer = Self::new(grammar);
-        todo!();
-    }
+        let analyser = Self::new(grammar);
+        let automaton = analyser.automaton();
+        let mut table = Table::new();
+
+        for (index, state) in automaton.states.iter().enumerate() {
+            for (sym, to_state) in automaton.edges(index) {
+                match sym {
+                    Symbol::Terminal(term) => {
+                        table.insert_action(index, term, Action::Shift(to_state))
+                    }
+                    Symbol::NonTerminal(nonterm) => table.insert_goto(index, nonterm, to_state),
+                }
+            }
 
-    fn close(&self, state: &HashSet<Item<'g, T, NT>>) -> HashSet<Item<'g, T, NT>> {
-        let mut already_closed = HashSet::new();
+            let mut reductions = state
+                .iter()
+                .filter(|item| !item.is_active())
+                .collect::<Vec<_>>();
+            reductions.sort_by_key(|item| grammar.rule_index(item.rule()));
+
+            for item in reductions {
+                let (term, action) = analyser.reduce_item(item);
+                table.insert_action(index, term, action);
+            }
+        }
 
-        // Fill the queue with initial closure of the kernel.
-        let mut queue = self.close_once(state, &mut already_closed);
+        table
+    }
 
-        let mut new_queue = HashSet::new();
-        loop {
-            // Fill the empty new_queue 'buffer' with the closure of the current queue.
-            new_queue.extend(self.close_once(&queue, &mut already_closed));
+    /// The active symbols of a state, ordered by their first occurrence in the grammar.
+    fn active_symbols(&self, state: &ItemSet<'g, T, NT>) -> Vec<Symbol<T, NT>> {
+        let mut items = state.iter().filter(|i| i.is_active()).collect::<Vec<_>>();
+        items.sort_by_key(|i| (self.grammar.rule_index(i.rule()), i.index()));
 
-            // If the above operation yields no new closures, it means that the current queue is fully closed,
-            // if not it we should add the new items to the queue to be closed in the next loop.
-            if new_queue.is_empty() {
-                break;
-            } else {
-                queue.extend(&new_queue);
-                new_queue.clear();
+        let mut symbols = Vec::new();
+        for sym in items.into_iter().filter_map(|i| i.active_symbol()) {
+            if !symbols.contains(&sym) {
+                symbols.push(sym);
             }
         }
 
-        queue
+        symbols
     }
 
-    // fn goto_state(&mut self, index: usize) {
-    //     for sym in self.states[index].active_symbols() {
-    //         self.goto_symbol(index, sym);
-    //     }
-    // }
-
-    fn close_once(
-        &self,
-        set: &HashSet<Item<'g, T, NT>>,
-        already_closed: &mut HashSet<Symbol<T, NT>>,
-    ) -> HashSet<Item<'g, T, NT>> {
-        // This function only shallowly closes an item. the resulting set may return unclosed items.
+    fn close(&self, kernel: &ItemSet<'g, T, NT>) -> ItemSet<'g, T, NT> {
+        let mut state = kernel.clone();
+        let mut queue = kernel.iter().copied().collect::<Vec<_>>();
 
-        let mut result = HashSet::new();
-        // If an item in the set is active we should close on the active symbol,
-        // if not it should be ingored.
-        for item in set {
-            if let Some(sym) = item.active_symbol() {
-                // 'already_closed' keeps a record of all previously closed symbols,
-                // if the active symbol of 'item' is already in this set it can be assumed that the
-                // resulting closure is already present somewhere. Without this, recursion problems would occure.
-                if !already_closed.contains(&sym) {
-                    result.extend(self.close_item(item));
-                    already_closed.insert(sym);
+        // Closing an item may yield items that need closing themselves, keep going until no new items appear.
+        while let Some(item) = queue.pop() {
+            for new_item in self.close_item(&item) {
+                if state.insert(new_item) {
+                    queue.push(new_item);
                 }
             }
         }
 
-        result
+        state
     }
 
-    fn goto_symbol(
-        &self,
-        state: &HashSet<Item<'g, T, NT>>,
-        sym: Symbol<T, NT>,
-    ) -> HashSet<Item<'g, T, NT>> {
+    fn goto_symbol(&self, state: &ItemSet<'g, T, NT>, sym: Symbol<T, NT>) -> ItemSet<'g, T, NT> {
         let mut new_set = HashSet::new();
 
         // Create a new set of items from the current item set,
@@ -151,14 +269,6 @@ where
         }
 
         new_set
-
-        // match sym {
-        //     Symbol::NonTerminal(non_term) => self.table.insert_goto(index, non_term, to_state),
-        //     Symbol::Terminal(term) => {
-        //         self.table
-        //             .insert_action(index, term, Action::Shift(to_state))
-        //     }
-        // }
     }
 
     fn reduce_item(&self, item: &Item<T, NT>) -> (T, Action<T, NT>) {
@@ -171,30 +281,25 @@ where
         }
     }
 
-    fn close_item(&self, item: &Item<'g, T, NT>) -> HashSet<Item<'g, T, NT>> {
+    fn close_item(&self, item: &Item<'g, T, NT>) -> ItemSet<'g, T, NT> {
         // This function only shallowly closes an item. the resulting set may return unclosed items.
         let mut result = HashSet::new();
 
         // In order to close an item, it must be active and it's active symbol must be a reference to other rules.
         // If this is not the case the resulting closure consists of an empty set.
         if let Some(Symbol::NonTerminal(lhs)) = item.active_symbol() {
-            // Find the first set of the symbol following the active symbol,
-            // if the item is not active or there is no symbol following the active symbol,
-            // use the the look_ahead of this item.
-            let look_aheads = self
-                .first_set
-                .get(&item.following_active())
-                .unwrap_or_else(|| {
-                    panic!(
-                        "Fatal error, first set does not contain {:?}",
-                        item.following_active()
-                    )
-                });
+            // The look aheads of the new items are the first set of everything following the active symbol,
+            // falling back to the look ahead of this item when all of it can be empty.
+            let look_aheads = Grammar::first_of(
+                &self.first_set,
+                &self.nullable,
+                item.following_symbols(),
+                item.look_ahead(),
+            );
 
             for rule in self.grammar.rules_by_lhs(lhs) {
-                for look_ahead in look_aheads {
-                    let item = Item::new(rule, 0, *look_ahead);
-                    result.insert(item);
+                for look_ahead in &look_aheads {
+                    result.insert(Item::new(rule, 0, *look_ahead));
                 }
             }
         }
@@ -202,3 +307,24 @@ where
         result
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::syntax::{self, Name};
+
+    #[test]
+    fn finds_conflicts_and_paths() {
+        let file = syntax::parse("Start: E; E: E Plus E | Num;").unwrap();
+        let table = Analyser::table(file.grammar());
+        let automaton = Analyser::new(file.grammar()).automaton();
+
+        assert_eq!(table.conflicts().len(), 1);
+        let conflict = &table.conflicts()[0];
+        assert_eq!(conflict.symbol, Symbol::Terminal(Name::new("Plus")));
+
+        let path = automaton.shortest_path(conflict.state);
+        let path = path.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(path, vec!["E", "Plus", "E"]);
+    }
+}
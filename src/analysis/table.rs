@@ -1,5 +1,6 @@
 use crate::common::{Rule, Symbol};
 use std::collections::HashMap;
+use std::fmt::{self, Debug, Display};
 use std::hash::Hash;
 
 pub type StateIndex = usize;
@@ -14,6 +15,21 @@ pub enum Action<T, NT> {
     Error,
 }
 
+impl<T, NT> Display for Action<T, NT>
+where
+    T: Debug,
+    NT: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Shift(state) => write!(f, "shift {}", state),
+            Self::Reduce(rule) => write!(f, "reduce {}", rule),
+            Self::Accept => write!(f, "accept"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Conflict<T, NT> {
     pub first_action: Action<T, NT>,
@@ -28,6 +44,16 @@ pub struct Table<T, NT> {
     conflicts: Vec<Conflict<T, NT>>,
 }
 
+impl<Term, NonTerm> Default for Table<Term, NonTerm>
+where
+    Term: Eq + Hash + Copy,
+    NonTerm: Eq + Hash + Copy,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<Term, NonTerm> Table<Term, NonTerm>
 where
     Term: Eq + Hash + Copy,
@@ -0,0 +1,359 @@
+//! Derivation of an abstract syntax tree that mirrors the grammar.
+//!
+//! Every non-terminal becomes a type of the same name:
+//!
+//! - a non-terminal with a single rule becomes a struct with a field per symbol,
+//! - a non-terminal with several rules becomes an enum with a variant per rule, named after the name of
+//!   the alternative, or after its symbol when it has only one,
+//! - a list, such as `Args: Args Comma Arg | Arg` or `Items: Item Items | ;`, becomes a `Vec` of its
+//!   elements under an alias of the non-terminal, dropping the separators. Naming or labelling an alternative keeps it from being read as
+//!   a list, so `Sum: Sum Plus Term => add | Term` becomes an enum.
+//!
+//! When a rule labels some of its symbols only those become fields, which is the way to leave out
+//! punctuation. Fields holding a type that contains the type of the field itself are boxed.
+
+use crate::common::{Grammar, Rule, Symbol, Terminal};
+use crate::generator::argument_names;
+use codegen::{Scope, Struct, Variant};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// The shape of a list non-terminal.
+struct List<T, NT> {
+    element: Symbol<T, NT>,
+}
+
+/// Declares the AST types of `grammar` in `scope`, and returns a copy of the grammar with the types and
+/// actions that build them. Terminals are stored as `Token<token_type>`.
+///
+/// Fails when the grammar has actions or types of its own, or when the variants of an enum cannot be
+/// told apart by name.
+pub fn annotate<T, NT>(
+    grammar: &Grammar<T, NT>,
+    scope: &mut Scope,
+    token_type: &str,
+) -> Result<Grammar<T, NT>, String>
+where
+    T: Terminal,
+    NT: Eq + Hash + Copy + Debug,
+{
+    let mut nonterminals = Vec::new();
+    for rule in grammar.rules() {
+        if let Some(action) = rule.action() {
+            return Err(format!(
+                "rule `{}` has the action `{}`, which is not used when generating an AST",
+                rule, action
+            ));
+        }
+        if !nonterminals.contains(&rule.lhs()) {
+            nonterminals.push(rule.lhs());
+        }
+    }
+
+    for &nt in &nonterminals {
+        if grammar.type_of(nt) != "()" {
+            return Err(format!(
+                "`{:?}` declares a type, which is not used when generating an AST",
+                nt
+            ));
+        }
+    }
+
+    let lists = nonterminals
+        .iter()
+        .filter_map(|&nt| list(grammar, nt).map(|list| (nt, list)))
+        .collect::<HashMap<_, _>>();
+    let boxed = boxed_fields(grammar, &lists);
+
+    let field_type = |lhs: NT, sym: &Symbol<T, NT>| match sym {
+        Symbol::Terminal(_) => format!("Token<{}>", token_type),
+        Symbol::NonTerminal(nt) if boxed.contains(&(lhs, *nt)) => format!("Box<{:?}>", nt),
+        Symbol::NonTerminal(nt) => format!("{:?}", nt),
+    };
+    let field_value = |lhs: NT, sym: &Symbol<T, NT>, index: usize| match sym {
+        Symbol::NonTerminal(nt) if boxed.contains(&(lhs, *nt)) => {
+            format!("Box::new(_{})", index + 1)
+        }
+        _ => format!("_{}", index + 1),
+    };
+
+    let mut rules = Vec::new();
+    let mut types = Vec::new();
+
+    for &nt in &nonterminals {
+        let alternatives = grammar.rules_by_lhs(nt);
+        let name = format!("{:?}", nt);
+
+        if let Some(list) = lists.get(&nt) {
+            let element = match list.element {
+                Symbol::Terminal(_) => format!("Token<{}>", token_type),
+                Symbol::NonTerminal(element) => format!("{:?}", element),
+            };
+            scope.raw(&format!("pub type {} = Vec<{}>;", name, element));
+            types.push((nt, name));
+
+            for rule in alternatives {
+                let symbols = rule.symbols();
+                let position = symbols.iter().position(|s| *s == list.element);
+                let action = match (symbols.first(), position) {
+                    (_, None) => "Vec::new()".to_owned(),
+                    (_, Some(i)) if symbols.len() == 1 => format!("vec![_{}]", i + 1),
+                    (Some(Symbol::NonTerminal(first)), Some(i)) if *first == nt => {
+                        format!("{{ let mut list = _1; list.push(_{}); list }}", i + 1)
+                    }
+                    (_, Some(i)) => format!(
+                        "{{ let mut list = _{}; list.insert(0, _{}); list }}",
+                        symbols.len(),
+                        i + 1
+                    ),
+                };
+                rules.push(unlabelled(rule).with_action(action));
+            }
+        } else if let [rule] = alternatives.as_slice() {
+            let mut ast_struct = Struct::new(&name);
+            ast_struct.vis("pub").derive("Debug, Clone, PartialEq");
+
+            let mut values = Vec::new();
+            for (i, field) in fields(rule) {
+                let sym = &rule.symbols()[i];
+                ast_struct.field(&format!("pub {}", field), field_type(nt, sym));
+                values.push(format!("{}: {}", field, field_value(nt, sym, i)));
+            }
+            scope.push_struct(ast_struct);
+
+            let action = if values.is_empty() {
+                name.clone()
+            } else {
+                format!("{} {{ {} }}", name, values.join(", "))
+            };
+            types.push((nt, name));
+            rules.push(unlabelled(rule).with_action(action));
+        } else {
+            let ast_enum = scope
+                .new_enum(&name)
+                .vis("pub")
+                .derive("Debug, Clone, PartialEq");
+            let mut variants = Vec::new();
+
+            for (position, rule) in alternatives.iter().enumerate() {
+                let fields = fields(rule);
+                let variant = match (rule.name(), rule.symbols().as_slice()) {
+                    (Some(name), _) => camel_case(name),
+                    (None, [Symbol::Terminal(sym)]) => format!("{:?}", sym),
+                    (None, [Symbol::NonTerminal(sym)]) => format!("{:?}", sym),
+                    (None, _) => format!("{}{}", name, position + 1),
+                };
+                if variants.contains(&variant) {
+                    return Err(format!(
+                        "rule `{}` needs a name to tell its variant apart from `{}::{}`",
+                        rule, name, variant
+                    ));
+                }
+
+                let mut ast_variant = Variant::new(&variant);
+                let action = match (rule.label(0), rule.symbols().as_slice()) {
+                    (None, [sym]) => {
+                        ast_variant.tuple(&field_type(nt, sym));
+                        format!("{}::{}({})", name, variant, field_value(nt, sym, 0))
+                    }
+                    _ if fields.is_empty() => format!("{}::{}", name, variant),
+                    _ => {
+                        let mut values = Vec::new();
+                        for (i, field) in fields {
+                            let sym = &rule.symbols()[i];
+                            ast_variant.named(&field, field_type(nt, sym));
+                            values.push(format!("{}: {}", field, field_value(nt, sym, i)));
+                        }
+                        format!("{}::{} {{ {} }}", name, variant, values.join(", "))
+                    }
+                };
+
+                ast_enum.push_variant(ast_variant);
+                variants.push(variant);
+                rules.push(unlabelled(rule).with_action(action));
+            }
+
+            types.push((nt, name));
+        }
+    }
+
+    // Keep the rules in the order of the grammar, so they are numbered the same.
+    rules.sort_by_key(|rule| {
+        grammar
+            .rules()
+            .iter()
+            .position(|r| r.lhs() == rule.lhs() && r.symbols() == rule.symbols())
+    });
+
+    let mut annotated = Grammar::new(rules, grammar.start_rule_index());
+    for (nt, ty) in types {
+        annotated.set_type(nt, ty);
+    }
+    Ok(annotated)
+}
+
+/// Recognises a list: a non-terminal with one rule that repeats it by recursion at either end, with an
+/// optional separator, and another rule consisting of just the element, or nothing when there is no separator.
+fn list<T, NT>(grammar: &Grammar<T, NT>, nt: NT) -> Option<List<T, NT>>
+where
+    T: Terminal,
+    NT: Eq + Hash + Copy + Debug,
+{
+    let alternatives = grammar.rules_by_lhs(nt);
+    let this = Symbol::NonTerminal(nt);
+
+    let plain = |rule: &Rule<T, NT>| {
+        rule.name().is_none() && (0..rule.symbols().len()).all(|i| rule.label(i).is_none())
+    };
+    if !alternatives.iter().all(|rule| plain(rule)) {
+        return None;
+    }
+
+    let (recursive, base) = match alternatives.as_slice() {
+        [a, b] if a.symbols().contains(&this) => (a.symbols(), b.symbols()),
+        [a, b] => (b.symbols(), a.symbols()),
+        _ => return None,
+    };
+
+    let (element, separated) = match recursive.as_slice() {
+        [first, element] | [first, _, element] if *first == this => {
+            (*element, recursive.len() == 3)
+        }
+        [element, last] | [element, _, last] if *last == this => (*element, recursive.len() == 3),
+        _ => return None,
+    };
+
+    let valid_base = match base.as_slice() {
+        [] => !separated,
+        [sym] => *sym == element,
+        _ => false,
+    };
+
+    if element == this || !valid_base || (separated && recursive[1] == element) {
+        return None;
+    }
+    Some(List { element })
+}
+
+/// The pairs of a non-terminal and the non-terminal of one of its fields for which the field has to
+/// be boxed, because the type of the field contains the non-terminal itself. Lists do not count, since
+/// a `Vec` already stores its elements on the heap.
+fn boxed_fields<T, NT>(
+    grammar: &Grammar<T, NT>,
+    lists: &HashMap<NT, List<T, NT>>,
+) -> HashSet<(NT, NT)>
+where
+    T: Terminal,
+    NT: Eq + Hash + Copy + Debug,
+{
+    let mut contains: HashMap<NT, HashSet<NT>> = HashMap::new();
+    for rule in grammar.rules() {
+        if lists.contains_key(&rule.lhs()) {
+            continue;
+        }
+        let fields = contains.entry(rule.lhs()).or_default();
+        for (i, _) in self::fields(rule) {
+            if let Symbol::NonTerminal(nt) = rule.symbols()[i] {
+                if !lists.contains_key(&nt) {
+                    fields.insert(nt);
+                }
+            }
+        }
+    }
+
+    let mut boxed = HashSet::new();
+    for (&outer, fields) in &contains {
+        for &field in fields {
+            // Walk the types contained in the field, looking for the outer type.
+            let mut seen = HashSet::new();
+            let mut queue = vec![field];
+            while let Some(nt) = queue.pop() {
+                if nt == outer {
+                    boxed.insert((outer, field));
+                    break;
+                }
+                if seen.insert(nt) {
+                    queue.extend(contains.get(&nt).into_iter().flatten());
+                }
+            }
+        }
+    }
+
+    boxed
+}
+
+/// The symbols of a rule that become fields together with their names: the labelled ones if there
+/// are any, and otherwise all symbols.
+fn fields<T, NT>(rule: &Rule<T, NT>) -> Vec<(usize, String)>
+where
+    T: Copy + Debug,
+    NT: Copy + Debug,
+{
+    let names = argument_names(rule);
+    let labelled = (0..names.len()).any(|i| rule.label(i).is_some());
+
+    names
+        .into_iter()
+        .enumerate()
+        .filter(|&(i, _)| !labelled || rule.label(i).is_some())
+        .collect()
+}
+
+/// A copy of `rule` without labels, so every symbol is bound by position in the generated actions.
+fn unlabelled<T, NT>(rule: &Rule<T, NT>) -> Rule<T, NT>
+where
+    T: Copy,
+    NT: Copy,
+{
+    Rule::new(rule.lhs(), rule.symbols().clone())
+}
+
+fn camel_case(name: &str) -> String {
+    name.split('_')
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .into_iter()
+                .flat_map(char::to_uppercase)
+                .chain(chars)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::syntax::{self, Name};
+
+    #[test]
+    fn derives_structs_enums_and_lists() {
+        let file = syntax::parse(
+            "Start: Expr;
+             Expr: Expr Plus Term => add | Term;
+             Term: Number | LParen Expr RParen => group | name:Identifier LParen args:Args RParen;
+             Args: Args Comma Expr | Expr;",
+        )
+        .unwrap();
+        let mut scope = Scope::new();
+        let grammar = annotate(file.grammar(), &mut scope, "TokenType").unwrap();
+        let code = scope.to_string();
+
+        assert!(code.contains("pub struct Start {\n    pub expr: Expr,\n}"));
+        assert!(code.contains("Add {\n        expr: Box<Expr>,\n        plus: Token<TokenType>,\n        term: Box<Term>,\n    }"));
+        assert!(
+            code.contains("Term3 {\n        name: Token<TokenType>,\n        args: Args,\n    }")
+        );
+        assert!(code.contains("pub type Args = Vec<Expr>;"));
+        assert_eq!(grammar.type_of(Name::new("Args")), "Args");
+        assert_eq!(
+            grammar.rules()[1].action(),
+            Some("Expr::Add { expr: Box::new(_1), plus: _2, term: Box::new(_3) }")
+        );
+        assert_eq!(
+            grammar.rules()[6].action(),
+            Some("{ let mut list = _1; list.push(_3); list }")
+        );
+    }
+}
@@ -1,73 +1,643 @@
-use yaw::analysis::Analyser;
-use yaw::common::{Grammar, Rule, Symbol};
-use yaw::generator::generate;
+use yaw::analysis::{Action, Analyser, Automaton, Conflict, Table};
+use yaw::build::{generate_source, relative_path};
+use yaw::common::{Grammar, Item, Symbol};
+use yaw::dot::{to_dot, Filter};
+use yaw::generator::Options;
+use yaw::lexer::{Lexer, Overlap};
+use yaw::messages::{error_examples, ErrorExample, Messages};
+use yaw::railroad::Diagrams;
+use yaw::report::report;
+use yaw::syntax::{self, Name};
 
 use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process;
 
-#[derive(Copy, Clone, Debug, PartialEq, Hash, Eq)]
-enum TerminalSymbols {
-    Identifier,
-    Separator(&'static str),
-    Eof,
+const USAGE: &str = "\
+usage: cargo yaw <command> [options] <grammar>
+
+commands:
+    generate    write a parser for the grammar
+    check       validate the grammar and report conflicts and overlapping tokens
+    explain     show the items and an example input for a state or conflict
+    states      list the states of the LR(1) automaton
+    report      write a verbose report of the grammar and every state of its automaton
+    dot         export the automaton as a Graphviz graph
+    railroad    write a syntax diagram per non-terminal as SVG, together with an HTML index
+    errors      list a shortest input for every state and terminal the parser finds an error at,
+                as a messages file to fill in or, with `--json`, as a test corpus
+
+options:
+    -o, --output <path>       output file of `generate`, `report`, `dot` or `errors`, defaults to the
+                              grammar with an `.rs` extension for `generate` and to standard output
+                              otherwise,
+                              output directory of `railroad`, defaults to `<grammar>-railroad`
+    --backend <match|table>   encoding of the parse tables in the generated parser
+    --parser-name <name>      name of the generated parser struct, `Parser` by default
+    --token-type <name>       name of the generated token enum, `TokenType` by default
+    --actions-trait <name>    generate a trait with a method per rule instead of running the actions
+                              in the grammar, making the parser generic over its implementation
+    --ast                     generate types mirroring the grammar and a parser that builds them
+    --cst                     generate a parser that builds a lossless syntax tree
+    --events                  generate a parser that returns start node, token and finish node events
+    --trivia <name>           token kind skipped by the parser, such as whitespace, can be repeated
+    --checkpoints             derive `Clone` for the values of the parser, so its state can be saved
+                              and restored
+    --messages <path>         hand-written error messages for `generate`, which `check` verifies to
+                              match the grammar and to cover every state in which errors are found
+    --state <index>           state to explain, or to include in the graph of `dot` when repeated
+    --depth <steps>           include the states reachable within this many steps in the graph of `dot`
+    --conflict <index>        conflict to explain, as numbered by `check`
+    --json                    print machine readable output
+
+exit codes:
+    0   success
+    1   the grammar is invalid or contains conflicts
+    2   invalid usage, or a grammar or messages file that cannot be parsed
+    3   a file could not be read or written";
+
+enum Failure {
+    Invalid(String),
+    Usage(String),
+    /// A grammar or messages file with syntax errors.
+    Malformed(String),
+    Io(io::Error),
+}
+
+impl Failure {
+    fn exit_code(&self) -> i32 {
+        match self {
+            Self::Invalid(_) => 1,
+            Self::Usage(_) | Self::Malformed(_) => 2,
+            Self::Io(_) => 3,
+        }
+    }
 }
 
-impl yaw::common::Terminal for TerminalSymbols {
-    fn eof() -> Self {
-        Self::Eof
+impl From<io::Error> for Failure {
+    fn from(err: io::Error) -> Self {
+        if err.kind() == io::ErrorKind::InvalidData {
+            Self::Invalid(err.to_string())
+        } else {
+            Self::Io(err)
+        }
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-enum NonTerminalSymbols {
-    Expression,
-    FunctionCall,
-    FunctionArgs,
-    Start,
+#[derive(Default)]
+struct Args {
+    command: String,
+    grammar: PathBuf,
+    output: Option<PathBuf>,
+    options: Options,
+    states: Vec<usize>,
+    depth: Option<usize>,
+    conflict: Option<usize>,
+    messages: Option<PathBuf>,
+    json: bool,
 }
 
-use NonTerminalSymbols::*;
-use Symbol::*;
-use TerminalSymbols::*;
+impl Args {
+    fn parse(args: impl Iterator<Item = String>) -> Result<Self, Failure> {
+        // When invoked as `cargo yaw`, cargo passes the subcommand name as first argument.
+        let mut args = args.skip(1).peekable();
+        if args.peek().map(String::as_str) == Some("yaw") {
+            args.next();
+        }
+
+        let mut parsed = Self {
+            command: args
+                .next()
+                .ok_or_else(|| Failure::Usage("missing command".into()))?,
+            ..Self::default()
+        };
+        if ![
+            "generate", "check", "explain", "states", "report", "dot", "railroad", "errors",
+        ]
+        .contains(&parsed.command.as_str())
+        {
+            return Err(Failure::Usage(format!(
+                "unknown command `{}`",
+                parsed.command
+            )));
+        }
+
+        let mut grammar = None;
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| Failure::Usage(format!("missing value for `{}`", arg)))
+            };
+            let index = |value: String| {
+                value
+                    .parse::<usize>()
+                    .map_err(|_| Failure::Usage(format!("`{}` is not a valid index", value)))
+            };
+
+            match arg.as_str() {
+                "-o" | "--output" => parsed.output = Some(value()?.into()),
+                "--backend" => parsed.options.backend = value()?.parse().map_err(Failure::Usage)?,
+                "--parser-name" => parsed.options.parser_name = value()?,
+                "--token-type" => parsed.options.token_type = value()?,
+                "--actions-trait" => parsed.options.actions_trait = Some(value()?),
+                "--ast" => parsed.options.ast = true,
+                "--cst" => parsed.options.cst = true,
+                "--events" => parsed.options.events = true,
+                "--trivia" => parsed.options.trivia.push(value()?),
+                "--checkpoints" => parsed.options.checkpoints = true,
+                "--state" => parsed.states.push(index(value()?)?),
+                "--depth" => parsed.depth = Some(index(value()?)?),
+                "--conflict" => parsed.conflict = Some(index(value()?)?),
+                "--messages" => parsed.messages = Some(value()?.into()),
+                "--json" => parsed.json = true,
+                "-h" | "--help" => return Err(Failure::Usage(String::new())),
+                flag if flag.starts_with('-') => {
+                    return Err(Failure::Usage(format!("unknown option `{}`", flag)))
+                }
+                _ if grammar.is_none() => grammar = Some(PathBuf::from(arg)),
+                _ => return Err(Failure::Usage(format!("unexpected argument `{}`", arg))),
+            }
+        }
+
+        parsed.grammar = grammar.ok_or_else(|| Failure::Usage("missing grammar file".into()))?;
+        Ok(parsed)
+    }
+}
 
 fn main() {
-    let grammar = Grammar::new(
-        vec![
-            Rule::new(Start, vec![NonTerminal(Expression)]),
-            Rule::new(Expression, vec![Terminal(Identifier)]),
-            Rule::new(Expression, vec![NonTerminal(FunctionCall)]),
-            Rule::new(
-                FunctionCall,
-                vec![
-                    NonTerminal(Expression),
-                    Terminal(Separator("(")),
-                    NonTerminal(FunctionArgs),
-                    Terminal(Separator(")")),
-                ],
-            ),
-            Rule::new(
-                FunctionCall,
-                vec![
-                    NonTerminal(Expression),
-                    Terminal(Separator("(")),
-                    Terminal(Separator(")")),
-                ],
-            ),
-            Rule::new(
-                FunctionArgs,
-                vec![
-                    NonTerminal(FunctionArgs),
-                    Terminal(Separator(",")),
-                    NonTerminal(Expression),
-                ],
+    let code = match Args::parse(env::args()).and_then(|args| run(&args)) {
+        Ok(code) => code,
+        Err(Failure::Usage(message)) => {
+            if !message.is_empty() {
+                eprintln!("error: {}\n", message);
+            }
+            eprintln!("{}", USAGE);
+            2
+        }
+        // The output was cut short by its reader, like `head`, so there is nobody to tell.
+        Err(Failure::Io(err)) if err.kind() == io::ErrorKind::BrokenPipe => 0,
+        Err(failure) => {
+            match &failure {
+                Failure::Invalid(message) | Failure::Malformed(message) => {
+                    eprintln!("error: {}", message)
+                }
+                Failure::Io(err) => eprintln!("error: {}", err),
+                Failure::Usage(_) => unreachable!(),
+            }
+            failure.exit_code()
+        }
+    };
+
+    process::exit(code);
+}
+
+fn run(args: &Args) -> Result<i32, Failure> {
+    let source = fs::read_to_string(&args.grammar)?;
+    let messages_source = match &args.messages {
+        Some(path) => Some(fs::read_to_string(path)?),
+        None => None,
+    };
+    let messages = match (&args.messages, &messages_source) {
+        (Some(path), Some(source)) => Some(
+            Messages::parse(source)
+                .map_err(|e| Failure::Malformed(format!("{}:{}", path.display(), e)))?,
+        ),
+        _ => None,
+    };
+
+    let file = syntax::parse(&source)
+        .map_err(|e| Failure::Malformed(format!("{}:{}", args.grammar.display(), e)))?;
+    if args.command == "generate" {
+        return generate(args, &source, messages_source.as_deref(), messages);
+    }
+
+    let grammar = file.grammar();
+    let analyser = Analyser::new(grammar);
+    let automaton = analyser.automaton();
+    let table = Analyser::table(grammar);
+
+    match args.command.as_str() {
+        "check" => {
+            let overlaps = if file.tokens().is_empty() {
+                Vec::new()
+            } else {
+                Lexer::compile(file.tokens())
+                    .map_err(|e| Failure::Invalid(format!("{}: {}", args.grammar.display(), e)))?
+                    .overlaps()
+            };
+            check(
+                args,
+                grammar,
+                &automaton,
+                &table,
+                &overlaps,
+                messages.as_ref(),
+            )
+        }
+        "explain" => explain(args, &automaton, &table),
+        "report" => write_output(args, &report(grammar)),
+        "errors" => errors(args, &automaton, &table),
+        "dot" => {
+            if let Some(state) = args.states.iter().find(|&&s| s >= automaton.states.len()) {
+                return Err(Failure::Usage(format!("there is no state {}", state)));
+            }
+            let filter = match (args.states.is_empty(), args.depth) {
+                (true, None) => Filter::All,
+                (true, Some(steps)) => Filter::Reachable {
+                    from: vec![0],
+                    steps,
+                },
+                (false, None) => Filter::States(args.states.clone()),
+                (false, Some(steps)) => Filter::Reachable {
+                    from: args.states.clone(),
+                    steps,
+                },
+            };
+            write_output(args, &to_dot(grammar, &filter))
+        }
+        "railroad" => {
+            let dir = args.output.clone().unwrap_or_else(|| {
+                let stem = args
+                    .grammar
+                    .file_stem()
+                    .unwrap_or_default()
+                    .to_string_lossy();
+                args.grammar.with_file_name(format!("{}-railroad", stem))
+            });
+            Diagrams::new(grammar).write(&dir)?;
+            Ok(0)
+        }
+        _ => states(args, &automaton),
+    }
+}
+
+fn generate(
+    args: &Args,
+    source: &str,
+    messages_source: Option<&str>,
+    messages: Option<Messages>,
+) -> Result<i32, Failure> {
+    let mut out = io::stdout();
+    let output = args
+        .output
+        .clone()
+        .unwrap_or_else(|| args.grammar.with_extension("rs"));
+
+    // The generated parser includes its grammar and messages relative to its own location.
+    let output_dir = match output.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    fs::create_dir_all(&output_dir)?;
+    let output_dir = fs::canonicalize(&output_dir)?;
+    let include_path = relative_path(&output_dir, &fs::canonicalize(&args.grammar)?);
+    let messages_include = match &args.messages {
+        Some(path) => Some(relative_path(&output_dir, &fs::canonicalize(path)?)),
+        None => None,
+    };
+
+    let options = Options {
+        messages,
+        ..args.options.clone()
+    };
+    let messages_file = messages_source.zip(messages_include.as_deref());
+    let generated =
+        generate_source(source, &include_path, messages_file, &options).map_err(|e| {
+            Failure::from(io::Error::new(
+                e.kind(),
+                format!("{}: {}", args.grammar.display(), e),
+            ))
+        })?;
+    fs::write(&output, generated)?;
+
+    if args.json {
+        writeln!(
+            out,
+            "{{\"output\":{}}}",
+            json_str(&output.display().to_string())
+        )?;
+    } else {
+        writeln!(out, "wrote {}", output.display())?;
+    }
+
+    Ok(0)
+}
+
+fn check(
+    args: &Args,
+    grammar: &Grammar<Name, Name>,
+    automaton: &Automaton<Name, Name>,
+    table: &Table<Name, Name>,
+    overlaps: &[Overlap],
+    messages: Option<&Messages>,
+) -> Result<i32, Failure> {
+    let mut out = io::stdout();
+    let conflicts = table.conflicts();
+
+    // The sentences that no longer match, and an example of each state in which errors are found
+    // without a message.
+    let (invalid, uncovered) = match messages.map(|m| m.resolve(grammar, table)) {
+        Some(Ok(resolved)) => {
+            let mut uncovered: Vec<ErrorExample<Name>> = Vec::new();
+            for example in error_examples(automaton, table) {
+                let covered = resolved.iter().any(|r| r.0 == example.state);
+                if !covered && uncovered.last().is_none_or(|e| e.state != example.state) {
+                    uncovered.push(example);
+                }
+            }
+            (Vec::new(), uncovered)
+        }
+        Some(Err(invalid)) => (invalid, Vec::new()),
+        None => (Vec::new(), Vec::new()),
+    };
+
+    if args.json {
+        let messages = match messages {
+            Some(_) => format!(
+                ",\"messages\":{{\"invalid\":{},\"uncovered\":{}}}",
+                json_list(invalid.iter().map(|i| json_str(i))),
+                json_list(uncovered.iter().map(|example| format!(
+                    "{{\"state\":{},\"example\":{}}}",
+                    example.state,
+                    json_str(&sentence(&example.sentence))
+                )))
             ),
-            Rule::new(FunctionArgs, vec![NonTerminal(Expression)]),
-        ],
-        0,
-    );
-
-    let args = env::args();
-    let file_name = args.skip(2).next().expect("No file name specified");
-	let table = Analyser::table(&grammar);
-	println!("{:?}", table.conflicts());
-    generate(&file_name, &table).unwrap();
+            None => String::new(),
+        };
+        writeln!(
+            out,
+            "{{\"rules\":{},\"states\":{},\"conflicts\":{},\"overlaps\":{}{}}}",
+            grammar.rules().len(),
+            automaton.states.len(),
+            json_list(conflicts.iter().map(conflict_json)),
+            json_list(overlaps.iter().map(overlap_json)),
+            messages
+        )?;
+    } else {
+        for (i, conflict) in conflicts.iter().enumerate() {
+            writeln!(out, "conflict {}: {}", i, describe_conflict(conflict))?;
+        }
+        for overlap in overlaps {
+            writeln!(out, "warning: {}", overlap)?;
+        }
+        for error in &invalid {
+            writeln!(out, "messages: {}", error)?;
+        }
+        for example in &uncovered {
+            writeln!(
+                out,
+                "messages: state {} has no message, it rejects `{}`",
+                example.state,
+                sentence(&example.sentence)
+            )?;
+        }
+        writeln!(
+            out,
+            "{}: {} rules, {} states, {} conflicts",
+            args.grammar.display(),
+            grammar.rules().len(),
+            automaton.states.len(),
+            conflicts.len()
+        )?;
+    }
+
+    if conflicts.is_empty() && invalid.is_empty() && uncovered.is_empty() {
+        Ok(0)
+    } else {
+        Ok(1)
+    }
+}
+
+fn explain(
+    args: &Args,
+    automaton: &Automaton<Name, Name>,
+    table: &Table<Name, Name>,
+) -> Result<i32, Failure> {
+    let mut out = io::stdout();
+    let (state, conflict) = match (args.states.as_slice(), args.conflict) {
+        (&[state], None) => (state, None),
+        (&[], Some(index)) => {
+            let conflict = table
+                .conflicts()
+                .get(index)
+                .ok_or_else(|| Failure::Usage(format!("there is no conflict {}", index)))?;
+            (conflict.state, Some(conflict))
+        }
+        _ => {
+            return Err(Failure::Usage(
+                "expected either `--state` or `--conflict`".into(),
+            ))
+        }
+    };
+
+    if state >= automaton.states.len() {
+        return Err(Failure::Usage(format!("there is no state {}", state)));
+    }
+
+    let path = automaton.shortest_path(state);
+    let items = automaton.items(state);
+
+    // For a conflict, the example input ends in the terminal both actions are competing for.
+    let (counterexample, involved) = match conflict {
+        Some(conflict) => {
+            let involved = items
+                .iter()
+                .filter(|item| involved_in(item, conflict))
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            (
+                format!("{} . {}", symbols(&path), conflict.symbol),
+                involved,
+            )
+        }
+        None => (format!("{} .", symbols(&path)), Vec::new()),
+    };
+
+    if args.json {
+        writeln!(
+            out,
+            "{{\"state\":{},\"example\":{},\"items\":{},\"conflict\":{},\"involved\":{}}}",
+            state,
+            json_str(&counterexample),
+            json_list(items.iter().map(|i| json_str(&i.to_string()))),
+            conflict.map_or_else(|| "null".into(), conflict_json),
+            json_list(involved.iter().map(|i| json_str(i))),
+        )?;
+    } else {
+        writeln!(out, "state {}", state)?;
+        writeln!(out, "  example: {}", counterexample.trim())?;
+        writeln!(out, "  items:")?;
+        for item in &items {
+            writeln!(out, "    {}", item)?;
+        }
+        if let Some(conflict) = conflict {
+            writeln!(out, "  conflict: {}", describe_conflict(conflict))?;
+            writeln!(out, "  caused by:")?;
+            for item in &involved {
+                writeln!(out, "    {}", item)?;
+            }
+        }
+    }
+
+    Ok(0)
+}
+
+fn errors(
+    args: &Args,
+    automaton: &Automaton<Name, Name>,
+    table: &Table<Name, Name>,
+) -> Result<i32, Failure> {
+    let examples = error_examples(automaton, table);
+
+    if args.json {
+        let examples = examples.iter().map(|example| {
+            format!(
+                "{{\"state\":{},\"sentence\":{}}}",
+                example.state,
+                json_list(example.sentence.iter().map(|t| json_str(t.as_str())))
+            )
+        });
+        write_output(args, &format!("{}\n", json_list(examples)))
+    } else {
+        let template = Messages::template(&examples, "<YOUR SYNTAX ERROR MESSAGE HERE>");
+        write_output(args, &template.to_string())
+    }
+}
+
+fn write_output(args: &Args, contents: &str) -> Result<i32, Failure> {
+    match &args.output {
+        Some(output) => fs::write(output, contents)?,
+        None => {
+            let mut out = io::stdout();
+            out.write_all(contents.as_bytes())?;
+            out.flush()?;
+        }
+    }
+    Ok(0)
+}
+
+fn states(args: &Args, automaton: &Automaton<Name, Name>) -> Result<i32, Failure> {
+    let mut out = io::stdout();
+    if args.json {
+        let states = (0..automaton.states.len()).map(|state| {
+            format!(
+                "{{\"state\":{},\"kernel\":{},\"items\":{},\"transitions\":{}}}",
+                state,
+                json_list(
+                    automaton
+                        .kernel(state)
+                        .iter()
+                        .map(|i| json_str(&i.to_string()))
+                ),
+                json_list(
+                    automaton
+                        .items(state)
+                        .iter()
+                        .map(|i| json_str(&i.to_string()))
+                ),
+                json_list(automaton.edges(state).iter().map(|(sym, to_state)| {
+                    format!(
+                        "{{\"symbol\":{},\"state\":{}}}",
+                        json_str(&sym.to_string()),
+                        to_state
+                    )
+                }))
+            )
+        });
+        writeln!(out, "{}", json_list(states))?;
+    } else {
+        for state in 0..automaton.states.len() {
+            writeln!(out, "state {}", state)?;
+            for item in automaton.kernel(state) {
+                writeln!(out, "    {}", item)?;
+            }
+            for (sym, to_state) in automaton.edges(state) {
+                writeln!(out, "    {} => state {}", sym, to_state)?;
+            }
+            writeln!(out)?;
+        }
+    }
+
+    Ok(0)
+}
+
+/// Whether `item` gives rise to one of the actions of `conflict`.
+fn involved_in(item: &Item<Name, Name>, conflict: &Conflict<Name, Name>) -> bool {
+    let causes = |action: &Action<Name, Name>| match action {
+        Action::Shift(_) => item.active_symbol() == Some(conflict.symbol),
+        Action::Reduce(rule) => {
+            !item.is_active()
+                && item.rule() == rule
+                && Some(item.look_ahead()) == conflict.symbol.terminal()
+        }
+        Action::Accept => {
+            !item.is_active() && Some(item.look_ahead()) == conflict.symbol.terminal()
+        }
+        Action::Error => false,
+    };
+
+    causes(&conflict.first_action) || causes(&conflict.second_action)
+}
+
+fn describe_conflict(conflict: &Conflict<Name, Name>) -> String {
+    format!(
+        "state {} on `{}` between {} and {}",
+        conflict.state, conflict.symbol, conflict.first_action, conflict.second_action
+    )
+}
+
+fn conflict_json(conflict: &Conflict<Name, Name>) -> String {
+    format!(
+        "{{\"state\":{},\"symbol\":{},\"actions\":[{},{}]}}",
+        conflict.state,
+        json_str(&conflict.symbol.to_string()),
+        json_str(&conflict.first_action.to_string()),
+        json_str(&conflict.second_action.to_string())
+    )
+}
+
+fn overlap_json(overlap: &Overlap) -> String {
+    format!(
+        "{{\"winner\":{},\"loser\":{},\"example\":{}}}",
+        json_str(&overlap.winner),
+        json_str(&overlap.loser),
+        json_str(&overlap.example)
+    )
+}
+
+fn symbols(path: &[Symbol<Name, Name>]) -> String {
+    path.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn sentence(terminals: &[Name]) -> String {
+    terminals
+        .iter()
+        .map(Name::as_str)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn json_str(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_list(items: impl Iterator<Item = String>) -> String {
+    format!("[{}]", items.collect::<Vec<_>>().join(","))
 }
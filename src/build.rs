@@ -0,0 +1,445 @@
+//! Integration with cargo build scripts.
+//!
+//! Calling [`process_root`] from `build.rs` generates a parser for every `.yaw` file under `src`
+//! into `OUT_DIR`, after which it can be included with
+//! `include!(concat!(env!("OUT_DIR"), "/calc.rs"));`.
+//!
+//! Every generated file embeds a hash of its grammar and the version of yaw that generated it.
+//! The hash is used to skip regenerating parsers that are up to date, and is checked again when the
+//! parser is compiled so that a parser which is out of date with its grammar fails to build.
+//!
+//! A grammar can have hand-written error messages in a file next to it with the `.messages`
+//! extension, see [`crate::messages`]. They are checked against the grammar when the parser is
+//! generated, and their hash is embedded and checked at compile time as well.
+
+use crate::analysis::Analyser;
+use crate::generator::{render, Options};
+use crate::lexer::{Lexer, Pattern};
+use crate::messages::Messages;
+use crate::syntax;
+use std::fs;
+use std::io::{self, Error, ErrorKind};
+use std::path::{Component, Path, PathBuf};
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const HASH_PREFIX: &str = "// yaw-hash: ";
+const MESSAGES_HASH_PREFIX: &str = "// yaw-messages-hash: ";
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+const fn fnv(mut hash: u64, bytes: &[u8]) -> u64 {
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+        i += 1;
+    }
+    hash
+}
+
+/// Hashes the source of a grammar together with the version of yaw.
+pub const fn grammar_hash(source: &str) -> u64 {
+    let hash = fnv(FNV_OFFSET, VERSION.as_bytes());
+    let hash = fnv(hash, &[0]);
+    fnv(hash, source.as_bytes())
+}
+
+/// Called from generated parsers in a constant, failing compilation when the grammar or its messages
+/// have changed or the parser was generated by another version of yaw.
+pub const fn assert_fresh(hash: u64, source: &str) {
+    if grammar_hash(source) != hash {
+        panic!("parser is out of date with its grammar, messages or yaw version, regenerate it");
+    }
+}
+
+/// Renders a parser for the grammar in `source`, prefixed with a header containing its hash.
+/// The generated code includes the grammar from `include_path` to verify the hash at compile time.
+/// `messages` is the source of the messages in `options` and the path to include it from, so that
+/// it is verified the same way.
+///
+/// When the grammar defines tokens, a lexer is generated after the parser. Defined tokens that the
+/// rules do not use are trivia.
+pub fn generate_source(
+    source: &str,
+    include_path: &Path,
+    messages: Option<(&str, &Path)>,
+    options: &Options,
+) -> io::Result<String> {
+    let file = syntax::parse(source).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+    let table = Analyser::table(file.grammar());
+
+    if !table.conflicts().is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("grammar contains {} conflicts", table.conflicts().len()),
+        ));
+    }
+
+    let mut options = options.clone();
+    let lexer = if file.tokens().is_empty() {
+        None
+    } else {
+        let terminals = file.grammar().unique_terminals();
+        for token in file.tokens() {
+            if !terminals.contains(&syntax::Name::new(&token.name))
+                && !options.trivia.contains(&token.name)
+            {
+                options.trivia.push(token.name.clone());
+            }
+            if let Pattern::Literal(text) = &token.pattern {
+                options.literals.push((token.name.clone(), text.clone()));
+            }
+        }
+        Some(Lexer::compile(file.tokens()).map_err(|e| Error::new(ErrorKind::InvalidData, e))?)
+    };
+
+    let mut parser = render(file.grammar(), &table, &options)
+        .map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+    if let Some(lexer) = lexer {
+        parser.push('\n');
+        parser.push_str(&lexer.render(&options.token_type, &options.trivia));
+    }
+    let hash = grammar_hash(source);
+    let (messages_header, messages_check) = match messages {
+        Some((messages, path)) => {
+            let hash = grammar_hash(messages);
+            (
+                format!("{}{:016x}\n", MESSAGES_HASH_PREFIX, hash),
+                format!(
+                    "pub const MESSAGES_HASH: u64 = 0x{:016x};\n\
+                     const _: () = yaw::build::assert_fresh(MESSAGES_HASH, include_str!({:?}));\n",
+                    hash,
+                    path.display().to_string()
+                ),
+            )
+        }
+        None => (String::new(), String::new()),
+    };
+    Ok(format!(
+        "// @generated by yaw {}, do not edit.\n\
+         {}{:016x}\n\
+         {}\
+         pub const GRAMMAR_HASH: u64 = 0x{:016x};\n\
+         const _: () = yaw::build::assert_fresh(GRAMMAR_HASH, include_str!({:?}));\n\
+         {}\n{}",
+        VERSION,
+        HASH_PREFIX,
+        hash,
+        messages_header,
+        hash,
+        include_path.display().to_string(),
+        messages_check,
+        parser
+    ))
+}
+
+/// Reads the hash from the header of a previously generated parser.
+pub fn embedded_hash(generated: &str) -> Option<u64> {
+    header_hash(generated, HASH_PREFIX)
+}
+
+/// Reads the hash of the messages from the header of a previously generated parser, if it had any.
+pub fn embedded_messages_hash(generated: &str) -> Option<u64> {
+    header_hash(generated, MESSAGES_HASH_PREFIX)
+}
+
+fn header_hash(generated: &str, prefix: &str) -> Option<u64> {
+    generated
+        .lines()
+        .take_while(|line| line.starts_with("//"))
+        .find_map(|line| line.strip_prefix(prefix))
+        .and_then(|hash| u64::from_str_radix(hash.trim(), 16).ok())
+}
+
+/// Processes all grammar files under `src` with the default [`Configuration`].
+pub fn process_root() -> io::Result<()> {
+    Configuration::new().process()
+}
+
+pub struct Configuration {
+    in_dir: PathBuf,
+    out_dir: Option<PathBuf>,
+    force_build: bool,
+    emit_rerun_directives: bool,
+}
+
+impl Default for Configuration {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Configuration {
+    pub fn new() -> Self {
+        Self {
+            in_dir: PathBuf::from("src"),
+            out_dir: None,
+            force_build: false,
+            emit_rerun_directives: true,
+        }
+    }
+
+    /// The directory that is searched for `.yaw` files, `src` by default.
+    pub fn set_in_dir(&mut self, dir: impl Into<PathBuf>) -> &mut Self {
+        self.in_dir = dir.into();
+        self
+    }
+
+    /// The directory parsers are generated into, `OUT_DIR` by default.
+    pub fn set_out_dir(&mut self, dir: impl Into<PathBuf>) -> &mut Self {
+        self.out_dir = Some(dir.into());
+        self
+    }
+
+    /// Regenerate parsers even when their hash is unchanged.
+    pub fn force_build(&mut self, force: bool) -> &mut Self {
+        self.force_build = force;
+        self
+    }
+
+    /// Print `cargo:rerun-if-changed` lines for the processed grammars.
+    pub fn emit_rerun_directives(&mut self, emit: bool) -> &mut Self {
+        self.emit_rerun_directives = emit;
+        self
+    }
+
+    fn out_dir(&self) -> io::Result<PathBuf> {
+        match &self.out_dir {
+            Some(dir) => Ok(dir.clone()),
+            None => std::env::var_os("OUT_DIR")
+                .map(PathBuf::from)
+                .ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::NotFound,
+                        "OUT_DIR is not set, call yaw from build.rs",
+                    )
+                }),
+        }
+    }
+
+    /// Generates a parser for every grammar file found under the input directory.
+    pub fn process(&self) -> io::Result<()> {
+        if self.emit_rerun_directives {
+            println!("cargo:rerun-if-changed={}", self.in_dir.display());
+        }
+
+        let mut grammars = Vec::new();
+        find_grammars(&self.in_dir, &mut grammars)?;
+        grammars.sort();
+
+        for grammar in grammars {
+            self.process_file(&grammar)?;
+        }
+
+        Ok(())
+    }
+
+    /// Generates a parser for a single grammar file, returning the path of the generated parser.
+    pub fn process_file(&self, grammar: &Path) -> io::Result<PathBuf> {
+        if self.emit_rerun_directives {
+            println!("cargo:rerun-if-changed={}", grammar.display());
+        }
+
+        let relative = grammar.strip_prefix(&self.in_dir).unwrap_or(grammar);
+        let output = self.out_dir()?.join(relative).with_extension("rs");
+        let source = fs::read_to_string(grammar)?;
+
+        let messages_path = grammar.with_extension("messages");
+        let mut options = Options::default();
+        let mut messages_source = None;
+        if messages_path.is_file() {
+            if self.emit_rerun_directives {
+                println!("cargo:rerun-if-changed={}", messages_path.display());
+            }
+            let source = fs::read_to_string(&messages_path)?;
+            let messages = Messages::parse(&source).map_err(|e| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("{}: {}", messages_path.display(), e),
+                )
+            })?;
+            options.messages = Some(messages);
+            messages_source = Some(source);
+        }
+
+        if !self.force_build {
+            if let Ok(existing) = fs::read_to_string(&output) {
+                let messages_hash = messages_source.as_deref().map(grammar_hash);
+                if embedded_hash(&existing) == Some(grammar_hash(&source))
+                    && embedded_messages_hash(&existing) == messages_hash
+                {
+                    return Ok(output);
+                }
+            }
+        }
+
+        // The parser includes its grammar relative to its own location, so it compiles on any machine.
+        let output_dir = match output.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        fs::create_dir_all(output_dir)?;
+        let output_dir = fs::canonicalize(output_dir)?;
+        let include_path = relative_path(&output_dir, &fs::canonicalize(grammar)?);
+        let messages_include = match messages_source {
+            Some(_) => Some(relative_path(
+                &output_dir,
+                &fs::canonicalize(&messages_path)?,
+            )),
+            None => None,
+        };
+        let messages = messages_source.as_deref().zip(messages_include.as_deref());
+        let generated = generate_source(&source, &include_path, messages, &options)
+            .map_err(|e| Error::new(e.kind(), format!("{}: {}", grammar.display(), e)))?;
+        fs::write(&output, generated)?;
+
+        Ok(output)
+    }
+}
+
+/// The path to `to` relative to the directory `from`, both of which must be absolute.
+pub fn relative_path(from: &Path, to: &Path) -> PathBuf {
+    let from = from.components().collect::<Vec<_>>();
+    let to = to.components().collect::<Vec<_>>();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut path = PathBuf::new();
+    for _ in common..from.len() {
+        path.push(Component::ParentDir);
+    }
+    for component in &to[common..] {
+        path.push(component);
+    }
+    path
+}
+
+fn find_grammars(dir: &Path, found: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            find_grammars(&path, found)?;
+        } else if path.extension().is_some_and(|ext| ext == "yaw") {
+            found.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const GRAMMAR: &str = "Start: List; List: List Item | Item;";
+
+    #[test]
+    fn skips_unchanged_grammars() {
+        let dir = std::env::temp_dir().join(format!("yaw-build-{}", std::process::id()));
+        let (src, out) = (dir.join("src"), dir.join("out"));
+        fs::create_dir_all(src.join("nested")).unwrap();
+        fs::write(src.join("nested/list.yaw"), GRAMMAR).unwrap();
+
+        let mut config = Configuration::new();
+        config
+            .set_in_dir(&src)
+            .set_out_dir(&out)
+            .emit_rerun_directives(false);
+        config.process().unwrap();
+
+        let output = out.join("nested/list.rs");
+        let generated = fs::read_to_string(&output).unwrap();
+        assert_eq!(embedded_hash(&generated), Some(grammar_hash(GRAMMAR)));
+        // The grammar is included relative to the parser, so the parser compiles on other machines.
+        assert!(generated.contains("include_str!(\"../../src/nested/list.yaw\")"));
+        assert!(!generated.contains(dir.to_str().unwrap()));
+
+        // An up to date parser is left alone, even when its body was changed.
+        fs::write(&output, generated.replace("Parser", "Renamed")).unwrap();
+        config.process().unwrap();
+        assert!(fs::read_to_string(&output).unwrap().contains("Renamed"));
+
+        config.force_build(true).process().unwrap();
+        assert!(!fs::read_to_string(&output).unwrap().contains("Renamed"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn regenerates_when_messages_change() {
+        let dir = std::env::temp_dir().join(format!("yaw-messages-{}", std::process::id()));
+        let (src, out) = (dir.join("src"), dir.join("out"));
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("list.yaw"), GRAMMAR).unwrap();
+        fs::write(src.join("list.messages"), "Eof\n\nNo items.\n").unwrap();
+
+        let mut config = Configuration::new();
+        config
+            .set_in_dir(&src)
+            .set_out_dir(&out)
+            .emit_rerun_directives(false);
+        let output = config.process_file(&src.join("list.yaw")).unwrap();
+        assert!(fs::read_to_string(&output)
+            .unwrap()
+            .contains("\"No items.\""));
+
+        fs::write(src.join("list.messages"), "Eof\n\nAn empty list.\n").unwrap();
+        config.process().unwrap();
+        assert!(fs::read_to_string(&output)
+            .unwrap()
+            .contains("\"An empty list.\""));
+
+        // Sentences that do not end in an error are rejected.
+        fs::write(src.join("list.messages"), "Item\n\nAn empty list.\n").unwrap();
+        let error = config.process().unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert!(error
+            .to_string()
+            .contains("line 1: the sentence is not rejected"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn includes_messages_to_check_them() {
+        let dir = std::env::temp_dir().join(format!("yaw-included-{}", std::process::id()));
+        let (src, out) = (dir.join("src"), dir.join("out"));
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("list.yaw"), GRAMMAR).unwrap();
+        let messages = "# Comments are part of the file that is checked.\nEof\n\nNo items.\n";
+        fs::write(src.join("list.messages"), messages).unwrap();
+
+        let mut config = Configuration::new();
+        config
+            .set_in_dir(&src)
+            .set_out_dir(&out)
+            .emit_rerun_directives(false);
+        let output = config.process_file(&src.join("list.yaw")).unwrap();
+        let generated = fs::read_to_string(&output).unwrap();
+        let hash = grammar_hash(messages);
+        assert_eq!(embedded_messages_hash(&generated), Some(hash));
+        assert!(generated.contains(&format!("pub const MESSAGES_HASH: u64 = 0x{:016x};", hash)));
+        assert!(generated
+            .contains("assert_fresh(MESSAGES_HASH, include_str!(\"../src/list.messages\"))"));
+        assert_fresh(hash, messages);
+
+        // The file is hashed as it is written, so even a change to a comment regenerates the parser.
+        fs::write(
+            src.join("list.messages"),
+            messages.replace("Comments", "Notes"),
+        )
+        .unwrap();
+        fs::write(&output, generated.replace("Parser", "Renamed")).unwrap();
+        config.process().unwrap();
+        assert!(!fs::read_to_string(&output).unwrap().contains("Renamed"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn hash_depends_on_source() {
+        assert_ne!(grammar_hash(GRAMMAR), grammar_hash("Start: Item;"));
+        assert_fresh(grammar_hash(GRAMMAR), GRAMMAR);
+    }
+}
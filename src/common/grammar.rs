@@ -5,6 +5,7 @@ use std::hash::Hash;
 pub struct Grammar<T, NT> {
     rules: Vec<Rule<T, NT>>,
     start_rule: usize,
+    types: HashMap<NT, String>,
 }
 
 pub type FirstSet<T, NT> = HashMap<Symbol<T, NT>, HashSet<T>>;
@@ -15,7 +16,20 @@ where
     NT: Eq + Hash + Copy,
 {
     pub fn new(rules: Vec<Rule<T, NT>>, start_rule: usize) -> Self {
-        Self { rules, start_rule }
+        Self {
+            rules,
+            start_rule,
+            types: HashMap::new(),
+        }
+    }
+
+    /// Declares the Rust type of the value of a non-terminal, which is `()` when left undeclared.
+    pub fn set_type(&mut self, nonterminal: NT, ty: impl Into<String>) {
+        self.types.insert(nonterminal, ty.into());
+    }
+
+    pub fn type_of(&self, nonterminal: NT) -> &str {
+        self.types.get(&nonterminal).map_or("()", String::as_str)
     }
 
     pub fn rules_by_lhs(&self, lhs: NT) -> Vec<&Rule<T, NT>> {
@@ -30,6 +44,15 @@ where
         &self.rules[self.start_rule]
     }
 
+    pub fn start_rule_index(&self) -> usize {
+        self.start_rule
+    }
+
+    /// Returns the position of `rule` in this grammar, comparing by identity rather than content.
+    pub fn rule_index(&self, rule: &Rule<T, NT>) -> Option<usize> {
+        self.rules.iter().position(|r| std::ptr::eq(r, rule))
+    }
+
     fn unique_symbols(&self) -> HashSet<Symbol<T, NT>> {
         self.rules
             .iter()
@@ -42,25 +65,50 @@ where
             .collect()
     }
 
-    fn unique_terminals(&self) -> HashSet<T> {
+    pub fn unique_terminals(&self) -> HashSet<T> {
         self.unique_symbols()
             .iter()
             .flat_map(|s| s.terminal())
             .collect()
     }
 
-    fn unique_nonterminals(&self) -> HashSet<NT> {
+    pub fn unique_nonterminals(&self) -> HashSet<NT> {
         self.unique_symbols()
             .iter()
             .flat_map(|s| s.non_terminal())
             .collect()
     }
 
+    /// The set of non-terminals that can derive the empty string.
+    pub fn nullable(&self) -> HashSet<NT> {
+        let mut nullable = HashSet::new();
+
+        loop {
+            let mut updated = false;
+
+            for rule in &self.rules {
+                if !nullable.contains(&rule.lhs())
+                    && rule.symbols().iter().all(|sym| match sym {
+                        Symbol::NonTerminal(nt) => nullable.contains(nt),
+                        Symbol::Terminal(_) => false,
+                    })
+                {
+                    nullable.insert(rule.lhs());
+                    updated = true;
+                }
+            }
+
+            if !updated {
+                break nullable;
+            }
+        }
+    }
+
     pub fn first_set(&self) -> HashMap<Symbol<T, NT>, HashSet<T>> {
         let mut first_set = HashMap::new();
         let terms = self.unique_terminals();
         let nonterms = self.unique_nonterminals();
-        let mut empty = HashSet::new();
+        let nullable = self.nullable();
 
         for nonterm in nonterms {
             first_set.insert(Symbol::NonTerminal(nonterm), HashSet::new());
@@ -75,21 +123,19 @@ where
 
             for rule in &self.rules {
                 let mut lhs_set = first_set[&rule.lhs_as_sym()].clone();
-                let mut all_empty = false;
+                let size = lhs_set.len();
 
+                // Every symbol contributes its first set until one is found that cannot be empty.
                 for sym in rule.symbols() {
-                    updated = first_set[sym].is_subset(&lhs_set);
                     lhs_set.extend(&first_set[sym]);
-                    if !empty.contains(sym) {
-                        all_empty = true;
-                        break;
-                    }
-                }
 
-                if !all_empty {
-                    updated = empty.insert(rule.lhs_as_sym());
+                    match sym {
+                        Symbol::NonTerminal(nt) if nullable.contains(nt) => {}
+                        _ => break,
+                    }
                 }
 
+                updated |= lhs_set.len() != size;
                 first_set.insert(rule.lhs_as_sym(), lhs_set);
             }
 
@@ -100,4 +146,156 @@ where
 
         first_set
     }
+
+    /// For every non-terminal, the terminals that can directly follow it in a sentence.
+    pub fn follow_set(&self) -> HashMap<NT, HashSet<T>> {
+        let first_set = self.first_set();
+        let nullable = self.nullable();
+        let mut follow_set = self
+            .unique_nonterminals()
+            .into_iter()
+            .map(|nt| (nt, HashSet::new()))
+            .collect::<HashMap<_, _>>();
+
+        follow_set
+            .get_mut(&self.start_rule().lhs())
+            .unwrap()
+            .insert(T::eof());
+
+        loop {
+            let mut updated = false;
+
+            for rule in &self.rules {
+                let symbols = rule.symbols();
+
+                for (i, sym) in symbols.iter().enumerate() {
+                    if let Symbol::NonTerminal(nt) = sym {
+                        // Everything that can start the rest of the rule follows this symbol,
+                        // and when the rest can be empty, so does everything that follows the lhs.
+                        // The end of input never appears in a rule, so it is only returned when the rest can be empty.
+                        let rest = &symbols[i + 1..];
+                        let mut follows = Self::first_of(&first_set, &nullable, rest, T::eof());
+
+                        if follows.remove(&T::eof()) {
+                            follows.extend(&follow_set[&rule.lhs()]);
+                        }
+
+                        let set = follow_set.get_mut(nt).unwrap();
+                        let size = set.len();
+                        set.extend(follows);
+                        updated |= set.len() != size;
+                    }
+                }
+            }
+
+            if !updated {
+                break follow_set;
+            }
+        }
+    }
+
+    /// The non-terminals that can derive at least one sentence consisting only of terminals.
+    pub fn productive(&self) -> HashSet<NT> {
+        let mut productive = HashSet::new();
+
+        loop {
+            let mut updated = false;
+
+            for rule in &self.rules {
+                if !productive.contains(&rule.lhs())
+                    && rule.symbols().iter().all(|sym| match sym {
+                        Symbol::NonTerminal(nt) => productive.contains(nt),
+                        Symbol::Terminal(_) => true,
+                    })
+                {
+                    productive.insert(rule.lhs());
+                    updated = true;
+                }
+            }
+
+            if !updated {
+                break productive;
+            }
+        }
+    }
+
+    /// A shortest sentence of terminals derived from each productive non-terminal. The error terminal
+    /// does not stand for any input, so rules containing it are left out.
+    pub fn shortest_yields(&self) -> HashMap<NT, Vec<T>> {
+        let mut yields: HashMap<NT, Vec<T>> = HashMap::new();
+
+        // Every update shortens a sentence, so this ends.
+        loop {
+            let mut updated = false;
+
+            for rule in &self.rules {
+                let mut sentence = Vec::new();
+                let derived = rule.symbols().iter().all(|sym| match sym {
+                    Symbol::Terminal(term) if term.is_error() => false,
+                    Symbol::Terminal(term) => {
+                        sentence.push(*term);
+                        true
+                    }
+                    Symbol::NonTerminal(nt) => match yields.get(nt) {
+                        Some(derived) => {
+                            sentence.extend(derived);
+                            true
+                        }
+                        None => false,
+                    },
+                });
+
+                if derived
+                    && yields
+                        .get(&rule.lhs())
+                        .is_none_or(|shortest| sentence.len() < shortest.len())
+                {
+                    yields.insert(rule.lhs(), sentence);
+                    updated = true;
+                }
+            }
+
+            if !updated {
+                break yields;
+            }
+        }
+    }
+
+    /// The non-terminals that appear in some sentential form derived from the start rule.
+    pub fn reachable(&self) -> HashSet<NT> {
+        let mut reachable = HashSet::new();
+        let mut queue = vec![self.start_rule().lhs()];
+
+        while let Some(nt) = queue.pop() {
+            if reachable.insert(nt) {
+                for rule in self.rules_by_lhs(nt) {
+                    queue.extend(rule.symbols().iter().filter_map(|s| s.non_terminal()));
+                }
+            }
+        }
+
+        reachable
+    }
+
+    /// Computes the first set of the sentence `symbols` followed by `look_ahead`.
+    pub fn first_of(
+        first_set: &FirstSet<T, NT>,
+        nullable: &HashSet<NT>,
+        symbols: &[Symbol<T, NT>],
+        look_ahead: T,
+    ) -> HashSet<T> {
+        let mut result = HashSet::new();
+
+        for sym in symbols {
+            result.extend(&first_set[sym]);
+
+            match sym {
+                Symbol::NonTerminal(nt) if nullable.contains(nt) => {}
+                _ => return result,
+            }
+        }
+
+        result.insert(look_ahead);
+        result
+    }
 }
@@ -1,4 +1,5 @@
 use crate::common::{Rule, Symbol};
+use std::fmt::{self, Debug, Display};
 use std::hash::Hash;
 
 #[derive(Eq, PartialEq, Hash, Clone, Copy)]
@@ -33,12 +34,16 @@ where
         self.rule
     }
 
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
     pub fn active_symbol(&self) -> Option<Symbol<T, NT>> {
         self.rule.symbols().get(self.index).copied()
     }
 
     pub fn is_active(&self) -> bool {
-        return self.index < self.rule.symbols().len();
+        self.index < self.rule.symbols().len()
     }
 
     pub fn following_active(&self) -> Symbol<T, NT> {
@@ -49,7 +54,33 @@ where
         }
     }
 
+    /// The symbols that follow the active symbol of this item.
+    pub fn following_symbols(&self) -> &'r [Symbol<T, NT>] {
+        let symbols = self.rule.symbols();
+        &symbols[(self.index + 1).min(symbols.len())..]
+    }
+
     pub fn look_ahead(&self) -> T {
         self.look_ahead
     }
 }
+
+impl<'r, T, NT> Display for Item<'r, T, NT>
+where
+    T: Debug + Copy,
+    NT: Debug + Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} ->", self.rule.lhs())?;
+        for (i, sym) in self.rule.symbols().iter().enumerate() {
+            if i == self.index {
+                write!(f, " .")?;
+            }
+            write!(f, " {}", sym)?;
+        }
+        if !self.is_active() {
+            write!(f, " .")?;
+        }
+        write!(f, ", {:?}", self.look_ahead)
+    }
+}
@@ -1,9 +1,13 @@
 use crate::common::Symbol;
+use std::fmt::{self, Debug, Display};
 
 #[derive(Eq, PartialEq, Hash, Clone, Debug)]
 pub struct Rule<T, NT> {
     lhs: NT,
     symbols: Vec<Symbol<T, NT>>,
+    labels: Vec<Option<String>>,
+    action: Option<String>,
+    name: Option<String>,
 }
 
 impl<T, NT> Rule<T, NT>
@@ -12,7 +16,49 @@ where
     NT: Copy,
 {
     pub fn new(lhs: NT, symbols: Vec<Symbol<T, NT>>) -> Self {
-        Self { lhs, symbols }
+        Self {
+            lhs,
+            labels: vec![None; symbols.len()],
+            symbols,
+            action: None,
+            name: None,
+        }
+    }
+
+    /// Attaches a semantic action to this rule: a Rust expression computing the value of the lhs,
+    /// in which the value of every symbol is bound to its label.
+    pub fn with_action(mut self, action: impl Into<String>) -> Self {
+        self.action = Some(action.into());
+        self
+    }
+
+    /// Names this alternative of the lhs, for use in generated code.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Names the symbols of this rule, `None` leaves a symbol unnamed.
+    pub fn with_labels(mut self, labels: Vec<Option<String>>) -> Self {
+        assert_eq!(
+            labels.len(),
+            self.symbols.len(),
+            "expected a label for every symbol"
+        );
+        self.labels = labels;
+        self
+    }
+
+    pub fn action(&self) -> Option<&str> {
+        self.action.as_deref()
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn label(&self, index: usize) -> Option<&str> {
+        self.labels[index].as_deref()
     }
 
     pub fn lhs(&self) -> NT {
@@ -36,3 +82,17 @@ impl<'a, T, NT> IntoIterator for &'a Rule<T, NT> {
         self.symbols.iter()
     }
 }
+
+impl<T, NT> Display for Rule<T, NT>
+where
+    T: Debug,
+    NT: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} ->", self.lhs)?;
+        for sym in &self.symbols {
+            write!(f, " {}", sym)?;
+        }
+        Ok(())
+    }
+}
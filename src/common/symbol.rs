@@ -1,8 +1,13 @@
-use std::fmt::Debug;
+use std::fmt::{self, Debug, Display};
 use std::hash::Hash;
 
 pub trait Terminal: Eq + Hash + Copy + Debug {
     fn eof() -> Self;
+
+    /// Whether this is the terminal that stands for a syntax error in rules that recover from them.
+    fn is_error(&self) -> bool {
+        false
+    }
 }
 
 pub trait NonTerminal: Eq + Hash + Copy + Debug {}
@@ -39,3 +44,16 @@ where
         }
     }
 }
+
+impl<T, NT> Display for Symbol<T, NT>
+where
+    T: Debug,
+    NT: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Terminal(term) => write!(f, "{:?}", term),
+            Self::NonTerminal(nonterm) => write!(f, "{:?}", nonterm),
+        }
+    }
+}
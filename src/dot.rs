@@ -0,0 +1,166 @@
+//! Export of the LR(1) automaton to the Graphviz DOT format.
+
+use crate::analysis::{Analyser, StateIndex};
+use crate::common::{Grammar, Terminal};
+use crate::report::group_items;
+use std::collections::HashSet;
+use std::fmt::{Debug, Write};
+use std::hash::Hash;
+
+/// Selects the states that are rendered, automata of real grammars are too large to read as a whole.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Filter {
+    All,
+    States(Vec<StateIndex>),
+    /// The given states together with every state that can be reached from them in at most `steps` transitions.
+    Reachable {
+        from: Vec<StateIndex>,
+        steps: usize,
+    },
+}
+
+/// Renders the automaton of `grammar` as a DOT graph. Every state is a node listing its items,
+/// edges are labelled with the shifted terminal or the non-terminal of the goto and states with
+/// conflicts are highlighted. Transitions to states outside of `filter` point to dashed placeholders.
+pub fn to_dot<T, NT>(grammar: &Grammar<T, NT>, filter: &Filter) -> String
+where
+    T: 'static + Terminal,
+    NT: 'static + Eq + Hash + Copy + Debug,
+{
+    let automaton = Analyser::new(grammar).automaton();
+    let table = Analyser::table(grammar);
+    let conflicting = table
+        .conflicts()
+        .iter()
+        .map(|c| c.state)
+        .collect::<HashSet<_>>();
+
+    let included = match filter {
+        Filter::All => (0..automaton.states.len()).collect::<Vec<_>>(),
+        Filter::States(states) => states.clone(),
+        Filter::Reachable { from, steps } => {
+            // States that do not exist have no transitions to follow.
+            let mut included = from
+                .iter()
+                .copied()
+                .filter(|&s| s < automaton.states.len())
+                .collect::<Vec<_>>();
+            let mut frontier = included.clone();
+
+            for _ in 0..*steps {
+                let mut next = Vec::new();
+                for state in frontier {
+                    for (_, to_state) in automaton.edges(state) {
+                        if !included.contains(&to_state) {
+                            included.push(to_state);
+                            next.push(to_state);
+                        }
+                    }
+                }
+                frontier = next;
+            }
+
+            included.sort_unstable();
+            included
+        }
+    };
+
+    let mut out = String::from("digraph automaton {\n");
+    out.push_str("    node [shape=box, fontname=\"monospace\"];\n");
+
+    let mut placeholders = Vec::new();
+    for &state in included.iter().filter(|&&s| s < automaton.states.len()) {
+        let mut label = format!("State {}\\n\\n", state);
+        for (core, look_aheads) in group_items(&automaton.items(state)) {
+            write!(label, "{}  [{}]\\l", escape(&core), escape(&look_aheads)).unwrap();
+        }
+
+        if conflicting.contains(&state) {
+            writeln!(
+                out,
+                "    s{} [label=\"{}\", style=filled, fillcolor=\"#f4cccc\", color=red];",
+                state, label
+            )
+            .unwrap();
+        } else {
+            writeln!(out, "    s{} [label=\"{}\"];", state, label).unwrap();
+        }
+
+        for (sym, to_state) in automaton.edges(state) {
+            if !included.contains(&to_state) && !placeholders.contains(&to_state) {
+                placeholders.push(to_state);
+            }
+
+            let style = if sym.is_terminal() { "solid" } else { "bold" };
+            writeln!(
+                out,
+                "    s{} -> s{} [label=\"{}\", style={}];",
+                state,
+                to_state,
+                escape(&sym.to_string()),
+                style
+            )
+            .unwrap();
+        }
+    }
+
+    for state in placeholders {
+        writeln!(
+            out,
+            "    s{} [label=\"State {}\", style=dashed];",
+            state, state
+        )
+        .unwrap();
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::syntax;
+
+    #[test]
+    fn filters_states() {
+        let file = syntax::parse("Start: E; E: E Plus E | Num;").unwrap();
+        let all = to_dot(file.grammar(), &Filter::All);
+
+        assert!(all.contains("s0 -> s1 [label=\"E\", style=bold];"));
+        assert!(all.contains("s0 -> s2 [label=\"Num\", style=solid];"));
+        assert!(all.contains("s4 [label=\"State 4\\n\\nE -> E . Plus E  [Eof, Plus]\\l"));
+        assert!(all.contains("fillcolor=\"#f4cccc\""));
+
+        let near = to_dot(
+            file.grammar(),
+            &Filter::Reachable {
+                from: vec![0],
+                steps: 1,
+            },
+        );
+        assert!(near.contains("s1 [label=\"State 1\\n"));
+        assert!(near.contains("s3 [label=\"State 3\", style=dashed];"));
+        assert!(!near.contains("s4 ["));
+    }
+
+    #[test]
+    fn skips_states_that_do_not_exist() {
+        let file = syntax::parse("Start: E; E: E Plus E | Num;").unwrap();
+        let empty = "digraph automaton {\n    node [shape=box, fontname=\"monospace\"];\n}\n";
+        let filters = [
+            Filter::States(vec![99]),
+            Filter::Reachable {
+                from: vec![99],
+                steps: 1,
+            },
+        ];
+        for filter in &filters {
+            assert_eq!(to_dot(file.grammar(), filter), empty);
+        }
+    }
+}
@@ -1,124 +1,889 @@
 use crate::analysis::{Action, Table};
-use crate::common::Rule;
-use codegen::Scope;
-use regex::Regex;
-use std::collections::HashSet;
+use crate::ast;
+use crate::common::{Grammar, Rule, Symbol, Terminal};
+use crate::messages::Messages;
+use codegen::{Block, Function, Scope};
 use std::fmt::Debug;
 use std::fs;
 use std::hash::Hash;
 
-pub fn generate<T, NT>(file_name: &str, table: &Table<T, NT>) -> std::io::Result<()>
+/// The way the action and goto tables are encoded in the generated parser.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+    /// Every table entry becomes an arm of a `match` on the state and token.
+    Match,
+    /// The tables are emitted as constant arrays indexed by state.
+    Table,
+}
+
+impl std::str::FromStr for Backend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "match" => Ok(Self::Match),
+            "table" => Ok(Self::Table),
+            other => Err(format!(
+                "unknown backend `{}`, expected `match` or `table`",
+                other
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub parser_name: String,
+    pub token_type: String,
+    pub backend: Backend,
+    /// When set, reductions are delegated to a generated trait with this name instead of running the
+    /// actions in the grammar, and the parser is generic over an implementation of that trait.
+    pub actions_trait: Option<String>,
+    /// Derive AST types from the grammar, see [`crate::ast`], and build them instead of running the
+    /// actions in the grammar.
+    pub ast: bool,
+    /// Build a lossless syntax tree, see [`crate::runtime::cst`], instead of running the actions in
+    /// the grammar. The parser keeps the source text to store the text of every token in the tree.
+    pub cst: bool,
+    /// Record the structure of the input as a list of events, see [`crate::runtime::events`], instead
+    /// of running the actions in the grammar.
+    pub events: bool,
+    /// Token kinds that are not part of the grammar, but are skipped by the parser, such as
+    /// whitespace and comments. They are added to the token enum together with an `is_trivia` function.
+    pub trivia: Vec<String>,
+    /// The texts of terminals defined by literals, which are displayed instead of their names, for
+    /// example in parse errors.
+    pub literals: Vec<(String, String)>,
+    /// Hand-written messages for errors in the states their sentences lead to.
+    pub messages: Option<Messages>,
+    /// Derive `Clone` for the values of the parser, so its state can be saved and restored, see
+    /// [`crate::runtime::ParseState::checkpoint`]. The types of the non-terminals must be `Clone`. The
+    /// values of syntax trees and events always are.
+    pub checkpoints: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            parser_name: "Parser".into(),
+            token_type: "TokenType".into(),
+            backend: Backend::Match,
+            actions_trait: None,
+            ast: false,
+            cst: false,
+            events: false,
+            trivia: Vec::new(),
+            literals: Vec::new(),
+            messages: None,
+            checkpoints: false,
+        }
+    }
+}
+
+pub fn generate<T, NT>(
+    file_name: &str,
+    grammar: &Grammar<T, NT>,
+    table: &Table<T, NT>,
+) -> std::io::Result<()>
+where
+    T: Terminal + Debug,
+    NT: Hash + Eq + Copy + Debug,
+{
+    let source = render(grammar, table, &Options::default())
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    fs::write(file_name, source)
+}
+
+/// Renders the source code of a parser for `table`, which must have been built from `grammar`.
+///
+/// Fails when a rule without action has a non-terminal with a declared type, and no value of that type
+/// can be passed on from its symbols. With an actions trait, fails when the grammar contains actions
+/// or two alternatives share a name. When generating an AST, fails when [`crate::ast::annotate`] does.
+/// Fails as well when more than one of an actions trait, an AST, a CST and events are requested.
+pub fn render<T, NT>(
+    grammar: &Grammar<T, NT>,
+    table: &Table<T, NT>,
+    options: &Options,
+) -> Result<String, String>
 where
-    T: Hash + Eq + Copy + Debug,
-    NT: Hash + Eq + Copy,
+    T: Terminal + Debug,
+    NT: Hash + Eq + Copy + Debug,
 {
     let mut scope = Scope::new();
-    let str_enum = Regex::new(r#"".*""#).unwrap();
 
-    let token_type = "TokenType";
-    let tokenizer = format!("std::vec::IntoIter<Token<{}>>", token_type);
-    let parser_name = "Parser";
-    let result = "Result<(), ()>";
-    let parse_state = format!("ParseState<{}, {}>", tokenizer, token_type);
+    let token_type = options.token_type.as_str();
+    // The parser is generic over its source of tokens, which is a vector of tokens by default.
+    let tokenizer = "I";
+    let default_tokenizer = format!("std::vec::IntoIter<Token<{}>>", token_type);
+    let parser_name = options.parser_name.as_str();
+    let actions_trait = options.actions_trait.as_deref();
 
-    scope.import("yaw::runtime", "{Parse, ParseState, Token}");
+    if [
+        actions_trait.is_some(),
+        options.ast,
+        options.cst,
+        options.events,
+    ]
+    .iter()
+    .filter(|&&mode| mode)
+    .count()
+        > 1
+    {
+        return Err(
+            "only one of an actions trait, an AST, a CST or events can be generated".into(),
+        );
+    }
+
+    let mut ast_types = Scope::new();
+    let annotated;
+    let grammar = if options.ast {
+        annotated = ast::annotate(grammar, &mut ast_types, token_type)?;
+        &annotated
+    } else {
+        grammar
+    };
+    let start_rule = grammar.start_rule();
+
+    // With an actions trait the values of the non-terminals are its associated types.
+    let value_type = |nt: NT| match actions_trait {
+        Some(_) => format!("A::{:?}", nt),
+        None => grammar.type_of(nt).to_owned(),
+    };
+    let (parser_type, value_enum_type, mut impl_generics) = match actions_trait {
+        Some(name) => (
+            format!("{}<A, I>", parser_name),
+            "Value<A>",
+            vec![format!("A: {}", name)],
+        ),
+        None if options.cst => (
+            format!("{}<'s, I>", parser_name),
+            "Value",
+            vec!["'s".into()],
+        ),
+        // Every value is the number of entries recorded for its symbol.
+        None if options.events => (format!("{}<I>", parser_name), "usize", Vec::new()),
+        None => (format!("{}<I>", parser_name), "Value", Vec::new()),
+    };
+    impl_generics.push(format!("I: TokenSource<{}>", token_type));
+    let start_type = if options.cst {
+        format!("cst::SyntaxNode<{}, NodeKind>", token_type)
+    } else if options.events {
+        format!("Vec<events::Event<{}, NodeKind>>", token_type)
+    } else {
+        value_type(start_rule.lhs())
+    };
+    let parse_trait = format!("Parse<{}, {}>", tokenizer, token_type);
+    let error_name = grammar
+        .unique_terminals()
+        .iter()
+        .find(|t| t.is_error())
+        .map(|error| format!("{:?}", error));
+    let error_terminal = match &error_name {
+        Some(error) => format!("Some({}::{})", token_type, error),
+        None => "None".to_owned(),
+    };
+    let messages = match &options.messages {
+        Some(messages) => messages
+            .resolve(grammar, table)
+            .map_err(|errors| format!("invalid messages: {}", errors.join(", ")))?,
+        None => Vec::new(),
+    };
+    let parse_state = format!(
+        "ParseState<{}, {}, {}>",
+        tokenizer, token_type, value_enum_type
+    );
+
+    for item in &["Parse", "ParseState", "Step", "Token", "TokenSource"] {
+        scope.import("yaw::runtime", item);
+    }
+    if options.cst {
+        scope.import("yaw::runtime", "cst");
+    } else if options.events {
+        scope.import("yaw::runtime", "events");
+    }
 
-    let tt_enum = scope.new_enum(token_type).vis("pub").derive("Copy, Clone");
+    // Rules are numbered by their position in the grammar. They are compared by their symbols only,
+    // since those of an annotated grammar have other actions than the ones in the table.
+    let rules = grammar.rules();
+    let rule_index = |rule: &Rule<T, NT>| {
+        rules
+            .iter()
+            .position(|r| r.lhs() == rule.lhs() && r.symbols() == rule.symbols())
+            .expect("the table was built from another grammar")
+    };
 
-    let mut unique_terminals = HashSet::new();
+    let mut unique_terminals = Vec::new();
     let mut unique_actions = Vec::new();
-    let mut unique_rules = HashSet::new();
     for (state, action) in table.actions() {
         for (nt, action) in action {
-            let enum_var = format!("{:?}", *nt);
-            unique_terminals.insert(String::from(
-                str_enum.replace(enum_var.as_str(), "&'static str"),
-            ));
-            unique_actions.push((state, nt, action));
-            if let Action::Reduce(rule) = action {
-                unique_rules.insert(rule);
+            let variant = format!("{:?}", nt);
+            if !unique_terminals.contains(&variant) {
+                unique_terminals.push(variant);
             }
+            unique_actions.push((*state, format!("{:?}", nt), action));
+        }
+    }
+
+    for trivia in &options.trivia {
+        if !unique_terminals.contains(trivia) {
+            unique_terminals.push(trivia.clone());
         }
     }
 
-    unique_actions.sort_by_key(|a| *a.0);
-    let rules: Vec<&Rule<T, NT>> = unique_rules.iter().copied().collect();
+    // Sort everything that came out of the hash maps of the table, so the output is the same between runs.
+    unique_terminals.sort();
+    unique_actions.sort_by(|a, b| (a.0, &a.1).cmp(&(b.0, &b.1)));
 
-    for term in unique_terminals {
+    let mut gotos = Vec::new();
+    for (&state, row) in table.gotos() {
+        for (&nt, &goto_state) in row {
+            for (i, rule) in rules.iter().enumerate() {
+                if rule.lhs() == nt {
+                    gotos.push((i, state, goto_state));
+                }
+            }
+        }
+    }
+    gotos.sort_unstable();
+
+    let tt_enum = scope
+        .new_enum(token_type)
+        .vis("pub")
+        .derive("Copy, Clone, Debug, PartialEq");
+    for term in &unique_terminals {
         tt_enum.new_variant(term.as_str());
     }
 
+    // The arms are in a block of their own, so they are indented inside the match.
+    let mut display_match = Block::new("f.write_str(match self");
+    for term in &unique_terminals {
+        // The error terminal is shown as it is written in rules.
+        let text = match options.literals.iter().find(|(name, _)| name == term) {
+            Some((_, text)) => text.as_str(),
+            None if error_name.as_ref() == Some(term) => "error",
+            None => term,
+        };
+        display_match.line(format!("{}::{} => {:?},", token_type, term, text));
+    }
+    display_match.after(")");
     scope
+        .new_impl(token_type)
+        .impl_trait("std::fmt::Display")
+        .new_fn("fmt")
+        .arg_ref_self()
+        .arg("f", "&mut std::fmt::Formatter")
+        .ret("std::fmt::Result")
+        .push_block(display_match);
+
+    if !options.trivia.is_empty() {
+        let kinds = options
+            .trivia
+            .iter()
+            .map(|t| format!("{}::{}", token_type, t))
+            .collect::<Vec<_>>();
+        scope
+            .new_fn("is_trivia")
+            .vis("pub")
+            .arg("kind", format!("&{}", token_type))
+            .ret("bool")
+            .line(format!("matches!(kind, {})", kinds.join(" | ")));
+    }
+
+    if options.ast {
+        scope.raw(ast_types.to_string().trim_end());
+    }
+
+    let mut nonterminals = Vec::new();
+    for rule in rules {
+        if !nonterminals.contains(&rule.lhs()) {
+            nonterminals.push(rule.lhs());
+        }
+    }
+
+    let mut methods = Vec::new();
+    if let Some(trait_name) = actions_trait {
+        methods = method_names(grammar)?;
+
+        let actions = scope.new_trait(trait_name).vis("pub");
+        for &nt in &nonterminals {
+            actions.associated_type(&format!("{:?}", nt));
+        }
+
+        for (rule, method) in rules.iter().zip(&methods) {
+            if rule.action().is_some() {
+                return Err(format!(
+                    "rule `{}` has an action, which is not used with an actions trait",
+                    rule
+                ));
+            }
+
+            let reduce_fn = actions.new_fn(method).doc(&rule.to_string()).arg_mut_self();
+            for (name, sym) in argument_names(rule).iter().zip(rule.symbols()) {
+                match sym {
+                    Symbol::Terminal(_) => reduce_fn.arg(name, format!("Token<{}>", token_type)),
+                    Symbol::NonTerminal(nt) => reduce_fn.arg(name, format!("Self::{:?}", nt)),
+                };
+            }
+            reduce_fn.ret(format!("Self::{:?}", rule.lhs()));
+        }
+    }
+
+    if options.cst || options.events {
+        let kind_enum = scope
+            .new_enum("NodeKind")
+            .vis("pub")
+            .derive("Copy, Clone, Debug, PartialEq, Eq, Hash");
+        for &nt in &nonterminals {
+            kind_enum.new_variant(&format!("{:?}", nt));
+        }
+
+        if options.cst {
+            // The error terminal already reserves its name, so only a non-terminal can clash.
+            if nonterminals
+                .iter()
+                .any(|nt| format!("{:?}", nt) == "Missing")
+            {
+                return Err("`Missing` is reserved for missing nodes in a CST".into());
+            }
+            kind_enum.new_variant("Error");
+            kind_enum.new_variant("Missing");
+
+            let kinds = scope.new_impl("NodeKind").impl_trait("cst::RecoveryKinds");
+            kinds.new_fn("error").ret("Self").line("NodeKind::Error");
+            kinds
+                .new_fn("missing")
+                .ret("Self")
+                .line("NodeKind::Missing");
+        }
+    }
+
+    if options.cst {
+        // The value stack holds the elements of the tree that have not been added to a node yet.
+        scope.raw(&format!(
+            "pub type Value = Vec<cst::GreenElement<{}, NodeKind>>;",
+            token_type
+        ));
+    } else if !options.events {
+        // The value stack holds the shifted tokens and the values of the reduced non-terminals.
+        let value_enum = scope.new_enum("Value").vis("pub");
+        if let Some(trait_name) = actions_trait {
+            value_enum.generic("A").bound("A", trait_name);
+        }
+        if options.checkpoints {
+            value_enum.derive("Clone");
+        }
+        value_enum
+            .new_variant("Terminal")
+            .tuple(&format!("Token<{}>", token_type));
+        for &nt in &nonterminals {
+            value_enum
+                .new_variant(&format!("{:?}", nt))
+                .tuple(&value_type(nt));
+        }
+    }
+
+    let parser_struct = scope
         .new_struct(parser_name)
         .vis("pub")
         .tuple_field(format!("pub {}", &parse_state));
+    if let Some(trait_name) = actions_trait {
+        // A where clause is not allowed before the fields of a tuple struct.
+        parser_struct
+            .generic(&format!("A: {}", trait_name))
+            .tuple_field("pub A");
+    } else if options.cst {
+        parser_struct.generic("'s").tuple_field("pub &'s str");
+    } else if options.events {
+        parser_struct.tuple_field(format!("pub events::Recorder<{}, NodeKind>", token_type));
+    }
+    parser_struct.generic(&format!("I = {}", default_tokenizer));
 
-    let parser_impl = scope
-        .new_impl(parser_name)
-        .impl_trait(format!("Parse<{}, {}>", tokenizer, token_type));
+    // The terminals with an action per state, which are the tokens the parser can accept in it.
+    let state_count = table
+        .actions()
+        .keys()
+        .chain(table.gotos().keys())
+        .max()
+        .map_or(0, |s| s + 1);
+    let mut expected = vec![Vec::new(); state_count];
+    for (state, term, _) in &unique_actions {
+        expected[*state].push(format!("{}::{}", token_type, term));
+    }
+    scope.raw(&format!(
+        "const EXPECTED: [&[{}]; {}] = [\n{}];",
+        token_type,
+        state_count,
+        expected
+            .iter()
+            .map(|terms| format!("    &[{}],\n", terms.join(", ")))
+            .collect::<String>()
+    ));
 
-    parser_impl
-        .new_fn("parse_state_mut")
-        .arg_mut_self()
-        .ret(format!("&mut {}", &parse_state))
-        .line("&mut self.0");
+    // An error-tolerant parse resynchronises on the terminals that can follow the non-terminals of the
+    // gotos of a state, see `yaw::runtime::tolerant`.
+    if options.cst {
+        let follow_set = grammar.follow_set();
+        let mut follows = vec![Vec::new(); state_count];
+        for (&state, row) in table.gotos() {
+            for (nt, &goto_state) in row {
+                let mut terminals = follow_set[nt]
+                    .iter()
+                    .map(|t| format!("{:?}", t))
+                    .filter(|t| unique_terminals.contains(t))
+                    .collect::<Vec<_>>();
+                terminals.sort();
+                follows[state].push((goto_state, terminals));
+            }
+            follows[state].sort();
+        }
+        scope.raw(&format!(
+            "const FOLLOWS: [&[(usize, &[{}])]; {}] = [\n{}];",
+            token_type,
+            state_count,
+            follows
+                .iter()
+                .map(|gotos| {
+                    let gotos = gotos
+                        .iter()
+                        .map(|(state, terms)| {
+                            let terms = terms
+                                .iter()
+                                .map(|t| format!("{}::{}", token_type, t))
+                                .collect::<Vec<_>>();
+                            format!("({}, &[{}])", state, terms.join(", "))
+                        })
+                        .collect::<Vec<_>>();
+                    format!("    &[{}],\n", gotos.join(", "))
+                })
+                .collect::<String>()
+        ));
+    }
+
+    let step = |action: &Action<T, NT>| match action {
+        Action::Shift(state) => Some(format!("Some(Step::Shift({}))", state)),
+        Action::Reduce(rule) => {
+            let i = rule_index(rule);
+            Some(format!(
+                "Some(Step::Reduce({}, {}))",
+                i,
+                rules[i].symbols().len()
+            ))
+        }
+        Action::Accept => Some(format!(
+            "Some(Step::Accept({}, {}))",
+            grammar.start_rule_index(),
+            start_rule.symbols().len()
+        )),
+        Action::Error => None,
+    };
+
+    let mut step_fn = Function::new("step");
+    step_fn
+        .arg_ref_self()
+        .arg("state", "usize")
+        .arg("kind", token_type)
+        .ret("Option<Step>");
+    let mut goto_fn = Function::new("goto_state");
+    goto_fn
+        .arg_ref_self()
+        .arg("state", "usize")
+        .arg("rule", "usize")
+        .ret("usize");
+
+    if options.backend == Backend::Table {
+        let terminals = unique_actions
+            .iter()
+            .map(|a| a.1.as_str())
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
 
+        // Actions are encoded as (kind, argument) with kinds error = 0, shift = 1, reduce = 2 and accept = 3.
+        let mut actions = vec![vec![(0, 0); terminals.len()]; state_count];
+        for (state, term, action) in &unique_actions {
+            let column = terminals.iter().position(|t| t == term).unwrap();
+            actions[*state][column] = match action {
+                Action::Shift(to_state) => (1, *to_state),
+                Action::Reduce(rule) => (2, rule_index(rule)),
+                Action::Accept => (3, grammar.start_rule_index()),
+                Action::Error => (0, 0),
+            };
+        }
+
+        let mut goto_rows = vec![vec![0; rules.len()]; state_count];
+        for &(rule, state, goto_state) in &gotos {
+            goto_rows[state][rule] = goto_state;
+        }
+
+        scope.raw(&format!(
+            "const ACTIONS: [[(u8, usize); {}]; {}] = {:?};",
+            terminals.len(),
+            state_count,
+            actions
+        ));
+        scope.raw(&format!(
+            "const GOTOS: [[usize; {}]; {}] = {:?};",
+            rules.len(),
+            state_count,
+            goto_rows
+        ));
+        scope.raw(&format!(
+            "const RULE_LENGTHS: [usize; {}] = {:?};",
+            rules.len(),
+            rules.iter().map(|r| r.symbols().len()).collect::<Vec<_>>()
+        ));
+
+        step_fn.line("let column = match kind {");
+        for (i, term) in terminals.iter().enumerate() {
+            step_fn.line(format!("{}::{} => {},", token_type, term, i));
+        }
+        step_fn
+            .line("#[allow(unreachable_patterns)]")
+            .line("_ => return None,")
+            .line("};")
+            .line("match ACTIONS[state][column] {")
+            .line("(1, state) => Some(Step::Shift(state)),")
+            .line("(2, rule) => Some(Step::Reduce(rule, RULE_LENGTHS[rule])),")
+            .line("(3, rule) => Some(Step::Accept(rule, RULE_LENGTHS[rule])),")
+            .line("_ => None,")
+            .line("}");
+
+        goto_fn.line("GOTOS[state][rule]");
+    } else {
+        step_fn.line("match (state, kind) {");
+        for (state, nt, action) in &unique_actions {
+            if let Some(step) = step(action) {
+                step_fn.line(format!("({}, {}::{}) => {},", state, token_type, nt, step));
+            }
+        }
+        step_fn.line("_ => None,").line("}");
+
+        goto_fn.line("match (rule, state) {");
+        for &(i, state, goto_state) in &gotos {
+            goto_fn.line(format!("({}, {}) => {},", i, state, goto_state));
+        }
+        goto_fn.line("_ => panic!(\"unkown goto\"),").line("}");
+    }
+
+    let parser_impl = new_parser_impl(
+        &mut scope,
+        &parser_type,
+        &parse_trait,
+        &parse_state,
+        &start_type,
+        value_enum_type,
+        &impl_generics,
+    );
     parser_impl
-        .new_fn("parse_state")
+        .new_fn("expected_terminals")
         .arg_ref_self()
-        .ret(format!("&{}", &parse_state))
-        .line("&self.0");
+        .arg("state", "usize")
+        .ret(format!("&'static [{}]", token_type))
+        .line("EXPECTED[state]");
+    parser_impl
+        .new_fn("eof")
+        .arg_ref_self()
+        .ret(token_type)
+        .line(format!("{}::Eof", token_type));
+    parser_impl
+        .new_fn("error_terminal")
+        .arg_ref_self()
+        .ret(format!("Option<{}>", token_type))
+        .line(&error_terminal);
+    parser_impl.push_fn(step_fn).push_fn(goto_fn);
+
+    if !messages.is_empty() {
+        let message_fn = parser_impl
+            .new_fn("message")
+            .arg_ref_self()
+            .arg("state", "usize")
+            .ret("Option<&'static str>")
+            .line("match state {");
+        for (i, &(_, message)) in messages.iter().enumerate() {
+            // States sharing a message share an arm, listed where the message first appears.
+            if messages[..i].iter().any(|&(_, m)| m == message) {
+                continue;
+            }
+            let states = messages
+                .iter()
+                .filter(|&&(_, m)| m == message)
+                .map(|(state, _)| state.to_string())
+                .collect::<Vec<_>>();
+            message_fn.line(format!("{} => Some({:?}),", states.join(" | "), message));
+        }
+        message_fn.line("_ => None,").line("}");
+    }
+
+    let is_trivia = if options.trivia.is_empty() {
+        "|_| false"
+    } else {
+        "is_trivia"
+    };
 
-    let action_fn = parser_impl
-        .new_fn("action")
+    if options.cst {
+        parser_impl
+            .new_fn("follow_gotos")
+            .arg_ref_self()
+            .arg("state", "usize")
+            .ret(format!("&'static [(usize, &'static [{}])]", token_type))
+            .line("FOLLOWS[state]");
+    }
+
+    if options.cst || options.events {
+        parser_impl
+            .new_fn("token_value")
+            .arg_mut_self()
+            .arg("token", format!("Token<{}>", token_type))
+            .ret(value_enum_type)
+            .line("let trivia = self.0.take_trivia();")
+            .line(if options.cst {
+                "cst::token_elements(self.1, trivia, token)"
+            } else {
+                "self.1.token(trivia, token)"
+            });
+
+        let reduce_fn = parser_impl
+            .new_fn("reduce_values")
+            .arg_mut_self()
+            .arg("rule", "usize")
+            .arg("values", format!("Vec<{}>", value_enum_type))
+            .ret(value_enum_type)
+            .line("let kind = match rule {");
+        for (i, rule) in rules.iter().enumerate() {
+            reduce_fn.line(format!("{} => NodeKind::{:?},", i, rule.lhs()));
+        }
+        reduce_fn
+            .line("_ => unreachable!(),")
+            .line("};")
+            .line(if options.cst {
+                format!("cst::node_elements(kind, values, {})", is_trivia)
+            } else {
+                "self.1.node(kind, values)".to_owned()
+            });
+
+        parser_impl
+            .new_fn("output")
+            .arg_mut_self()
+            // The recorder already holds the whole parse, so events mode ignores the value.
+            .arg(
+                if options.cst { "value" } else { "_value" },
+                value_enum_type,
+            )
+            .ret(&start_type)
+            .line("let trivia = self.0.take_trivia();")
+            .line(if options.cst {
+                "cst::root(self.1, value, trivia)"
+            } else {
+                "self.1.finish(trivia)"
+            });
+
+        return Ok(scope.to_string());
+    }
+
+    parser_impl
+        .new_fn("token_value")
         .arg_mut_self()
-        .ret(format!("Option<{}>", result))
         .arg("token", format!("Token<{}>", token_type))
-        .line("match (self.state(), token.kind) {");
-
-    for (state, nt, action) in unique_actions {
-        let action_str = match action {
-            Action::Shift(state) => format!("self.shift({}, token)", state),
-            Action::Reduce(rule) => format!(
-                "self.reduce({})",
-                rules.iter().position(|r| r == &rule).unwrap()
-            ),
-            Action::Accept => "return Some(self.accept())".into(),
-            Action::Error => "return Some(self.error())".into(),
+        .ret(value_enum_type)
+        .line("Value::Terminal(token)");
+
+    let reduce_fn = parser_impl
+        .new_fn("reduce_values")
+        .attr(
+            "allow(unused_variables, unused_braces, clippy::just_underscores_and_digits, \
+             clippy::let_unit_value)",
+        )
+        .arg_mut_self()
+        .arg("rule", "usize")
+        .arg("values", format!("Vec<{}>", value_enum_type))
+        .ret(value_enum_type)
+        .line("let mut values = values.into_iter();")
+        .line("match rule {");
+
+    for (i, rule) in rules.iter().enumerate() {
+        reduce_fn.line(format!("{} => {{", i));
+
+        let mut names = Vec::new();
+        for (j, sym) in rule.symbols().iter().enumerate() {
+            let name = rule
+                .label(j)
+                .map_or_else(|| format!("_{}", j + 1), String::from);
+            let variant = match sym {
+                Symbol::Terminal(_) => "Terminal".to_owned(),
+                Symbol::NonTerminal(nt) => format!("{:?}", nt),
+            };
+            reduce_fn.line(format!(
+                "let {} = match values.next() {{ Some(Value::{}(v)) => v, _ => unreachable!() }};",
+                name, variant
+            ));
+            names.push(name);
+        }
+
+        let lhs_type = grammar.type_of(rule.lhs());
+        let value = match (rule.action(), rule.symbols().as_slice()) {
+            _ if actions_trait.is_some() => format!("self.1.{}({})", methods[i], names.join(", ")),
+            (Some(action), _) => action.to_owned(),
+            // A single symbol of the same type passes on its value.
+            (None, [Symbol::NonTerminal(nt)]) if grammar.type_of(*nt) == lhs_type => {
+                names[0].clone()
+            }
+            (None, _) if lhs_type == "()" => "()".to_owned(),
+            (None, _) => {
+                return Err(format!(
+                    "rule `{}` needs an action to compute a value of type `{}`",
+                    rule, lhs_type
+                ))
+            }
         };
 
-        action_fn.line(format!(
-            "({}, TokenType::{:?}) => {},",
-            state, nt, action_str
-        ));
+        reduce_fn.line(format!("Value::{:?}({})", rule.lhs(), value));
+        reduce_fn.line("}");
     }
 
-    action_fn.line("_ => return Some(self.error())");
-    action_fn.line("}");
-    action_fn.line("None");
+    reduce_fn.line("_ => unreachable!(),");
+    reduce_fn.line("}");
 
-    let goto_fn = parser_impl
-        .new_fn("goto")
+    let output_fn = parser_impl
+        .new_fn("output")
         .arg_mut_self()
-        .arg("rule", "usize")
-        .line("let state = self.state();")
-        .line("let goto_state = match (rule, state) {");
+        .arg("value", value_enum_type);
+    // Clippy rejects an explicit unit return type.
+    if start_type != "()" {
+        output_fn.ret(&start_type);
+    }
+    output_fn
+        .line("match value {")
+        .line(format!("Value::{:?}(v) => v,", start_rule.lhs()))
+        .line("_ => unreachable!(),")
+        .line("}");
 
-    for (state, gotos) in table.gotos() {
-        for (&nt, &goto_state) in gotos {
-            unique_rules
-                .iter()
-                .enumerate()
-                .filter_map(|(i, &r)| if r.lhs() == nt { Some(i) } else { None })
-                .for_each(|i| {
-                    goto_fn.line(format!("({}, {}) => {},", i, state, goto_state));
-                });
+    Ok(scope.to_string())
+}
+
+/// Names the methods of the actions trait: `reduce_` followed by the name of the alternative, or by the
+/// lhs in snake case and, when it has several alternatives, the position of the alternative.
+fn method_names<T, NT>(grammar: &Grammar<T, NT>) -> Result<Vec<String>, String>
+where
+    T: Terminal + Debug,
+    NT: Hash + Eq + Copy + Debug,
+{
+    let mut names: Vec<String> = Vec::new();
+
+    for rule in grammar.rules() {
+        let alternatives = grammar.rules_by_lhs(rule.lhs());
+        let name = match rule.name() {
+            Some(name) => format!("reduce_{}", name),
+            None if alternatives.len() == 1 => format!("reduce_{}", snake_case(rule.lhs())),
+            None => {
+                let position = alternatives.iter().position(|r| *r == rule).unwrap();
+                format!("reduce_{}_{}", snake_case(rule.lhs()), position + 1)
+            }
+        };
+
+        if names.contains(&name) {
+            return Err(format!(
+                "rule `{}` needs a name other than `{}`",
+                rule, name
+            ));
+        }
+        names.push(name);
+    }
+
+    Ok(names)
+}
+
+/// Names the arguments of a method of the actions trait after the labels of the symbols, or after
+/// the symbols themselves in snake case, numbering the ones that occur more than once.
+pub(crate) fn argument_names<T, NT>(rule: &Rule<T, NT>) -> Vec<String>
+where
+    T: Copy + Debug,
+    NT: Copy + Debug,
+{
+    let mut names: Vec<String> = Vec::new();
+
+    for (i, sym) in rule.symbols().iter().enumerate() {
+        let base = match rule.label(i) {
+            Some(label) => label.to_owned(),
+            None => match sym {
+                Symbol::Terminal(t) => snake_case(t),
+                Symbol::NonTerminal(nt) => snake_case(nt),
+            },
+        };
+
+        let mut name = base.clone();
+        let mut count = 1;
+        while names.contains(&name) {
+            count += 1;
+            name = format!("{}_{}", base, count);
+        }
+        names.push(name);
+    }
+
+    names
+}
+
+pub(crate) fn snake_case(name: impl Debug) -> String {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else",
+        "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod",
+        "move", "mut", "pub", "ref", "return", "self", "static", "struct", "super", "trait",
+        "true", "try", "type", "unsafe", "use", "where", "while", "yield",
+    ];
+
+    let name = format!("{:?}", name);
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut snake = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        // A word starts at an uppercase letter after a lowercase one, or before one in a run of capitals.
+        if c.is_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if previous.is_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_uppercase() && next_lower)
+            {
+                snake.push('_');
+            }
         }
+        snake.extend(c.to_lowercase());
+    }
+
+    if KEYWORDS.contains(&snake.as_str()) {
+        snake.push('_');
+    }
+    snake
+}
+
+fn new_parser_impl<'s>(
+    scope: &'s mut Scope,
+    parser_type: &str,
+    parse_trait: &str,
+    parse_state: &str,
+    output: &str,
+    value_type: &str,
+    generics: &[String],
+) -> &'s mut codegen::Impl {
+    let parser_impl = scope
+        .new_impl(parser_type)
+        .impl_trait(parse_trait)
+        .associate_type("Value", value_type)
+        .associate_type("Output", output);
+    for generic in generics {
+        parser_impl.generic(generic);
     }
 
-    goto_fn.line("_ => panic!(\"unkown goto\")");
-    goto_fn.line("};");
-    goto_fn.line("self.push_state(goto_state)");
+    parser_impl
+        .new_fn("parse_state_mut")
+        .arg_mut_self()
+        .ret(format!("&mut {}", parse_state))
+        .line("&mut self.0");
 
-    fs::write(file_name, scope.to_string())
+    parser_impl
+        .new_fn("parse_state")
+        .arg_ref_self()
+        .ret(format!("&{}", parse_state))
+        .line("&self.0");
+
+    parser_impl
 }
@@ -0,0 +1,752 @@
+//! Generation of lexers from token definitions.
+//!
+//! Every terminal can be defined by a literal or a regular expression, see [`pattern`] for the
+//! supported syntax. The definitions are compiled into a single minimal DFA, which is rendered as a
+//! [`crate::runtime::lexer::Lexer`] constant next to the parser. The lexer takes the longest match
+//! at every position. When several definitions match the same text, literals take priority over
+//! regexes, so keywords are not lexed as identifiers, and otherwise the one defined first wins.
+//!
+//! Definitions that match the empty string or can never win are rejected when compiling. Texts that
+//! are matched by more than one definition are reported as [`Overlap`]s.
+//!
+//! Like the start conditions of flex, definitions can be restricted to modes, each of which is
+//! compiled into its own DFA. The lexer starts in the [`INITIAL_MODE`], which holds the definitions
+//! without modes, and keeps a stack of modes that tokens push to and pop from when they are matched.
+//! Anything the modes cannot express, such as nested comments or heredocs, is left to callbacks
+//! registered on the [`crate::runtime::lexer::Scanner`].
+
+pub mod pattern;
+
+use pattern::Regex;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    Literal(String),
+    Regex(String),
+}
+
+/// The mode the lexer starts in.
+pub const INITIAL_MODE: &str = "Initial";
+
+/// A change of the mode of the lexer after a token is matched.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModeAction {
+    /// Enters the named mode, until a token pops it.
+    Push(String),
+    /// Returns to the mode that was active before the last push.
+    Pop,
+}
+
+/// The definition of the terminal `name` as the text matched by `pattern`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenDefinition {
+    pub name: String,
+    pub pattern: Pattern,
+    /// The modes in which the token is lexed, only the initial mode when empty.
+    pub modes: Vec<String>,
+    pub action: Option<ModeAction>,
+}
+
+impl TokenDefinition {
+    /// A definition in the initial mode that does not change the mode.
+    pub fn new(name: impl Into<String>, pattern: Pattern) -> Self {
+        Self {
+            name: name.into(),
+            pattern,
+            modes: Vec::new(),
+            action: None,
+        }
+    }
+
+    fn in_mode(&self, mode: &str) -> bool {
+        if self.modes.is_empty() {
+            mode == INITIAL_MODE
+        } else {
+            self.modes.iter().any(|m| m == mode)
+        }
+    }
+}
+
+/// A text matched by two definitions, which is lexed as the `winner` because it takes priority.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Overlap {
+    pub winner: String,
+    pub loser: String,
+    /// The shortest text matched by both.
+    pub example: String,
+}
+
+impl fmt::Display for Overlap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:?} is matched by both `{}` and `{}`, `{}` takes priority",
+            self.example, self.winner, self.loser, self.winner
+        )
+    }
+}
+
+#[derive(Default)]
+struct NfaState {
+    epsilon: Vec<usize>,
+    edges: Vec<(u32, u32, usize)>,
+    accept: Option<usize>,
+}
+
+/// A Thompson automaton, in which state 0 is the start state.
+struct Nfa {
+    states: Vec<NfaState>,
+}
+
+impl Nfa {
+    fn new(regexes: &[Regex]) -> Self {
+        let mut nfa = Self {
+            states: vec![NfaState::default()],
+        };
+
+        for (token, regex) in regexes.iter().enumerate() {
+            let start = nfa.state();
+            nfa.states[0].epsilon.push(start);
+            let end = nfa.add(regex, start);
+            nfa.states[end].accept = Some(token);
+        }
+
+        nfa
+    }
+
+    fn state(&mut self) -> usize {
+        self.states.push(NfaState::default());
+        self.states.len() - 1
+    }
+
+    /// Adds the states matching `regex` after `from`, returning the state in which a match ends.
+    fn add(&mut self, regex: &Regex, from: usize) -> usize {
+        match regex {
+            Regex::Class(ranges) => {
+                let to = self.state();
+                for &(start, end) in ranges {
+                    self.states[from].edges.push((start, end, to));
+                }
+                to
+            }
+            Regex::Concat(items) => items.iter().fold(from, |at, item| self.add(item, at)),
+            Regex::Alternation(alternatives) => {
+                let to = self.state();
+                for alternative in alternatives {
+                    let end = self.add(alternative, from);
+                    self.states[end].epsilon.push(to);
+                }
+                to
+            }
+            Regex::Repeat(item, min, max) => {
+                let mut at = (0..*min).fold(from, |at, _| self.add(item, at));
+                let to = self.state();
+                self.states[at].epsilon.push(to);
+
+                match max {
+                    None => {
+                        let end = self.add(item, to);
+                        self.states[end].epsilon.push(to);
+                    }
+                    Some(max) => {
+                        for _ in *min..*max {
+                            at = self.add(item, at);
+                            self.states[at].epsilon.push(to);
+                        }
+                    }
+                }
+                to
+            }
+        }
+    }
+
+    fn closure(&self, states: impl IntoIterator<Item = usize>) -> BTreeSet<usize> {
+        let mut closure = BTreeSet::new();
+        let mut stack = states.into_iter().collect::<Vec<_>>();
+
+        while let Some(state) = stack.pop() {
+            if closure.insert(state) {
+                stack.extend(&self.states[state].epsilon);
+            }
+        }
+
+        closure
+    }
+}
+
+/// A deterministic automaton recognising the tokens of a set of definitions, in which state 0 is the
+/// start state.
+#[derive(Debug)]
+pub struct Dfa {
+    tokens: Vec<String>,
+    /// Per state, the sorted ranges of characters with the state they lead to.
+    transitions: Vec<Vec<(char, char, usize)>>,
+    /// Per state, the indices of the definitions that match when the input ends in it, in order of
+    /// priority. A lexer that knows which tokens the parser expects can skip the ones it does not.
+    accept: Vec<Vec<usize>>,
+    overlaps: Vec<Overlap>,
+}
+
+impl Dfa {
+    /// Compiles `definitions`, of which the literals take priority over the regexes. Fails when a regex
+    /// is invalid, a definition matches the empty string, or a definition can never be matched.
+    pub fn compile(definitions: &[TokenDefinition]) -> Result<Self, String> {
+        let (literals, regexes): (Vec<_>, Vec<_>) = definitions
+            .iter()
+            .partition(|d| matches!(d.pattern, Pattern::Literal(_)));
+        let definitions = literals.into_iter().chain(regexes).collect::<Vec<_>>();
+
+        let regexes = definitions
+            .iter()
+            .map(|definition| match &definition.pattern {
+                Pattern::Literal(text) => Ok(Regex::literal(text)),
+                Pattern::Regex(regex) => pattern::parse(regex)
+                    .map_err(|e| format!("invalid regex for `{}`: {}", definition.name, e)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let nfa = Nfa::new(&regexes);
+
+        // Every range in the automaton is split at the start and end of all others, so the
+        // resulting intervals are either fully contained in a range or disjoint from it.
+        let mut bounds = nfa
+            .states
+            .iter()
+            .flat_map(|s| {
+                s.edges
+                    .iter()
+                    .flat_map(|&(start, end, _)| vec![start, end + 1])
+            })
+            .collect::<Vec<_>>();
+        bounds.sort_unstable();
+        bounds.dedup();
+        let intervals = bounds
+            .windows(2)
+            .map(|w| (w[0], w[1] - 1))
+            .collect::<Vec<_>>();
+
+        // Subset construction, in which every state tracks all definitions it accepts.
+        let mut sets = vec![nfa.closure(Some(0))];
+        let mut ids = HashMap::new();
+        ids.insert(sets[0].clone(), 0);
+        let mut moves: Vec<Vec<Option<usize>>> = Vec::new();
+
+        while moves.len() < sets.len() {
+            let mut targets = vec![BTreeSet::new(); intervals.len()];
+            for &state in &sets[moves.len()] {
+                for &(start, end, to) in &nfa.states[state].edges {
+                    let first = intervals.partition_point(|&(s, _)| s < start);
+                    for (interval, target) in intervals[first..].iter().zip(&mut targets[first..]) {
+                        if interval.0 > end {
+                            break;
+                        }
+                        target.insert(to);
+                    }
+                }
+            }
+
+            let row = targets
+                .into_iter()
+                .map(|target| {
+                    if target.is_empty() {
+                        return None;
+                    }
+                    let set = nfa.closure(target);
+                    let next = sets.len();
+                    Some(*ids.entry(set.clone()).or_insert_with(|| {
+                        sets.push(set);
+                        next
+                    }))
+                })
+                .collect();
+            moves.push(row);
+        }
+
+        let accepts = sets
+            .iter()
+            .map(|set| {
+                let mut tokens = set
+                    .iter()
+                    .filter_map(|&s| nfa.states[s].accept)
+                    .collect::<Vec<_>>();
+                tokens.sort_unstable();
+                tokens
+            })
+            .collect::<Vec<_>>();
+
+        let name = |token: usize| definitions[token].name.clone();
+        if let Some(&token) = accepts[0].first() {
+            return Err(format!("`{}` matches the empty string", name(token)));
+        }
+
+        let overlaps = overlaps(&moves, &accepts, &intervals)
+            .into_iter()
+            .map(|(winner, loser, example)| Overlap {
+                winner: name(winner),
+                loser: name(loser),
+                example,
+            })
+            .collect::<Vec<_>>();
+
+        for (token, definition) in definitions.iter().enumerate() {
+            if accepts.iter().all(|tokens| tokens.first() != Some(&token)) {
+                return Err(match overlaps.iter().find(|o| o.loser == definition.name) {
+                    Some(overlap) => format!(
+                        "`{}` can never be matched, since `{}` takes priority",
+                        definition.name, overlap.winner
+                    ),
+                    None => format!("`{}` does not match any text", definition.name),
+                });
+            }
+        }
+
+        let (transitions, accept) = minimize(&moves, &accepts, &intervals);
+
+        Ok(Self {
+            tokens: definitions.iter().map(|d| d.name.clone()).collect(),
+            transitions,
+            accept,
+            overlaps,
+        })
+    }
+
+    pub fn overlaps(&self) -> &[Overlap] {
+        &self.overlaps
+    }
+
+    pub fn state_count(&self) -> usize {
+        self.transitions.len()
+    }
+
+    /// The definition matching the longest prefix of `text`, with the length of that prefix.
+    pub fn longest_match(&self, text: &str) -> Option<(&str, usize)> {
+        let mut state = 0;
+        let mut found = None;
+
+        for (i, c) in text.char_indices() {
+            state = match self.transitions[state]
+                .iter()
+                .find(|&&(start, end, _)| start <= c && c <= end)
+            {
+                Some(&(_, _, next)) => next,
+                None => break,
+            };
+            if let Some(&token) = self.accept[state].first() {
+                found = Some((self.tokens[token].as_str(), i + c.len_utf8()));
+            }
+        }
+
+        found
+    }
+
+    /// Renders the tables of the automaton as a `Mode` of a lexer producing tokens of `token_type`.
+    fn render_mode(&self, token_type: &str, out: &mut String) {
+        out.push_str("        yaw::runtime::lexer::Mode {\n            transitions: &[\n");
+        for row in &self.transitions {
+            let ranges = row
+                .iter()
+                .map(|(start, end, next)| format!("({:?}, {:?}, {})", start, end, next))
+                .collect::<Vec<_>>();
+            out.push_str(&format!("                &[{}],\n", ranges.join(", ")));
+        }
+
+        out.push_str("            ],\n            accept: &[\n");
+        for accept in &self.accept {
+            let names = accept.iter().map(|&token| &self.tokens[token]).collect();
+            out.push_str(&format!(
+                "                &[{}],\n",
+                kinds(token_type, names)
+            ));
+        }
+        out.push_str("            ],\n        },\n");
+    }
+}
+
+/// The automata of all modes of a lexer, of which the first is the initial mode, with the mode changes
+/// of its tokens.
+#[derive(Debug)]
+pub struct Lexer {
+    modes: Vec<(String, Dfa)>,
+    actions: Vec<(String, ModeAction)>,
+}
+
+impl Lexer {
+    /// Compiles the definitions of every mode, see [`Dfa::compile`]. Also fails when a token enters a
+    /// mode that has no tokens.
+    pub fn compile(definitions: &[TokenDefinition]) -> Result<Self, String> {
+        let mut names = vec![INITIAL_MODE.to_string()];
+        for mode in definitions.iter().flat_map(|d| &d.modes) {
+            if !names.contains(mode) {
+                names.push(mode.clone());
+            }
+        }
+
+        for definition in definitions {
+            if let Some(ModeAction::Push(mode)) = &definition.action {
+                if !names.contains(mode) {
+                    return Err(format!(
+                        "`{}` enters mode `{}`, which has no tokens",
+                        definition.name, mode
+                    ));
+                }
+            }
+        }
+
+        let modes = names
+            .into_iter()
+            .map(|mode| {
+                let in_mode = definitions
+                    .iter()
+                    .filter(|d| d.in_mode(&mode))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                match Dfa::compile(&in_mode) {
+                    Ok(dfa) => Ok((mode, dfa)),
+                    Err(e) if mode == INITIAL_MODE => Err(e),
+                    Err(e) => Err(format!("in mode `{}`: {}", mode, e)),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let actions = definitions
+            .iter()
+            .filter_map(|d| Some((d.name.clone(), d.action.clone()?)))
+            .collect();
+
+        Ok(Self { modes, actions })
+    }
+
+    /// The names of the modes with their automata.
+    pub fn modes(&self) -> impl Iterator<Item = (&str, &Dfa)> {
+        self.modes.iter().map(|(name, dfa)| (name.as_str(), dfa))
+    }
+
+    /// The overlaps of all modes.
+    pub fn overlaps(&self) -> Vec<Overlap> {
+        self.modes
+            .iter()
+            .flat_map(|(_, dfa)| dfa.overlaps().iter().cloned())
+            .collect()
+    }
+
+    fn mode_index(&self, mode: &str) -> usize {
+        self.modes
+            .iter()
+            .position(|(name, _)| name == mode)
+            .unwrap()
+    }
+
+    /// Renders a `LEXER` constant with `lex` and `scan` functions producing tokens of `token_type`, of
+    /// which the `trivia` are always accepted by the parser. With more than one mode, the indices of
+    /// the modes are rendered as constants in a `mode` module.
+    pub fn render(&self, token_type: &str, trivia: &[String]) -> String {
+        let mut out = String::new();
+
+        if self.modes.len() > 1 {
+            out.push_str("/// The modes of the lexer, which can be entered from lexer callbacks.\npub mod mode {\n");
+            for (i, (name, _)) in self.modes.iter().enumerate() {
+                out.push_str(&format!(
+                    "    pub const {}: usize = {};\n",
+                    crate::generator::snake_case(format_args!("{}", name)).to_uppercase(),
+                    i
+                ));
+            }
+            out.push_str("}\n\n");
+        }
+
+        out.push_str(&format!(
+            "/// Recognises the tokens of the grammar, taking the longest match and otherwise the token with priority.\n\
+             pub static LEXER: yaw::runtime::lexer::Lexer<{tt}> = yaw::runtime::lexer::Lexer {{\n    \
+             modes: &[\n",
+            tt = token_type
+        ));
+        for (_, dfa) in &self.modes {
+            dfa.render_mode(token_type, &mut out);
+        }
+
+        let actions = self
+            .actions
+            .iter()
+            .map(|(token, action)| {
+                let action = match action {
+                    ModeAction::Push(mode) => format!("Push({})", self.mode_index(mode)),
+                    ModeAction::Pop => "Pop".to_string(),
+                };
+                format!(
+                    "\n        ({}::{}, yaw::runtime::lexer::ModeAction::{}),",
+                    token_type, token, action
+                )
+            })
+            .collect::<String>();
+        let actions = if actions.is_empty() {
+            actions
+        } else {
+            actions + "\n    "
+        };
+
+        out.push_str(&format!(
+            "    ],\n    actions: &[{actions}],\n    trivia: &[{trivia}],\n    eof: {tt}::Eof,\n}};\n\n\
+             pub fn lex(source: &str) -> Result<Vec<Token<{tt}>>, yaw::runtime::lexer::LexError> {{\n    \
+             LEXER.tokenize(source)\n}}\n\n\
+             /// Lexes `source` while parsing, preferring the tokens the parser expects.\n\
+             pub fn scan(source: &str) -> yaw::runtime::lexer::Scanner<'_, {tt}> {{\n    \
+             LEXER.scan(source)\n}}\n",
+            tt = token_type,
+            actions = actions,
+            trivia = kinds(token_type, trivia.iter().collect())
+        ));
+
+        out
+    }
+}
+
+/// Renders the `names` as a list of variants of `token_type`.
+fn kinds(token_type: &str, names: Vec<&String>) -> String {
+    names
+        .iter()
+        .map(|name| format!("{}::{}", token_type, name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Finds every pair of definitions that accept the same text, with the shortest such text.
+fn overlaps(
+    moves: &[Vec<Option<usize>>],
+    accepts: &[Vec<usize>],
+    intervals: &[(u32, u32)],
+) -> Vec<(usize, usize, String)> {
+    // The shortest text leading to every state, found breadth first.
+    let mut examples = vec![None; moves.len()];
+    examples[0] = Some(String::new());
+    let mut queue = VecDeque::from(vec![0]);
+
+    while let Some(state) = queue.pop_front() {
+        for (interval, next) in moves[state].iter().enumerate() {
+            if let Some(next) = *next {
+                if examples[next].is_none() {
+                    let mut example = examples[state].clone().unwrap();
+                    example.push(example_char(intervals[interval]));
+                    examples[next] = Some(example);
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+
+    let mut found: Vec<(usize, usize, String)> = Vec::new();
+    for (tokens, example) in accepts.iter().zip(examples) {
+        let example = example.unwrap();
+        for &loser in tokens.iter().skip(1) {
+            let pair = (tokens[0], loser);
+            match found.iter_mut().find(|(w, l, _)| (*w, *l) == pair) {
+                Some(existing) if example.chars().count() < existing.2.chars().count() => {
+                    existing.2 = example.clone()
+                }
+                Some(_) => {}
+                None => found.push((pair.0, pair.1, example.clone())),
+            }
+        }
+    }
+
+    found.sort();
+    found
+}
+
+/// A character in `interval`, preferring one that is printable.
+fn example_char((start, end): (u32, u32)) -> char {
+    (start..=end.min(start + 0x80))
+        .filter_map(char::from_u32)
+        .find(|c| !c.is_control())
+        .or_else(|| (start..=end).find_map(char::from_u32))
+        .unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+/// Merges equivalent states of the automaton by partition refinement, returning the transitions
+/// and accepted definitions of the minimal automaton with states numbered breadth first.
+#[allow(clippy::type_complexity)]
+fn minimize(
+    moves: &[Vec<Option<usize>>],
+    accepts: &[Vec<usize>],
+    intervals: &[(u32, u32)],
+) -> (Vec<Vec<(char, char, usize)>>, Vec<Vec<usize>>) {
+    let mut initial = HashMap::new();
+    let mut class = accepts
+        .iter()
+        .map(|tokens| {
+            let next = initial.len();
+            *initial.entry(tokens).or_insert(next)
+        })
+        .collect::<Vec<_>>();
+    let mut count = 0;
+
+    loop {
+        let mut signatures = HashMap::new();
+        let refined = (0..moves.len())
+            .map(|state| {
+                let signature = (
+                    class[state],
+                    moves[state]
+                        .iter()
+                        .map(|next| next.map(|n| class[n]))
+                        .collect::<Vec<_>>(),
+                );
+                let next = signatures.len();
+                *signatures.entry(signature).or_insert(next)
+            })
+            .collect::<Vec<_>>();
+
+        class = refined;
+        if signatures.len() == count {
+            break;
+        }
+        count = signatures.len();
+    }
+
+    // Number the classes in the order they are reached from the start state.
+    let mut numbers = vec![None; count];
+    let mut representatives = Vec::new();
+    let mut queue = VecDeque::from(vec![0]);
+    numbers[class[0]] = Some(0);
+
+    while let Some(state) = queue.pop_front() {
+        representatives.push(state);
+        for next in moves[state].iter().flatten() {
+            if numbers[class[*next]].is_none() {
+                numbers[class[*next]] = Some(representatives.len() + queue.len());
+                queue.push_back(*next);
+            }
+        }
+    }
+
+    let transitions = representatives
+        .iter()
+        .map(|&state| {
+            let mut row: Vec<(u32, u32, usize)> = Vec::new();
+            for (&(start, end), next) in intervals.iter().zip(&moves[state]) {
+                let next = match next {
+                    Some(next) => numbers[class[*next]].unwrap(),
+                    None => continue,
+                };
+                match row.last_mut() {
+                    Some(last) if last.1 + 1 == start && last.2 == next => last.1 = end,
+                    _ => row.push((start, end, next)),
+                }
+            }
+            row.into_iter().filter_map(char_range).collect()
+        })
+        .collect();
+    let accept = representatives
+        .iter()
+        .map(|&s| accepts[s].clone())
+        .collect();
+
+    (transitions, accept)
+}
+
+/// Converts a range of code points to a range of characters, leaving out the surrogates at its ends.
+fn char_range((start, end, next): (u32, u32, usize)) -> Option<(char, char, usize)> {
+    let start =
+        char::from_u32(start).or_else(|| char::from_u32(0xE000).filter(|_| end >= 0xE000))?;
+    let end = char::from_u32(end).unwrap_or('\u{D7FF}');
+    if start <= end {
+        Some((start, end, next))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn definitions(patterns: &[(&str, Pattern)]) -> Vec<TokenDefinition> {
+        patterns
+            .iter()
+            .map(|(name, pattern)| TokenDefinition::new(*name, pattern.clone()))
+            .collect()
+    }
+
+    fn regex(pattern: &str) -> Pattern {
+        Pattern::Regex(pattern.into())
+    }
+
+    fn literal(text: &str) -> Pattern {
+        Pattern::Literal(text.into())
+    }
+
+    #[test]
+    fn takes_longest_match_then_literals() {
+        let dfa = Dfa::compile(&definitions(&[
+            ("Identifier", regex("[a-z]+")),
+            ("If", literal("if")),
+            ("Equals", literal("=")),
+            ("EqualsEquals", literal("==")),
+        ]))
+        .unwrap();
+
+        assert_eq!(dfa.longest_match("if x"), Some(("If", 2)));
+        assert_eq!(dfa.longest_match("iffy"), Some(("Identifier", 4)));
+        assert_eq!(dfa.longest_match("==="), Some(("EqualsEquals", 2)));
+        assert_eq!(dfa.longest_match("+"), None);
+        assert_eq!(
+            dfa.overlaps(),
+            [Overlap {
+                winner: "If".into(),
+                loser: "Identifier".into(),
+                example: "if".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn minimizes_states() {
+        // Both alternatives end in the same state after minimization: start, a or b, and accepting.
+        let dfa = Dfa::compile(&definitions(&[("Word", regex("ab+|bb+"))])).unwrap();
+        assert_eq!(dfa.state_count(), 3);
+        assert_eq!(dfa.longest_match("bbbc"), Some(("Word", 3)));
+    }
+
+    #[test]
+    fn rejects_unmatchable_definitions() {
+        let error = Dfa::compile(&definitions(&[
+            ("Identifier", regex(r"\w+")),
+            ("If", regex("if")),
+        ]))
+        .unwrap_err();
+        assert_eq!(
+            error,
+            "`If` can never be matched, since `Identifier` takes priority"
+        );
+
+        let error = Dfa::compile(&definitions(&[("Space", regex(" *"))])).unwrap_err();
+        assert_eq!(error, "`Space` matches the empty string");
+
+        let error = Dfa::compile(&definitions(&[("Nothing", regex(r"[^\s\S]"))])).unwrap_err();
+        assert_eq!(error, "`Nothing` does not match any text");
+    }
+
+    #[test]
+    fn compiles_modes() {
+        let mut definitions = definitions(&[
+            ("Quote", literal("'")),
+            ("Text", regex("[^']+")),
+            ("EndQuote", literal("'")),
+        ]);
+        definitions[0].action = Some(ModeAction::Push("String".into()));
+        definitions[1].modes = vec!["String".into()];
+        definitions[2].modes = vec!["String".into()];
+        let lexer = Lexer::compile(&definitions).unwrap();
+        let modes = lexer.modes().collect::<Vec<_>>();
+
+        assert_eq!(modes[0].0, INITIAL_MODE);
+        assert_eq!(modes[0].1.longest_match("'a"), Some(("Quote", 1)));
+        assert_eq!(modes[1].1.longest_match("a'"), Some(("Text", 1)));
+        assert_eq!(modes[1].1.longest_match("'"), Some(("EndQuote", 1)));
+
+        definitions[0].action = Some(ModeAction::Push("Other".into()));
+        assert_eq!(
+            Lexer::compile(&definitions).unwrap_err(),
+            "`Quote` enters mode `Other`, which has no tokens"
+        );
+    }
+}
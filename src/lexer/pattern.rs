@@ -0,0 +1,354 @@
+//! Parser for the regular expressions of token definitions.
+//!
+//! The supported syntax is a subset of the `regex` crate: alternation `|`, groups `(...)`, the
+//! repetitions `*`, `+`, `?`, `{n}`, `{n,}` and `{n,m}`, the wildcard `.` matching anything but a
+//! newline, character classes such as `[a-z_]` and `[^"]`, and the escapes `\d`, `\w`, `\s`, their
+//! negations `\D`, `\W`, `\S`, `\n`, `\r`, `\t`, `\0` and `\u{...}`. Any other punctuation can be
+//! escaped to match it literally.
+
+/// The largest number of repetitions that can be given between braces.
+const MAX_REPEAT: usize = 256;
+const MAX_CHAR: u32 = char::MAX as u32;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Regex {
+    /// Any single character in one of the sorted, disjoint, inclusive ranges.
+    Class(Vec<(u32, u32)>),
+    Concat(Vec<Regex>),
+    Alternation(Vec<Regex>),
+    Repeat(Box<Regex>, usize, Option<usize>),
+}
+
+impl Regex {
+    /// The regex matching exactly `text`.
+    pub fn literal(text: &str) -> Self {
+        Self::Concat(
+            text.chars()
+                .map(|c| Self::Class(vec![(c as u32, c as u32)]))
+                .collect(),
+        )
+    }
+}
+
+/// Parses `pattern`, returning a description of the first error.
+pub fn parse(pattern: &str) -> Result<Regex, String> {
+    let mut parser = Parser {
+        chars: pattern.chars().collect(),
+        pos: 0,
+    };
+    let regex = parser.alternation()?;
+
+    match parser.peek() {
+        None => Ok(regex),
+        Some(c) => parser.error(format!("unmatched `{}`", c)),
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn error<R>(&self, message: impl Into<String>) -> Result<R, String> {
+        Err(format!("{} at position {}", message.into(), self.pos))
+    }
+
+    fn alternation(&mut self) -> Result<Regex, String> {
+        let mut alternatives = vec![self.concat()?];
+        while self.eat('|') {
+            alternatives.push(self.concat()?);
+        }
+
+        Ok(if alternatives.len() == 1 {
+            alternatives.pop().unwrap()
+        } else {
+            Regex::Alternation(alternatives)
+        })
+    }
+
+    fn concat(&mut self) -> Result<Regex, String> {
+        let mut items = Vec::new();
+        while !matches!(self.peek(), None | Some('|') | Some(')')) {
+            items.push(self.repeat()?);
+        }
+
+        Ok(if items.len() == 1 {
+            items.pop().unwrap()
+        } else {
+            Regex::Concat(items)
+        })
+    }
+
+    fn repeat(&mut self) -> Result<Regex, String> {
+        let mut regex = self.atom()?;
+
+        loop {
+            let (min, max) = match self.peek() {
+                Some('*') => (0, None),
+                Some('+') => (1, None),
+                Some('?') => (0, Some(1)),
+                Some('{') => {
+                    self.bump();
+                    let min = self.number()?;
+                    let max = if self.eat(',') {
+                        match self.peek() {
+                            Some('}') => None,
+                            _ => Some(self.number()?),
+                        }
+                    } else {
+                        Some(min)
+                    };
+                    if self.peek() != Some('}') {
+                        return self.error("expected `}` to close the repetition");
+                    }
+                    if max.is_some_and(|max| max < min) {
+                        return self.error("the minimum of a repetition exceeds its maximum");
+                    }
+                    (min, max)
+                }
+                _ => break Ok(regex),
+            };
+            self.bump();
+            regex = Regex::Repeat(Box::new(regex), min, max);
+        }
+    }
+
+    fn number(&mut self) -> Result<usize, String> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.bump();
+        }
+
+        match self.chars[start..self.pos]
+            .iter()
+            .collect::<String>()
+            .parse()
+        {
+            Ok(n) if n <= MAX_REPEAT => Ok(n),
+            Ok(_) => self.error(format!("repetitions are limited to {}", MAX_REPEAT)),
+            Err(_) => self.error("expected a number"),
+        }
+    }
+
+    fn atom(&mut self) -> Result<Regex, String> {
+        match self.bump() {
+            Some('(') => {
+                let regex = self.alternation()?;
+                if !self.eat(')') {
+                    return self.error("expected `)`");
+                }
+                Ok(regex)
+            }
+            Some('[') => self.class(),
+            Some('.') => Ok(Regex::Class(complement(vec![('\n' as u32, '\n' as u32)]))),
+            Some('\\') => Ok(Regex::Class(self.escape()?)),
+            Some(c @ '*') | Some(c @ '+') | Some(c @ '?') | Some(c @ '{') => {
+                self.pos -= 1;
+                self.error(format!("`{}` has nothing to repeat", c))
+            }
+            Some(c) => Ok(Regex::Class(vec![(c as u32, c as u32)])),
+            None => self.error("unexpected end of pattern"),
+        }
+    }
+
+    /// Reads the escape after a consumed backslash as the ranges it matches.
+    fn escape(&mut self) -> Result<Vec<(u32, u32)>, String> {
+        let single = |c: char| vec![(c as u32, c as u32)];
+
+        let ranges = match self.bump() {
+            Some('d') => digit(),
+            Some('w') => word(),
+            Some('s') => space(),
+            Some('D') => complement(digit()),
+            Some('W') => complement(word()),
+            Some('S') => complement(space()),
+            Some('n') => single('\n'),
+            Some('r') => single('\r'),
+            Some('t') => single('\t'),
+            Some('0') => single('\0'),
+            Some('u') => {
+                if !self.eat('{') {
+                    return self.error("expected `{` after `\\u`");
+                }
+                let start = self.pos;
+                while self.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+                    self.bump();
+                }
+                let digits = self.chars[start..self.pos].iter().collect::<String>();
+                let c = u32::from_str_radix(&digits, 16)
+                    .ok()
+                    .and_then(char::from_u32);
+
+                match c {
+                    Some(c) if self.eat('}') => single(c),
+                    _ => return self.error("expected a unicode scalar value in `\\u{...}`"),
+                }
+            }
+            Some(c) if c.is_ascii_punctuation() || c == ' ' => single(c),
+            Some(c) => return self.error(format!("unknown escape `\\{}`", c)),
+            None => return self.error("unexpected end of pattern"),
+        };
+
+        Ok(ranges)
+    }
+
+    fn class(&mut self) -> Result<Regex, String> {
+        let negated = self.eat('^');
+        let mut ranges = Vec::new();
+
+        loop {
+            let start = match self.bump() {
+                Some(']') => break,
+                Some('\\') => {
+                    let escaped = self.escape()?;
+                    if escaped.len() != 1 || escaped[0].0 != escaped[0].1 {
+                        // A class escape like `\d` cannot start a range.
+                        ranges.extend(escaped);
+                        continue;
+                    }
+                    escaped[0].0
+                }
+                Some(c) => c as u32,
+                None => return self.error("unclosed character class"),
+            };
+
+            if self.peek() == Some('-') && self.chars.get(self.pos + 1) != Some(&']') {
+                self.bump();
+                let end = match self.bump() {
+                    Some('\\') => match self.escape()?.as_slice() {
+                        [(c, d)] if c == d => *c,
+                        _ => return self.error("a class escape cannot end a range"),
+                    },
+                    Some(c) => c as u32,
+                    None => return self.error("unclosed character class"),
+                };
+                if end < start {
+                    return self.error("the end of a range comes before its start");
+                }
+                ranges.push((start, end));
+            } else {
+                ranges.push((start, start));
+            }
+        }
+
+        let ranges = normalize(ranges);
+        Ok(Regex::Class(if negated {
+            complement(ranges)
+        } else {
+            ranges
+        }))
+    }
+}
+
+fn digit() -> Vec<(u32, u32)> {
+    vec![('0' as u32, '9' as u32)]
+}
+
+fn word() -> Vec<(u32, u32)> {
+    normalize(vec![
+        ('0' as u32, '9' as u32),
+        ('A' as u32, 'Z' as u32),
+        ('_' as u32, '_' as u32),
+        ('a' as u32, 'z' as u32),
+    ])
+}
+
+fn space() -> Vec<(u32, u32)> {
+    normalize(vec![('\t' as u32, '\r' as u32), (' ' as u32, ' ' as u32)])
+}
+
+/// Sorts `ranges` and merges the ones that overlap or touch.
+fn normalize(mut ranges: Vec<(u32, u32)>) -> Vec<(u32, u32)> {
+    ranges.sort_unstable();
+    let mut merged: Vec<(u32, u32)> = Vec::new();
+
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    merged
+}
+
+/// The ranges of all characters that are not in the normalized `ranges`.
+fn complement(ranges: Vec<(u32, u32)>) -> Vec<(u32, u32)> {
+    let mut result = Vec::new();
+    let mut next = 0;
+
+    for (start, end) in ranges {
+        if start > next {
+            result.push((next, start - 1));
+        }
+        next = end + 1;
+    }
+    if next <= MAX_CHAR {
+        result.push((next, MAX_CHAR));
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn class(ranges: &[(char, char)]) -> Regex {
+        Regex::Class(ranges.iter().map(|&(a, b)| (a as u32, b as u32)).collect())
+    }
+
+    #[test]
+    fn parses_operators_and_classes() {
+        assert_eq!(
+            parse("a|[b-d_]+").unwrap(),
+            Regex::Alternation(vec![
+                class(&[('a', 'a')]),
+                Regex::Repeat(Box::new(class(&[('_', '_'), ('b', 'd')])), 1, None),
+            ])
+        );
+        assert_eq!(
+            parse(r"(\d){2,}\.?").unwrap(),
+            Regex::Concat(vec![
+                Regex::Repeat(Box::new(class(&[('0', '9')])), 2, None),
+                Regex::Repeat(Box::new(class(&[('.', '.')])), 0, Some(1)),
+            ])
+        );
+        assert_eq!(
+            parse("[^a]").unwrap(),
+            Regex::Class(vec![(0, 'a' as u32 - 1), ('b' as u32, MAX_CHAR)])
+        );
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert_eq!(parse("a)").unwrap_err(), "unmatched `)` at position 1");
+        assert_eq!(
+            parse("*").unwrap_err(),
+            "`*` has nothing to repeat at position 0"
+        );
+        assert!(parse("[a").is_err());
+        assert!(parse("a{3,2}").is_err());
+        assert!(parse(r"\q").is_err());
+    }
+}
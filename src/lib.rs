@@ -1,7 +1,15 @@
 pub mod analysis;
+pub mod ast;
+pub mod build;
 pub mod common;
+pub mod dot;
 pub mod generator;
+pub mod lexer;
+pub mod messages;
+pub mod railroad;
+pub mod report;
 pub mod runtime;
+pub mod syntax;
 
 mod test {
     #[test]
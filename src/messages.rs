@@ -0,0 +1,420 @@
+//! Hand-written syntax error messages, read from a file in the format of Menhir's `.messages` files.
+//!
+//! The file is a list of entries. An entry starts with one or more sentences, each on its own line,
+//! followed by a blank line and the message, which runs until the next blank line. A sentence is a
+//! sequence of terminal names separated by whitespace, which the parser rejects at its last terminal.
+//! The state in which it does so identifies the entry, and the message is used for every error found
+//! in that state. Lines starting with `#` outside of messages are comments.
+//!
+//! ```text
+//! # A statement without a name.
+//! Let Equals
+//! Let Semicolon
+//!
+//! Expected the name of the variable after `let`.
+//! ```
+//!
+//! Since entries are written as sentences, they keep working when the states of the automaton are
+//! renumbered, and checking them tells which ones no longer end in an error after the grammar changed.
+
+use crate::analysis::{Action, Automaton, StateIndex, Table};
+use crate::common::{Grammar, Terminal};
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::hash::Hash;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sentence {
+    pub terminals: Vec<String>,
+    /// The line of the sentence in the file, counting from 1.
+    pub line: usize,
+}
+
+impl fmt::Display for Sentence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.terminals.join(" "))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub sentences: Vec<Sentence>,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Messages {
+    pub entries: Vec<Entry>,
+}
+
+impl Messages {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut entries = Vec::new();
+        let mut lines = source.lines().enumerate().map(|(i, line)| (i + 1, line));
+
+        loop {
+            let mut sentences = Vec::new();
+            for (line, text) in lines.by_ref() {
+                let text = text.trim();
+                if text.starts_with('#') {
+                    continue;
+                } else if text.is_empty() && !sentences.is_empty() {
+                    break;
+                } else if !text.is_empty() {
+                    let terminals = text.split_whitespace().map(str::to_owned).collect();
+                    sentences.push(Sentence { terminals, line });
+                }
+            }
+            let last_line = match sentences.last() {
+                Some(sentence) => sentence.line,
+                None => return Ok(Self { entries }),
+            };
+
+            let message = lines
+                .by_ref()
+                .map(|(_, text)| text.trim_end())
+                .skip_while(|text| text.is_empty())
+                .take_while(|text| !text.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n");
+            if message.is_empty() {
+                return Err(format!(
+                    "line {}: expected a message after the sentences",
+                    last_line
+                ));
+            }
+
+            entries.push(Entry { sentences, message });
+        }
+    }
+
+    /// Finds the state each sentence is rejected in, and pairs it with the message of its entry.
+    /// Returns the messages ordered by state, or a description of every sentence that does not
+    /// identify a state with a message of its own.
+    pub fn resolve<T, NT>(
+        &self,
+        grammar: &Grammar<T, NT>,
+        table: &Table<T, NT>,
+    ) -> Result<Vec<(StateIndex, &str)>, Vec<String>>
+    where
+        T: Terminal,
+        NT: Eq + Hash + Copy + Debug,
+    {
+        let mut terminals = grammar
+            .unique_terminals()
+            .into_iter()
+            .map(|t| (format!("{:?}", t), t))
+            .collect::<HashMap<_, _>>();
+        terminals.insert(format!("{:?}", T::eof()), T::eof());
+
+        let mut resolved: Vec<(StateIndex, &str, usize)> = Vec::new();
+        let mut errors = Vec::new();
+
+        for entry in &self.entries {
+            for sentence in &entry.sentences {
+                let state = sentence
+                    .terminals
+                    .iter()
+                    .map(|name| {
+                        terminals
+                            .get(name)
+                            .copied()
+                            .ok_or_else(|| format!("unknown terminal `{}`", name))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+                    .and_then(|sentence| error_state(table, &sentence));
+
+                match state {
+                    Ok(state) => match resolved.iter().find(|r| r.0 == state) {
+                        Some(&(_, message, line)) if message != entry.message => {
+                            errors.push(format!(
+                                "line {}: `{}` is rejected in state {}, which has another message on line {}",
+                                sentence.line, sentence, state, line
+                            ))
+                        }
+                        Some(_) => {}
+                        None => resolved.push((state, &entry.message, sentence.line)),
+                    },
+                    Err(error) => errors.push(format!("line {}: {}", sentence.line, error)),
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        resolved.sort_unstable_by_key(|r| r.0);
+        Ok(resolved
+            .into_iter()
+            .map(|(state, message, _)| (state, message))
+            .collect())
+    }
+
+    /// Messages with an entry for each state of the `examples`, holding their sentences and the same
+    /// `message`, to be filled in by hand.
+    pub fn template<T: Debug>(examples: &[ErrorExample<T>], message: &str) -> Self {
+        let mut entries = Vec::new();
+        let mut line = 0;
+
+        for (i, example) in examples.iter().enumerate() {
+            let sentence = Sentence {
+                terminals: example
+                    .sentence
+                    .iter()
+                    .map(|t| format!("{:?}", t))
+                    .collect(),
+                line: 0,
+            };
+            if i > 0 && examples[i - 1].state == example.state {
+                line += 1;
+                let entry: &mut Entry = entries.last_mut().unwrap();
+                entry.sentences.push(Sentence { line, ..sentence });
+            } else {
+                // After the previous sentence come a blank line, the message and another blank line.
+                line += if i > 0 {
+                    message.lines().count() + 3
+                } else {
+                    1
+                };
+                entries.push(Entry {
+                    sentences: vec![Sentence { line, ..sentence }],
+                    message: message.to_owned(),
+                });
+            }
+        }
+
+        Self { entries }
+    }
+}
+
+/// Writes the messages in the format they are read in.
+impl fmt::Display for Messages {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, entry) in self.entries.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for sentence in &entry.sentences {
+                writeln!(f, "{}", sentence)?;
+            }
+            writeln!(f, "\n{}", entry.message)?;
+        }
+        Ok(())
+    }
+}
+
+/// A shortest input the parser rejects in a state, at the last terminal of the sentence.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ErrorExample<T> {
+    pub state: StateIndex,
+    pub sentence: Vec<T>,
+}
+
+/// Every pair of a state and a terminal without an action in it, as the shortest sentence leading to
+/// the state followed by the terminal. Ordered by state and then by the name of the terminal.
+///
+/// Only the start state and the states entered on a terminal find errors. A state entered on a
+/// non-terminal is only reached by a reduction on a terminal it has an action for.
+pub fn error_examples<T, NT>(
+    automaton: &Automaton<T, NT>,
+    table: &Table<T, NT>,
+) -> Vec<ErrorExample<T>>
+where
+    T: Terminal,
+    NT: Eq + Hash + Copy + Debug,
+{
+    let mut terminals = automaton
+        .grammar()
+        .unique_terminals()
+        .into_iter()
+        .filter(|t| !t.is_error())
+        .collect::<Vec<_>>();
+    terminals.push(T::eof());
+    terminals.sort_by_cached_key(|t| format!("{:?}", t));
+    terminals.dedup();
+
+    let mut entered_on_terminal = vec![false; automaton.states.len()];
+    for edges in &automaton.transitions {
+        for (sym, &to_state) in edges {
+            entered_on_terminal[to_state] |= sym.is_terminal();
+        }
+    }
+
+    let mut examples = Vec::new();
+    for (state, sentence) in automaton.shortest_sentences().into_iter().enumerate() {
+        let sentence = match sentence {
+            Some(sentence) if state == 0 || entered_on_terminal[state] => sentence,
+            _ => continue,
+        };
+        let row = table.actions().get(&state);
+
+        for &term in &terminals {
+            if row.is_none_or(|row| !row.contains_key(&term)) {
+                let mut sentence = sentence.clone();
+                sentence.push(term);
+                examples.push(ErrorExample { state, sentence });
+            }
+        }
+    }
+
+    examples
+}
+
+/// The states in which the parser can find an error, see [`error_examples`].
+pub fn error_states<T, NT>(automaton: &Automaton<T, NT>, table: &Table<T, NT>) -> Vec<StateIndex>
+where
+    T: Terminal,
+    NT: Eq + Hash + Copy + Debug,
+{
+    let mut states = error_examples(automaton, table)
+        .into_iter()
+        .map(|example| example.state)
+        .collect::<Vec<_>>();
+    states.dedup();
+    states
+}
+
+/// Runs the parse table on `sentence`, returning the state in which its last terminal is rejected.
+fn error_state<T, NT>(table: &Table<T, NT>, sentence: &[T]) -> Result<StateIndex, String>
+where
+    T: Terminal,
+    NT: Eq + Hash + Copy + Debug,
+{
+    let mut stack = vec![0];
+
+    for (i, terminal) in sentence.iter().enumerate() {
+        loop {
+            let state = *stack.last().unwrap();
+            match table
+                .actions()
+                .get(&state)
+                .and_then(|row| row.get(terminal))
+            {
+                Some(Action::Shift(to_state)) => {
+                    stack.push(*to_state);
+                    break;
+                }
+                Some(Action::Reduce(rule)) => {
+                    stack.truncate(stack.len() - rule.symbols().len());
+                    let state = *stack.last().unwrap();
+                    stack.push(table.gotos()[&state][&rule.lhs()]);
+                }
+                Some(Action::Accept) => {
+                    return Err(format!("the sentence is accepted at `{:?}`", terminal))
+                }
+                None | Some(Action::Error) if i + 1 == sentence.len() => return Ok(state),
+                None | Some(Action::Error) => {
+                    return Err(format!(
+                        "the sentence is rejected at `{:?}`, before its last terminal",
+                        terminal
+                    ))
+                }
+            }
+        }
+    }
+
+    Err("the sentence is not rejected at its last terminal".into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::analysis::Analyser;
+    use crate::syntax;
+
+    const MESSAGES: &str = "\
+# Statements without a name.
+Let Equals
+Let Semicolon
+
+Expected a name after `let`.
+Names may not be keywords.
+
+Let Identifier Semicolon
+Let Identifier Number
+
+Expected `=`.
+";
+
+    #[test]
+    fn reads_entries() {
+        let messages = Messages::parse(MESSAGES).unwrap();
+
+        assert_eq!(messages.entries.len(), 2);
+        assert_eq!(
+            messages.entries[0].message,
+            "Expected a name after `let`.\nNames may not be keywords."
+        );
+        assert_eq!(messages.entries[1].sentences[1].line, 9);
+        // Comments are not kept.
+        let written = messages.to_string();
+        assert_eq!(Messages::parse(&written).unwrap().to_string(), written);
+        assert!(written.starts_with("Let Equals\n"));
+        assert_eq!(
+            Messages::parse("Let\n\n").unwrap_err(),
+            "line 1: expected a message after the sentences"
+        );
+    }
+
+    #[test]
+    fn resolves_sentences_to_states() {
+        let file = syntax::parse(
+            "Start: Statements; Statements: Statements Statement | ;
+             Statement: Let Identifier Equals Number Semicolon;",
+        )
+        .unwrap();
+        let table = Analyser::table(file.grammar());
+        let messages = Messages::parse(MESSAGES).unwrap();
+
+        let resolved = messages.resolve(file.grammar(), &table).unwrap();
+        assert_eq!(resolved.len(), 2);
+        assert!(resolved[0].0 < resolved[1].0);
+        let automaton = Analyser::new(file.grammar()).automaton();
+        assert!(error_states(&automaton, &table).contains(&resolved[0].0));
+
+        let messages = Messages::parse(
+            "Let Identifier\n\nIncomplete.\n\nLet Equals Let\n\nOther.\n\nLet Comma\n\nComma.\n",
+        )
+        .unwrap();
+        assert_eq!(
+            messages.resolve(file.grammar(), &table).unwrap_err(),
+            [
+                "line 1: the sentence is not rejected at its last terminal",
+                "line 5: the sentence is rejected at `Equals`, before its last terminal",
+                "line 9: unknown terminal `Comma`"
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_shortest_errors() {
+        let file = syntax::parse(
+            "Start: Statements; Statements: Statements Statement | ;
+             Statement: Let Identifier Equals Number Semicolon;",
+        )
+        .unwrap();
+        let automaton = Analyser::new(file.grammar()).automaton();
+        let table = Analyser::table(file.grammar());
+        let examples = error_examples(&automaton, &table);
+
+        let sentences = examples
+            .iter()
+            .map(|e| format!("{:?}", e.sentence))
+            .collect::<Vec<_>>();
+        assert!(sentences.contains(&"[Identifier]".to_owned()));
+        assert!(sentences.contains(&"[Let, Identifier, Equals, Number, Number]".to_owned()));
+        // Every example is rejected at its last terminal, in the state it was found for.
+        for example in &examples {
+            assert_eq!(error_state(&table, &example.sentence), Ok(example.state));
+        }
+        // The start state and the states after each terminal of a statement.
+        assert_eq!(error_states(&automaton, &table).len(), 6);
+
+        let template = Messages::template(&examples, "Message.");
+        let resolved = template.resolve(file.grammar(), &table).unwrap();
+        assert_eq!(resolved.len(), 6);
+        let written = template.to_string();
+        let reread = Messages::parse(&written).unwrap();
+        assert_eq!(reread.entries, template.entries);
+    }
+}
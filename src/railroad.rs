@@ -0,0 +1,490 @@
+//! Railroad (syntax) diagrams of a grammar, rendered as standalone SVG.
+//!
+//! Every non-terminal gets its own diagram in which its rules are folded into a single choice.
+//! Left and right recursive rules that describe lists, such as `Args: Args Comma Expr | Expr`,
+//! are drawn as loops instead of references to the non-terminal itself.
+
+use crate::common::{Grammar, Symbol, Terminal};
+use std::fmt::{Debug, Write};
+use std::fs;
+use std::hash::Hash;
+use std::io;
+use std::path::Path;
+
+const ARC: f64 = 10.0;
+const VERTICAL_GAP: f64 = 10.0;
+const CHAR_WIDTH: f64 = 8.0;
+const BOX_HEIGHT: f64 = 22.0;
+const MARGIN: f64 = 20.0;
+
+const STYLE: &str = "\
+path { stroke: #333; stroke-width: 2; fill: none; }
+rect { stroke: #333; stroke-width: 2; fill: #eef5e9; }
+rect.nonterminal { fill: #e8eef8; }
+text { font: 13px monospace; text-anchor: middle; }
+a text { fill: #1a4fa0; }";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Node {
+    Terminal(String),
+    NonTerminal(String),
+    Sequence(Vec<Node>),
+    /// The first alternative is drawn on the main line, the others below it.
+    Choice(Vec<Node>),
+    /// Passes through `body` at least once, taking `repeat` every time it goes around.
+    OneOrMore {
+        body: Box<Node>,
+        repeat: Box<Node>,
+    },
+    Skip,
+}
+
+impl Node {
+    fn optional(node: Node) -> Self {
+        Self::Choice(vec![Self::Skip, node])
+    }
+
+    fn zero_or_more(body: Node, repeat: Node) -> Self {
+        Self::optional(Self::OneOrMore {
+            body: Box::new(body),
+            repeat: Box::new(repeat),
+        })
+    }
+
+    fn box_width(text: &str) -> f64 {
+        text.chars().count() as f64 * CHAR_WIDTH + 2.0 * ARC
+    }
+
+    pub fn width(&self) -> f64 {
+        match self {
+            Self::Terminal(text) | Self::NonTerminal(text) => Self::box_width(text) + 2.0 * ARC,
+            Self::Sequence(nodes) => nodes.iter().map(Node::width).sum(),
+            Self::Choice(nodes) => max(nodes.iter().map(Node::width)) + 4.0 * ARC,
+            Self::OneOrMore { body, repeat } => body.width().max(repeat.width()) + 4.0 * ARC,
+            Self::Skip => 0.0,
+        }
+    }
+
+    /// The height above the line entering and leaving the node.
+    pub fn up(&self) -> f64 {
+        match self {
+            Self::Terminal(_) | Self::NonTerminal(_) => BOX_HEIGHT / 2.0,
+            Self::Sequence(nodes) => max(nodes.iter().map(Node::up)),
+            Self::Choice(nodes) => nodes.first().map_or(0.0, Node::up),
+            Self::OneOrMore { body, .. } => body.up(),
+            Self::Skip => 0.0,
+        }
+    }
+
+    /// The height below the line entering and leaving the node.
+    pub fn down(&self) -> f64 {
+        match self {
+            Self::Terminal(_) | Self::NonTerminal(_) => BOX_HEIGHT / 2.0,
+            Self::Sequence(nodes) => max(nodes.iter().map(Node::down)),
+            Self::Choice(nodes) => match (choice_offsets(nodes).last(), nodes.last()) {
+                (Some(offset), Some(last)) => offset + last.down(),
+                _ => 0.0,
+            },
+            Self::OneOrMore { body, repeat } => loop_offset(body, repeat) + repeat.down(),
+            Self::Skip => 0.0,
+        }
+    }
+
+    /// Draws the node with its entry at `(x, y)`, the exit is at `(x + width, y)`.
+    fn render(&self, out: &mut String, x: f64, y: f64, link: &dyn Fn(&str) -> String) {
+        match self {
+            Self::Terminal(text) | Self::NonTerminal(text) => {
+                let width = Self::box_width(text);
+                let terminal = matches!(self, Self::Terminal(_));
+                line(out, x, y, ARC);
+
+                if !terminal {
+                    write!(out, "<a href=\"{}\">", escape(&link(text))).unwrap();
+                }
+                writeln!(
+                    out,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\"{}/>",
+                    x + ARC,
+                    y - BOX_HEIGHT / 2.0,
+                    width,
+                    BOX_HEIGHT,
+                    if terminal { ARC } else { 0.0 },
+                    if terminal {
+                        ""
+                    } else {
+                        " class=\"nonterminal\""
+                    }
+                )
+                .unwrap();
+                write!(
+                    out,
+                    "<text x=\"{}\" y=\"{}\">{}</text>",
+                    x + ARC + width / 2.0,
+                    y + 4.0,
+                    escape(text)
+                )
+                .unwrap();
+                if !terminal {
+                    out.push_str("</a>");
+                }
+                out.push('\n');
+
+                line(out, x + ARC + width, y, ARC);
+            }
+            Self::Sequence(nodes) => {
+                let mut x = x;
+                for node in nodes {
+                    node.render(out, x, y, link);
+                    x += node.width();
+                }
+            }
+            Self::Choice(nodes) => {
+                let inner = self.width() - 4.0 * ARC;
+
+                for (node, offset) in nodes.iter().zip(choice_offsets(nodes)) {
+                    if offset == 0.0 {
+                        line(out, x, y, 2.0 * ARC);
+                        line(out, x + 2.0 * ARC + inner, y, 2.0 * ARC);
+                    } else {
+                        writeln!(
+                            out,
+                            "<path d=\"M{} {} a{a} {a} 0 0 1 {a} {a} v{} a{a} {a} 0 0 0 {a} {a}\"/>",
+                            x,
+                            y,
+                            offset - 2.0 * ARC,
+                            a = ARC
+                        )
+                        .unwrap();
+                        writeln!(
+                            out,
+                            "<path d=\"M{} {} a{a} {a} 0 0 0 {a} -{a} v-{} a{a} {a} 0 0 1 {a} -{a}\"/>",
+                            x + 2.0 * ARC + inner,
+                            y + offset,
+                            offset - 2.0 * ARC,
+                            a = ARC
+                        )
+                        .unwrap();
+                    }
+
+                    node.render(out, x + 2.0 * ARC, y + offset, link);
+                    line(
+                        out,
+                        x + 2.0 * ARC + node.width(),
+                        y + offset,
+                        inner - node.width(),
+                    );
+                }
+            }
+            Self::OneOrMore { body, repeat } => {
+                let inner = self.width() - 4.0 * ARC;
+                let offset = loop_offset(body, repeat);
+
+                line(out, x, y, 2.0 * ARC);
+                body.render(out, x + 2.0 * ARC, y, link);
+                line(out, x + 2.0 * ARC + body.width(), y, inner - body.width());
+                line(out, x + 2.0 * ARC + inner, y, 2.0 * ARC);
+
+                // The way back runs below the body, from right to left through `repeat`.
+                writeln!(
+                    out,
+                    "<path d=\"M{} {} a{a} {a} 0 0 1 {a} {a} v{} a{a} {a} 0 0 1 -{a} {a}\"/>",
+                    x + 2.0 * ARC + inner,
+                    y,
+                    offset - 2.0 * ARC,
+                    a = ARC
+                )
+                .unwrap();
+                writeln!(
+                    out,
+                    "<path d=\"M{} {} a{a} {a} 0 0 1 -{a} -{a} v-{} a{a} {a} 0 0 1 {a} -{a}\"/>",
+                    x + 2.0 * ARC,
+                    y + offset,
+                    offset - 2.0 * ARC,
+                    a = ARC
+                )
+                .unwrap();
+                repeat.render(out, x + 2.0 * ARC, y + offset, link);
+                line(
+                    out,
+                    x + 2.0 * ARC + repeat.width(),
+                    y + offset,
+                    inner - repeat.width(),
+                );
+            }
+            Self::Skip => {}
+        }
+    }
+
+    /// Renders the node as a standalone SVG document, linking non-terminals with `link`.
+    pub fn to_svg(&self, link: &dyn Fn(&str) -> String) -> String {
+        let width = self.width() + 2.0 * MARGIN;
+        let height = self.up() + self.down() + 2.0 * MARGIN;
+        let (x, y) = (MARGIN, MARGIN + self.up());
+
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n<style>\n{}\n</style>\n",
+            STYLE,
+            w = width,
+            h = height
+        );
+
+        // Start and end of the diagram are marked with a short vertical bar.
+        writeln!(out, "<path d=\"M{} {} v{}\"/>", x - ARC, y - ARC, 2.0 * ARC).unwrap();
+        line(&mut out, x - ARC, y, ARC);
+        self.render(&mut out, x, y, link);
+        line(&mut out, x + self.width(), y, ARC);
+        writeln!(
+            out,
+            "<path d=\"M{} {} v{}\"/>",
+            x + self.width() + ARC,
+            y - ARC,
+            2.0 * ARC
+        )
+        .unwrap();
+
+        out.push_str("</svg>\n");
+        out
+    }
+}
+
+fn max(values: impl Iterator<Item = f64>) -> f64 {
+    values.fold(0.0, f64::max)
+}
+
+/// The vertical offsets of the alternatives of a choice relative to the main line.
+fn choice_offsets(nodes: &[Node]) -> Vec<f64> {
+    let mut offsets: Vec<f64> = Vec::new();
+
+    for (i, node) in nodes.iter().enumerate() {
+        let offset = match i {
+            0 => 0.0,
+            _ => {
+                let previous = offsets[i - 1];
+                let spaced = previous + nodes[i - 1].down() + VERTICAL_GAP + node.up();
+                spaced.max(previous + 2.0 * ARC)
+            }
+        };
+        offsets.push(offset);
+    }
+
+    offsets
+}
+
+fn loop_offset(body: &Node, repeat: &Node) -> f64 {
+    (body.down() + VERTICAL_GAP + repeat.up()).max(2.0 * ARC)
+}
+
+fn line(out: &mut String, x: f64, y: f64, width: f64) {
+    if width > 0.0 {
+        writeln!(out, "<path d=\"M{} {} h{}\"/>", x, y, width).unwrap();
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn sequence<T, NT>(symbols: &[Symbol<T, NT>]) -> Node
+where
+    T: Debug,
+    NT: Debug,
+{
+    let mut nodes = symbols
+        .iter()
+        .map(|sym| match sym {
+            Symbol::Terminal(term) => Node::Terminal(format!("{:?}", term)),
+            Symbol::NonTerminal(nt) => Node::NonTerminal(format!("{:?}", nt)),
+        })
+        .collect::<Vec<_>>();
+
+    match nodes.len() {
+        0 => Node::Skip,
+        1 => nodes.pop().unwrap(),
+        _ => Node::Sequence(nodes),
+    }
+}
+
+fn choice(mut nodes: Vec<Node>) -> Node {
+    // An empty alternative goes first, so the diagram can skip straight over the others.
+    if let Some(skip) = nodes.iter().position(|n| n == &Node::Skip) {
+        nodes.remove(skip);
+        nodes.insert(0, Node::Skip);
+    }
+
+    match nodes.len() {
+        1 => nodes.pop().unwrap(),
+        _ => Node::Choice(nodes),
+    }
+}
+
+/// Builds the diagram of `nt`, folding all of its rules into a single node.
+pub fn diagram<T, NT>(grammar: &Grammar<T, NT>, nt: NT) -> Node
+where
+    T: Terminal,
+    NT: Eq + Hash + Copy + Debug,
+{
+    let recursive = Symbol::NonTerminal(nt);
+    let rules = grammar.rules_by_lhs(nt);
+    let (mut left, mut right, mut bases) = (Vec::new(), Vec::new(), Vec::new());
+
+    for rule in &rules {
+        let symbols = rule.symbols().as_slice();
+        match symbols {
+            [first, rest @ ..]
+                if first == &recursive && !rest.is_empty() && !rest.contains(&recursive) =>
+            {
+                left.push(rest)
+            }
+            [rest @ .., last]
+                if last == &recursive && !rest.is_empty() && !rest.contains(&recursive) =>
+            {
+                right.push(rest)
+            }
+            _ => bases.push(symbols),
+        }
+    }
+
+    let folded = if bases.is_empty() || (!left.is_empty() && !right.is_empty()) {
+        None
+    } else if !left.is_empty() {
+        // `A: A sep item | item` is a list of items separated by `sep`.
+        match (left.as_slice(), bases.as_slice()) {
+            ([tail], [base]) if !base.is_empty() && tail.ends_with(base) => Some(Node::OneOrMore {
+                body: Box::new(sequence(base)),
+                repeat: Box::new(sequence(&tail[..tail.len() - base.len()])),
+            }),
+            _ => Some(Node::Sequence(vec![
+                choice(bases.iter().map(|b| sequence(b)).collect()),
+                Node::zero_or_more(
+                    choice(left.iter().map(|t| sequence(t)).collect()),
+                    Node::Skip,
+                ),
+            ])),
+        }
+    } else if !right.is_empty() {
+        // `A: item sep A | item` is the same list, built from the other end.
+        match (right.as_slice(), bases.as_slice()) {
+            ([head], [base]) if !base.is_empty() && head.starts_with(base) => {
+                Some(Node::OneOrMore {
+                    body: Box::new(sequence(base)),
+                    repeat: Box::new(sequence(&head[base.len()..])),
+                })
+            }
+            _ => Some(Node::Sequence(vec![
+                Node::zero_or_more(
+                    choice(right.iter().map(|h| sequence(h)).collect()),
+                    Node::Skip,
+                ),
+                choice(bases.iter().map(|b| sequence(b)).collect()),
+            ])),
+        }
+    } else {
+        None
+    };
+
+    folded.unwrap_or_else(|| choice(rules.iter().map(|r| sequence(r.symbols())).collect()))
+}
+
+/// The diagrams of every non-terminal of a grammar, in order of their first rule.
+pub struct Diagrams {
+    pub diagrams: Vec<(String, Node)>,
+}
+
+impl Diagrams {
+    pub fn new<T, NT>(grammar: &Grammar<T, NT>) -> Self
+    where
+        T: Terminal,
+        NT: Eq + Hash + Copy + Debug,
+    {
+        let mut nonterminals = Vec::new();
+        for rule in grammar.rules() {
+            if !nonterminals.contains(&rule.lhs()) {
+                nonterminals.push(rule.lhs());
+            }
+        }
+
+        Self {
+            diagrams: nonterminals
+                .into_iter()
+                .map(|nt| (format!("{:?}", nt), diagram(grammar, nt)))
+                .collect(),
+        }
+    }
+
+    /// An HTML page containing every diagram, in which non-terminals link to their own diagram.
+    pub fn index(&self) -> String {
+        let mut out = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Syntax diagrams</title>\n</head>\n<body>\n<ul>\n",
+        );
+
+        for (name, _) in &self.diagrams {
+            writeln!(out, "<li><a href=\"#{0}\">{0}</a></li>", escape(name)).unwrap();
+        }
+        out.push_str("</ul>\n");
+
+        for (name, node) in &self.diagrams {
+            writeln!(out, "<h2 id=\"{0}\">{0}</h2>", escape(name)).unwrap();
+            out.push_str(&node.to_svg(&|nt| format!("#{}", nt)));
+        }
+
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+
+    /// Writes an SVG file per non-terminal together with `index.html` into `dir`.
+    pub fn write(&self, dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+
+        for (name, node) in &self.diagrams {
+            let svg = node.to_svg(&|nt| format!("{}.svg", nt));
+            fs::write(dir.join(format!("{}.svg", name)), svg)?;
+        }
+
+        fs::write(dir.join("index.html"), self.index())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::syntax::{self, Name};
+
+    fn terminal(name: &str) -> Box<Node> {
+        Box::new(Node::Terminal(name.into()))
+    }
+
+    #[test]
+    fn folds_lists_into_loops() {
+        let file = syntax::parse(
+            "Start: Args; Args: Args Comma Expr | Expr; Items: Item Items | Item; Expr: Id | Num | ;",
+        )
+        .unwrap();
+        let grammar = file.grammar();
+
+        assert_eq!(
+            diagram(grammar, Name::new("Args")),
+            Node::OneOrMore {
+                body: Box::new(Node::NonTerminal("Expr".into())),
+                repeat: terminal("Comma"),
+            }
+        );
+        assert_eq!(
+            diagram(grammar, Name::new("Items")),
+            Node::OneOrMore {
+                body: terminal("Item"),
+                repeat: Box::new(Node::Skip),
+            }
+        );
+        assert_eq!(
+            diagram(grammar, Name::new("Expr")),
+            Node::Choice(vec![Node::Skip, *terminal("Id"), *terminal("Num")])
+        );
+
+        let index = Diagrams::new(grammar).index();
+        assert!(index.contains("<a href=\"#Expr\"><rect"));
+        assert_eq!(index.matches("<svg").count(), 4);
+    }
+}
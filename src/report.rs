@@ -0,0 +1,434 @@
+//! Human readable reports of a grammar and its LR(1) automaton, similar to the output of
+//! `bison --report=all`.
+
+use crate::analysis::{Action, Analyser, Automaton, Conflict, StateIndex, Table};
+use crate::common::{Grammar, Item, Rule, Symbol, Terminal};
+use std::collections::HashSet;
+use std::fmt::{Debug, Write};
+use std::hash::Hash;
+
+/// Renders a report of `grammar`, listing its rules, symbols and every state of its automaton.
+pub fn report<T, NT>(grammar: &Grammar<T, NT>) -> String
+where
+    T: 'static + Terminal,
+    NT: 'static + Eq + Hash + Copy + Debug,
+{
+    let automaton = Analyser::new(grammar).automaton();
+    let table = Analyser::table(grammar);
+    let mut out = String::new();
+
+    write_conflict_summary(&mut out, &table);
+    write_grammar(&mut out, grammar, &table);
+    write_symbols(&mut out, grammar);
+
+    for state in 0..automaton.states.len() {
+        write_state(&mut out, &automaton, &table, state);
+    }
+
+    out
+}
+
+fn write_conflict_summary<T, NT>(out: &mut String, table: &Table<T, NT>)
+where
+    T: Eq + Hash + Copy + Debug,
+    NT: Eq + Hash + Copy + Debug,
+{
+    let mut per_state: Vec<(StateIndex, usize, usize)> = Vec::new();
+
+    for conflict in table.conflicts() {
+        let shift_reduce = is_shift_reduce(conflict);
+        match per_state.iter_mut().find(|(s, _, _)| *s == conflict.state) {
+            Some(entry) if shift_reduce => entry.1 += 1,
+            Some(entry) => entry.2 += 1,
+            None if shift_reduce => per_state.push((conflict.state, 1, 0)),
+            None => per_state.push((conflict.state, 0, 1)),
+        }
+    }
+
+    per_state.sort_unstable();
+    for (state, shift_reduce, reduce_reduce) in &per_state {
+        let mut kinds = Vec::new();
+        if *shift_reduce > 0 {
+            kinds.push(format!("{} shift/reduce", shift_reduce));
+        }
+        if *reduce_reduce > 0 {
+            kinds.push(format!("{} reduce/reduce", reduce_reduce));
+        }
+        writeln!(out, "State {} conflicts: {}", state, kinds.join(", ")).unwrap();
+    }
+
+    if !per_state.is_empty() {
+        writeln!(out, "\n").unwrap();
+    }
+}
+
+fn write_grammar<T, NT>(out: &mut String, grammar: &Grammar<T, NT>, table: &Table<T, NT>)
+where
+    T: Terminal,
+    NT: Eq + Hash + Copy + Debug,
+{
+    writeln!(out, "Grammar\n").unwrap();
+
+    let mut previous = None;
+    for (i, rule) in grammar.rules().iter().enumerate() {
+        if previous.is_some() && previous != Some(rule.lhs()) {
+            writeln!(out).unwrap();
+        }
+        writeln!(out, "  {:3} {}", i, rule).unwrap();
+        previous = Some(rule.lhs());
+    }
+
+    // Rules that never appear in a reduction can never be used by the parser.
+    let reduced = table
+        .actions()
+        .values()
+        .flat_map(|row| row.values())
+        .filter_map(|action| match action {
+            Action::Reduce(rule) => Some(rule),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let useless = grammar
+        .rules()
+        .iter()
+        .enumerate()
+        .filter(|(i, rule)| *i != grammar.start_rule_index() && !reduced.contains(rule))
+        .collect::<Vec<_>>();
+
+    let reachable = grammar.reachable();
+    let productive = grammar.productive();
+    let mut unused = nonterminals(grammar)
+        .into_iter()
+        .filter_map(|nt| {
+            if !reachable.contains(&nt) {
+                Some(format!("{:?} (unreachable)", nt))
+            } else if !productive.contains(&nt) {
+                Some(format!("{:?} (derives no sentence)", nt))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    writeln!(out, "\n\nUnused symbols and rules\n").unwrap();
+    if useless.is_empty() && unused.is_empty() {
+        unused.push("none".into());
+    }
+    for (i, rule) in useless {
+        unused.push(format!("rule {} ({}) is never reduced", i, rule));
+    }
+    for line in unused {
+        writeln!(out, "    {}", line).unwrap();
+    }
+}
+
+fn write_symbols<T, NT>(out: &mut String, grammar: &Grammar<T, NT>)
+where
+    T: Terminal,
+    NT: Eq + Hash + Copy + Debug,
+{
+    let rule_numbers = |matches: &dyn Fn(&Rule<T, NT>) -> bool| {
+        grammar
+            .rules()
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| matches(rule))
+            .map(|(i, _)| i.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    writeln!(out, "\n\nTerminals, with rules where they appear\n").unwrap();
+    for term in sorted(grammar.unique_terminals()) {
+        let sym = Symbol::Terminal(term);
+        let used = rule_numbers(&|rule| rule.symbols().contains(&sym));
+        if used.is_empty() {
+            writeln!(out, "    {:?}", term).unwrap();
+        } else {
+            writeln!(out, "    {:?} ({})", term, used).unwrap();
+        }
+    }
+
+    let nullable = grammar.nullable();
+    let first_set = grammar.first_set();
+    let follow_set = grammar.follow_set();
+
+    writeln!(out, "\n\nNonterminals, with rules where they appear\n").unwrap();
+    for nt in nonterminals(grammar) {
+        let sym = Symbol::NonTerminal(nt);
+        writeln!(out, "    {:?}", nt).unwrap();
+        writeln!(
+            out,
+            "        on left: {}",
+            rule_numbers(&|rule| rule.lhs() == nt)
+        )
+        .unwrap();
+        let right = rule_numbers(&|rule| rule.symbols().contains(&sym));
+        if !right.is_empty() {
+            writeln!(out, "        on right: {}", right).unwrap();
+        }
+        writeln!(
+            out,
+            "        nullable: {}",
+            if nullable.contains(&nt) { "yes" } else { "no" }
+        )
+        .unwrap();
+        writeln!(out, "        first: {}", terminals(&first_set[&sym])).unwrap();
+        writeln!(out, "        follow: {}", terminals(&follow_set[&nt])).unwrap();
+    }
+}
+
+fn write_state<T, NT>(
+    out: &mut String,
+    automaton: &Automaton<T, NT>,
+    table: &Table<T, NT>,
+    state: StateIndex,
+) where
+    T: Terminal,
+    NT: Eq + Hash + Copy + Debug,
+{
+    let grammar = automaton.grammar();
+    let rule_number = |rule: &Rule<T, NT>| grammar.rules().iter().position(|r| r == rule).unwrap();
+
+    writeln!(out, "\n\nState {}\n", state).unwrap();
+
+    // Kernel items come first, followed by the items added by the closure.
+    let kernel = automaton.kernel(state);
+    let closure = automaton
+        .items(state)
+        .into_iter()
+        .filter(|item| !kernel.contains(item))
+        .collect::<Vec<_>>();
+
+    for (core, look_aheads) in group_items(&kernel) {
+        writeln!(out, "    {}  [{}]", core, look_aheads).unwrap();
+    }
+    if !closure.is_empty() {
+        writeln!(out).unwrap();
+        for (core, look_aheads) in group_items(&closure) {
+            writeln!(out, "    {}  [{}]", core, look_aheads).unwrap();
+        }
+    }
+
+    let edges = automaton.edges(state);
+    let width = edges
+        .iter()
+        .map(|(sym, _)| sym.to_string().len())
+        .chain(Some("$default".len()))
+        .max()
+        .unwrap();
+
+    let shifts = edges
+        .iter()
+        .filter(|(sym, _)| sym.is_terminal())
+        .collect::<Vec<_>>();
+    if !shifts.is_empty() {
+        writeln!(out).unwrap();
+        for (sym, to_state) in &shifts {
+            writeln!(
+                out,
+                "    {:w$}  shift, and go to state {}",
+                sym.to_string(),
+                to_state,
+                w = width
+            )
+            .unwrap();
+        }
+    }
+
+    let gotos = edges
+        .iter()
+        .filter(|(sym, _)| !sym.is_terminal())
+        .collect::<Vec<_>>();
+    if !gotos.is_empty() {
+        writeln!(out).unwrap();
+        for (sym, to_state) in &gotos {
+            writeln!(
+                out,
+                "    {:w$}  go to state {}",
+                sym.to_string(),
+                to_state,
+                w = width
+            )
+            .unwrap();
+        }
+    }
+
+    // Group the reductions by rule, in grammar order.
+    let mut reductions: Vec<(usize, Vec<T>)> = Vec::new();
+    for item in automaton
+        .items(state)
+        .iter()
+        .filter(|item| !item.is_active())
+    {
+        let number = rule_number(item.rule());
+        match reductions.iter_mut().find(|(n, _)| *n == number) {
+            Some((_, look_aheads)) => look_aheads.push(item.look_ahead()),
+            None => reductions.push((number, vec![item.look_ahead()])),
+        }
+    }
+    reductions.sort_by_key(|(number, _)| *number);
+
+    if !reductions.is_empty() {
+        writeln!(out).unwrap();
+    }
+    for (number, look_aheads) in &reductions {
+        let rule = &grammar.rules()[*number];
+        let action = if *number == grammar.start_rule_index() {
+            "accept".to_string()
+        } else {
+            format!("reduce using rule {} ({:?})", number, rule.lhs())
+        };
+
+        // A state without shifts and with a single reduction can reduce without looking ahead.
+        if shifts.is_empty() && reductions.len() == 1 && *number != grammar.start_rule_index() {
+            writeln!(out, "    {:w$}  {}", "$default", action, w = width).unwrap();
+        } else {
+            for look_ahead in sorted(look_aheads.iter().copied()) {
+                writeln!(
+                    out,
+                    "    {:w$}  {}",
+                    format!("{:?}", look_ahead),
+                    action,
+                    w = width
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    let mut conflicts = table
+        .conflicts()
+        .iter()
+        .filter(|c| c.state == state)
+        .collect::<Vec<_>>();
+    conflicts.sort_by_cached_key(|c| c.symbol.to_string());
+    if !conflicts.is_empty() {
+        writeln!(out).unwrap();
+    }
+    for conflict in conflicts {
+        let term = conflict.symbol.terminal().unwrap();
+        let kept = &table.actions()[&state][&term];
+        writeln!(
+            out,
+            "    {} conflict on {}: {} / {}, unresolved, the table keeps {}",
+            if is_shift_reduce(conflict) {
+                "shift/reduce"
+            } else {
+                "reduce/reduce"
+            },
+            conflict.symbol,
+            describe(conflict.first_action.clone(), &rule_number),
+            describe(conflict.second_action.clone(), &rule_number),
+            describe(kept.clone(), &rule_number),
+        )
+        .unwrap();
+    }
+}
+
+fn describe<T, NT>(action: Action<T, NT>, rule_number: &dyn Fn(&Rule<T, NT>) -> usize) -> String {
+    match action {
+        Action::Shift(state) => format!("shift to state {}", state),
+        Action::Reduce(rule) => format!("reduce using rule {}", rule_number(&rule)),
+        Action::Accept => "accept".into(),
+        Action::Error => "error".into(),
+    }
+}
+
+fn is_shift_reduce<T, NT>(conflict: &Conflict<T, NT>) -> bool {
+    matches!(conflict.first_action, Action::Shift(_))
+        || matches!(conflict.second_action, Action::Shift(_))
+}
+
+/// Merges items that only differ in their look ahead, rendering them as the item without look ahead
+/// together with the list of look aheads.
+pub(crate) fn group_items<T, NT>(items: &[Item<T, NT>]) -> Vec<(String, String)>
+where
+    T: Copy + Debug,
+    NT: Copy + Debug,
+{
+    let mut groups: Vec<(&Rule<T, NT>, usize, Vec<T>)> = Vec::new();
+
+    for item in items {
+        match groups
+            .iter_mut()
+            .find(|(rule, index, _)| std::ptr::eq(*rule, item.rule()) && *index == item.index())
+        {
+            Some((_, _, look_aheads)) => look_aheads.push(item.look_ahead()),
+            None => groups.push((item.rule(), item.index(), vec![item.look_ahead()])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(rule, index, look_aheads)| {
+            let mut core = format!("{:?} ->", rule.lhs());
+            for (i, sym) in rule.symbols().iter().enumerate() {
+                if i == index {
+                    core.push_str(" .");
+                }
+                write!(core, " {}", sym).unwrap();
+            }
+            if index >= rule.symbols().len() {
+                core.push_str(" .");
+            }
+            (
+                core,
+                look_aheads
+                    .iter()
+                    .map(|t| format!("{:?}", t))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )
+        })
+        .collect()
+}
+
+/// The non-terminals of a grammar, in order of their first rule.
+fn nonterminals<T, NT>(grammar: &Grammar<T, NT>) -> Vec<NT>
+where
+    T: Terminal,
+    NT: Eq + Hash + Copy,
+{
+    let mut nonterminals = Vec::new();
+    for rule in grammar.rules() {
+        if !nonterminals.contains(&rule.lhs()) {
+            nonterminals.push(rule.lhs());
+        }
+    }
+    nonterminals
+}
+
+fn sorted<T: Debug>(items: impl IntoIterator<Item = T>) -> Vec<T> {
+    let mut items = items.into_iter().collect::<Vec<_>>();
+    items.sort_by_cached_key(|t| format!("{:?}", t));
+    items
+}
+
+fn terminals<T: Debug + Copy>(set: &HashSet<T>) -> String {
+    sorted(set.iter().copied())
+        .iter()
+        .map(|t| format!("{:?}", t))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::syntax;
+
+    #[test]
+    fn reports_states_and_conflicts() {
+        let file = syntax::parse("Start: E; E: E Plus E | Num | Opt E; Opt: | Minus;").unwrap();
+        let report = report(file.grammar());
+
+        assert!(report.starts_with("State 0 conflicts: 2 shift/reduce\n"));
+        assert!(report.contains("    1 E -> E Plus E\n"));
+        assert!(report.contains("        nullable: yes\n        first: Minus\n"));
+        assert!(report.contains("E -> E . Plus E  [Eof, Plus]"));
+        assert!(report.contains("$default  reduce using rule 2 (E)"));
+        assert!(report.contains("    Eof\n    Minus (5)\n"));
+        assert!(report.contains("shift/reduce conflict on Plus"));
+    }
+}
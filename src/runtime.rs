@@ -1,5 +1,17 @@
-use std::iter::Peekable;
+pub mod cst;
+pub mod events;
+pub mod incremental;
+pub mod layout;
+pub mod lexer;
+pub mod repair;
+pub mod stream;
+pub mod tolerant;
 
+use repair::{Repair, RepairOptions};
+use std::collections::VecDeque;
+use std::fmt;
+
+#[derive(Debug)]
 pub struct Token<T> {
     pub span: (usize, usize),
     pub kind: T,
@@ -19,57 +31,364 @@ where
 
 impl<T> Copy for Token<T> where T: Copy {}
 
-pub struct ParseState<I, T> where I: Iterator {
-    tokens: Peekable<I>,
-    token_stack: Vec<Token<T>>,
-    rule_stack: Vec<usize>,
-    state_stack: Vec<usize>,
+impl<T> PartialEq for Token<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.span == other.span && self.kind == other.kind
+    }
+}
+
+/// An entry of the parse table, telling what the parser does with a terminal in a state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Step {
+    Shift(usize),
+    /// Reduces by the rule with the given index and length.
+    Reduce(usize, usize),
+    /// Reduces by the start rule with the given index and length, which completes the parse.
+    Accept(usize, usize),
+}
+
+/// What became of a token fed to the parser with [`Parse::feed`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Feed<O, T: 'static> {
+    /// The token was shifted, and the parser waits for the next one.
+    NeedMore,
+    /// The input is complete, and the parser starts over for the next one.
+    Accepted(O),
+    /// The token was rejected, leaving the parser as it was before it.
+    Error(ParseError<T>),
+}
+
+/// A token for which the parser has no action, with the terminals it would have accepted instead.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError<T: 'static> {
+    /// The unexpected token, or `None` at the end of input. The input also ends when the source stops
+    /// early, for example because it could not lex the rest.
+    pub found: Option<Token<T>>,
+    /// The state of the automaton in which the token was found.
+    pub state: usize,
+    /// The terminals that have an action in the state, ordered by name.
+    pub expected: &'static [T],
+    /// The hand-written message for errors in the state, see [`crate::messages`].
+    pub message: Option<&'static str>,
+    /// The edits after which the parse continued, when it repaired the error.
+    pub repairs: Vec<Repair<T>>,
+}
+
+/// Displays the hand-written message when there is one, and otherwise the expected and found terminals.
+impl<T: fmt::Display> fmt::Display for ParseError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(message) = self.message {
+            f.write_str(message)?;
+        } else {
+            let expected = self
+                .expected
+                .iter()
+                .map(|kind| format!("`{}`", kind))
+                .collect::<Vec<_>>()
+                .join(", ");
+            match self.expected.len() {
+                0 => write!(f, "unexpected input")?,
+                1 => write!(f, "expected {}", expected)?,
+                _ => write!(f, "expected one of {}", expected)?,
+            }
+            match &self.found {
+                Some(token) => write!(f, " but found `{}`", token.kind)?,
+                None => write!(f, " but found end of input")?,
+            }
+        }
+        if !self.repairs.is_empty() {
+            let repairs = self
+                .repairs
+                .iter()
+                .map(|repair| repair.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            write!(f, ", repaired with: {}", repairs)?;
+        }
+        Ok(())
+    }
 }
 
-impl<I, T> ParseState<I, T>
+impl<T: fmt::Debug + fmt::Display> std::error::Error for ParseError<T> {}
+
+/// A source of tokens for the parser, which is told the terminals the parser accepts in its current
+/// state. Trivia is always accepted. Every iterator over tokens is a source that ignores them.
+pub trait TokenSource<T> {
+    fn next_token(&mut self, expected: &[T]) -> Option<Token<T>>;
+}
+
+impl<T, I> TokenSource<T> for I
 where
     I: Iterator<Item = Token<T>>,
+{
+    fn next_token(&mut self, _expected: &[T]) -> Option<Token<T>> {
+        self.next()
+    }
+}
+
+/// The state of a running parser. Next to the states of the automaton it keeps a stack of values,
+/// holding a value for every symbol that has been shifted or reduced but not yet consumed by a rule.
+///
+/// Tokens for which the trivia filter holds, such as whitespace and comments, are skipped by the parser.
+/// They are kept until the next token is shifted, so they can be added to a syntax tree.
+#[derive(Clone)]
+pub struct ParseState<I, T, V> {
+    tokens: I,
+    /// The next token that is not trivia, once it has been requested.
+    lookahead: Option<Token<T>>,
+    /// The tokens after the lookahead that have been requested already, with the trivia before them.
+    pending: VecDeque<(Vec<Token<T>>, Token<T>)>,
+    value_stack: Vec<V>,
+    state_stack: Vec<usize>,
+    is_trivia: Option<fn(&T) -> bool>,
+    trivia: Vec<Token<T>>,
+    /// The end of the last token fed to the parser, where [`Parse::finish`] ends the input.
+    end: usize,
+    /// The tokens requested from the source since the first checkpoint, to give them again after a
+    /// restore, and the number of them given to the parser.
+    history: Option<Vec<Token<T>>>,
+    position: usize,
+}
+
+impl<I, T, V> ParseState<I, T, V>
+where
+    I: TokenSource<T>,
 {
     pub fn new(tokens: I) -> Self {
         Self {
-            tokens: tokens.peekable(),
-            token_stack: Vec::new(),
-            rule_stack: Vec::new(),
+            tokens,
+            lookahead: None,
+            pending: VecDeque::new(),
+            value_stack: Vec::new(),
             state_stack: vec![0],
+            is_trivia: None,
+            trivia: Vec::new(),
+            end: 0,
+            history: None,
+            position: 0,
+        }
+    }
+
+    /// Skips the tokens for which `is_trivia` holds.
+    pub fn with_trivia(mut self, is_trivia: fn(&T) -> bool) -> Self {
+        self.is_trivia = Some(is_trivia);
+        self
+    }
+
+    /// Removes the trivia skipped since the last shifted token.
+    pub fn take_trivia(&mut self) -> Vec<Token<T>> {
+        std::mem::take(&mut self.trivia)
+    }
+
+    /// Requests the next token from the source, or gives again one requested after a checkpoint that
+    /// was restored.
+    fn pull(&mut self, expected: &[T]) -> Option<Token<T>>
+    where
+        T: Copy,
+    {
+        match &mut self.history {
+            Some(history) if self.position < history.len() => {
+                self.position += 1;
+                Some(history[self.position - 1])
+            }
+            Some(history) => {
+                let token = self.tokens.next_token(expected)?;
+                history.push(token);
+                self.position += 1;
+                Some(token)
+            }
+            None => self.tokens.next_token(expected),
+        }
+    }
+
+    /// Requests tokens from the source until one is found that is not trivia.
+    fn fill_lookahead(&mut self, expected: &[T])
+    where
+        T: Copy,
+    {
+        if self.lookahead.is_none() {
+            if let Some((trivia, token)) = self.pending.pop_front() {
+                self.trivia.extend(trivia);
+                self.lookahead = Some(token);
+            }
+        }
+        while self.lookahead.is_none() {
+            match self.pull(expected) {
+                Some(token) if self.is_trivia.is_some_and(|is_trivia| is_trivia(&token.kind)) => {
+                    self.trivia.push(token)
+                }
+                Some(token) => self.lookahead = Some(token),
+                None => break,
+            }
+        }
+    }
+
+    /// Makes `token` the next token, before the lookahead.
+    fn insert(&mut self, token: Token<T>) {
+        if let Some(lookahead) = self.lookahead.replace(token) {
+            self.pending.push_front((Vec::new(), lookahead));
+        }
+    }
+
+    /// Returns the lookahead and up to `count - 1` tokens after it that are not trivia, requesting them
+    /// from the source when needed. The tokens after the lookahead are requested without knowing the
+    /// terminals that will be expected.
+    fn peek(&mut self, count: usize, expected: &[T]) -> Vec<Token<T>>
+    where
+        T: Copy,
+    {
+        self.fill_lookahead(expected);
+        let mut trivia = Vec::new();
+        while self.lookahead.is_some() && self.pending.len() + 1 < count {
+            match self.pull(&[]) {
+                Some(token) if self.is_trivia.is_some_and(|is_trivia| is_trivia(&token.kind)) => {
+                    trivia.push(token)
+                }
+                Some(token) => self.pending.push_back((std::mem::take(&mut trivia), token)),
+                None => break,
+            }
+        }
+        // Trivia at the end of a source that stopped early is kept with the trivia before the lookahead.
+        self.trivia.extend(trivia);
+
+        self.lookahead
+            .iter()
+            .chain(self.pending.iter().map(|(_, token)| token))
+            .copied()
+            .collect()
+    }
+
+    /// Saves everything but the source, so the parse can be rolled back to this point with
+    /// [`Self::restore`]. Copies the stacks, whose values are cheap to clone in a syntax tree. From the
+    /// first checkpoint on, the tokens requested from the source are kept to be given again.
+    pub fn checkpoint(&mut self) -> Checkpoint<T, V>
+    where
+        T: Clone,
+        V: Clone,
+    {
+        self.history.get_or_insert_with(Vec::new);
+        Checkpoint {
+            lookahead: self.lookahead.clone(),
+            pending: self.pending.clone(),
+            value_stack: self.value_stack.clone(),
+            state_stack: self.state_stack.clone(),
+            trivia: self.trivia.clone(),
+            end: self.end,
+            position: self.position,
         }
     }
+
+    /// Rolls the parse back to the `checkpoint`. The tokens requested from the source since then are
+    /// given to the parser again, as they were lexed then, before new ones are requested.
+    pub fn restore(&mut self, checkpoint: Checkpoint<T, V>) {
+        self.lookahead = checkpoint.lookahead;
+        self.pending = checkpoint.pending;
+        self.value_stack = checkpoint.value_stack;
+        self.state_stack = checkpoint.state_stack;
+        self.trivia = checkpoint.trivia;
+        self.end = checkpoint.end;
+        self.position = checkpoint.position;
+    }
+
+    /// The source of the tokens, for example to inspect why it stopped.
+    pub fn tokens(&self) -> &I {
+        &self.tokens
+    }
+
+    pub fn values(&self) -> &[V] {
+        &self.value_stack
+    }
+
+    pub fn states(&self) -> &[usize] {
+        &self.state_stack
+    }
+}
+
+/// A snapshot of a [`ParseState`] without its source, see [`ParseState::checkpoint`]. State kept by a
+/// parser outside of its parse state, like the recorder of events, is not part of it.
+#[derive(Clone)]
+pub struct Checkpoint<T, V> {
+    lookahead: Option<Token<T>>,
+    pending: VecDeque<(Vec<Token<T>>, Token<T>)>,
+    value_stack: Vec<V>,
+    state_stack: Vec<usize>,
+    trivia: Vec<Token<T>>,
+    end: usize,
+    position: usize,
 }
 
 pub trait Parse<I, T>
 where
-    I: Iterator<Item = Token<T>> + 'static,
-	T: Copy
+    I: TokenSource<T>,
+	T: Copy + PartialEq + 'static
 {
-    fn parse_state(&self) -> &ParseState<I, T>;
-    fn parse_state_mut(&mut self) -> &mut ParseState<I, T>;
+    /// The type of the values on the value stack, able to hold a token or the value of any non-terminal.
+    type Value;
+    /// The type of the value of the start symbol, which is returned by a successful parse.
+    type Output;
+
+    fn parse_state(&self) -> &ParseState<I, T, Self::Value>;
+    fn parse_state_mut(&mut self) -> &mut ParseState<I, T, Self::Value>;
+
+    /// The terminals for which the automaton has an action in `state`, ordered by name.
+    fn expected_terminals(&self, state: usize) -> &'static [T];
+
+    /// The terminal marking the end of input.
+    fn eof(&self) -> T;
+
+    /// The terminal standing for a syntax error in rules that recover from errors, if the grammar has any.
+    fn error_terminal(&self) -> Option<T>;
+
+    /// The hand-written message for errors in `state`, when the parser was generated with messages.
+    fn message(&self, _state: usize) -> Option<&'static str> {
+        None
+    }
+
+    /// The states reached by the gotos of `state`, each with the terminals that can follow its
+    /// non-terminal, when the parser was generated in CST mode. See [`tolerant`].
+    fn follow_gotos(&self, _state: usize) -> &'static [(usize, &'static [T])] {
+        &[]
+    }
+
+    /// The terminals that are acceptable as the next token.
+    fn expected(&self) -> &'static [T] {
+        self.expected_terminals(self.state())
+    }
 
     fn next_token(&mut self) -> Option<Token<T>> {
-        self.parse_state_mut().tokens.next()
+        let expected = self.expected();
+        let state = self.parse_state_mut();
+        state.fill_lookahead(expected);
+        state.lookahead.take()
     }
 
-    fn current_token(&mut self) -> Option<&Token<T>> {
-        self.parse_state_mut().tokens.peek()
+    fn current_token(&mut self) -> Option<Token<T>> {
+        let expected = self.expected();
+        let state = self.parse_state_mut();
+        state.fill_lookahead(expected);
+        state.lookahead
     }
 
-    fn push_token(&mut self, token: Token<T>) {
-        self.parse_state_mut().token_stack.push(token);
+    fn push_value(&mut self, value: Self::Value) {
+        self.parse_state_mut().value_stack.push(value);
     }
 
-    fn push_state(&mut self, state: usize) {
-        self.parse_state_mut().state_stack.push(state);
+    /// Removes the values of the last `count` symbols from the value stack, in the order they were pushed.
+    fn pop_values(&mut self, count: usize) -> Vec<Self::Value> {
+        let stack = &mut self.parse_state_mut().value_stack;
+        stack.split_off(stack.len() - count)
     }
 
-    fn pop_state(&mut self) {
-        self.parse_state_mut().state_stack.pop();
+    fn push_state(&mut self, state: usize) {
+        self.parse_state_mut().state_stack.push(state);
     }
 
-    fn push_rule(&mut self, rule: usize) {
-        self.parse_state_mut().rule_stack.push(rule);
+    fn pop_states(&mut self, count: usize) {
+        let stack = &mut self.parse_state_mut().state_stack;
+        stack.truncate(stack.len() - count);
     }
 
     fn state(&self) -> usize {
@@ -80,40 +399,316 @@ where
             .expect("empty parse state")
     }
 
-    fn action(&mut self, token: Token<T>) -> Option<Result<(), ()>>;
+    /// The entry of the parse table for `kind` in `state`, or `None` when it is a syntax error.
+    fn step(&self, state: usize, kind: T) -> Option<Step>;
+
+    /// The state reached after reducing by `rule` in `state`.
+    fn goto_state(&self, state: usize, rule: usize) -> usize;
 
-    fn parse(&mut self) -> Result<(), ()> {
+    /// Whether the terminals `kinds` can be parsed from the current state, without changing the parser
+    /// or running any actions. A token that was read but not shifted yet, like the lookahead after an
+    /// error, comes after them. When they end in the end of input, they must complete the parse.
+    fn accepts(&self, kinds: &[T]) -> bool {
+        let mut stack = self.parse_state().state_stack.clone();
+        for (i, &kind) in kinds.iter().enumerate() {
+            match simulate(self, &mut stack, kind) {
+                Outcome::Shifted => {}
+                Outcome::Accepted => return i == kinds.len() - 1,
+                Outcome::Rejected => return false,
+            }
+        }
+        true
+    }
+
+    /// Performs the action of the parse table for `token` in the current state. Returns the result
+    /// when the parse is complete or fails.
+    fn action(&mut self, token: Token<T>) -> Option<Result<Self::Output, ParseError<T>>> {
+        match self.step(self.state(), token.kind) {
+            Some(Step::Shift(state)) => self.shift(state, token),
+            Some(Step::Reduce(rule, len)) => self.reduce(rule, len),
+            Some(Step::Accept(rule, len)) => return Some(self.accept(rule, len)),
+            None => return Some(self.error(Some(token))),
+        }
+        None
+    }
+
+    fn parse(&mut self) -> Result<Self::Output, ParseError<T>> {
         loop {
-            if let Some(&token) = self.current_token() {
-                match self.action(token) {
-                    Some(res) => break res,
-                    None => {}
+            if let Some(token) = self.current_token() {
+                if let Some(res) = self.action(token) {
+                    break res;
                 }
             } else {
-                panic!("unhandled EOF")
+                // The source stopped before the end of input, because it could not produce a token.
+                break self.error(None);
+            }
+        }
+    }
+
+    /// Parses like [`Self::parse`], but recovers from syntax errors like yacc when the grammar has rules
+    /// with the error terminal. Returns the output when the parse could be completed, together with
+    /// all errors found.
+    ///
+    /// On an error, states are popped until one is found that accepts the error terminal, and an
+    /// error token is inserted with an empty span before the token that was not accepted. After the
+    /// error token is shifted, tokens are discarded until one is accepted. Errors found before three
+    /// more tokens have been shifted are not reported, since they are likely caused by the first one.
+    fn parse_recovering(&mut self) -> (Option<Self::Output>, Vec<ParseError<T>>) {
+        const QUIET_TOKENS: usize = 3;
+
+        let mut errors = Vec::new();
+        // The number of tokens still to be shifted before errors are reported again.
+        let mut quiet = 0;
+
+        loop {
+            let token = match self.current_token() {
+                Some(token) => token,
+                None => {
+                    errors.extend(self.error(None).err());
+                    return (None, errors);
+                }
+            };
+
+            let error = match self.action(token) {
+                Some(Ok(output)) => return (Some(output), errors),
+                Some(Err(error)) => error,
+                None => {
+                    let shifted = self.parse_state().lookahead.is_none();
+                    if shifted && Some(token.kind) != self.error_terminal() {
+                        quiet -= quiet.min(1);
+                    }
+                    continue;
+                }
+            };
+
+            if quiet == 0 {
+                errors.push(error);
             }
+            if quiet == QUIET_TOKENS {
+                // Nothing has been shifted since the error token, so this token cannot follow it.
+                if token.kind == self.eof() {
+                    return (None, errors);
+                }
+                self.next_token();
+                continue;
+            }
+
+            let error_terminal = match self.error_terminal() {
+                Some(error_terminal) => error_terminal,
+                None => return (None, errors),
+            };
+            while !self.expected().contains(&error_terminal) {
+                if self.parse_state().state_stack.len() == 1 {
+                    return (None, errors);
+                }
+                self.pop_states(1);
+                self.pop_values(1);
+            }
+
+            quiet = QUIET_TOKENS;
+            self.parse_state_mut().insert(Token {
+                span: (token.span.0, token.span.0),
+                kind: error_terminal,
+            });
         }
     }
 
+    /// Parses like [`Self::parse`], but repairs syntax errors by inserting and deleting tokens, so the
+    /// grammar needs no rules with the error terminal. Returns the output when the parse could be
+    /// completed, together with all errors found, each with the repair that was applied.
+    ///
+    /// The cheapest repair is searched as described in [`repair`], looking at the tokens up to a few
+    /// after the error. Inserted tokens have an empty span before the next token, and are passed to
+    /// the actions of rules like any other token. The parse stops at an error that cannot be repaired
+    /// within the maximum cost of the `options`.
+    fn parse_repairing(
+        &mut self,
+        options: &RepairOptions<T>,
+    ) -> (Option<Self::Output>, Vec<ParseError<T>>) {
+        let mut errors = Vec::new();
+
+        loop {
+            let token = match self.current_token() {
+                Some(token) => token,
+                None => {
+                    errors.extend(self.error(None).err());
+                    return (None, errors);
+                }
+            };
+
+            let mut error = match self.action(token) {
+                Some(Ok(output)) => return (Some(output), errors),
+                Some(Err(error)) => error,
+                None => continue,
+            };
+
+            let expected = self.expected();
+            let tokens = self
+                .parse_state_mut()
+                .peek(options.max_cost + repair::SHIFTS + 1, expected);
+            let repairs = match repair::search(self, &tokens, options) {
+                Some(repairs) => repairs,
+                None => {
+                    errors.push(error);
+                    return (None, errors);
+                }
+            };
+
+            for repair in &repairs {
+                match *repair {
+                    Repair::Delete(_) => {
+                        self.next_token();
+                        continue;
+                    }
+                    Repair::Insert(kind) => {
+                        let start = self.current_token().map_or(token.span.0, |t| t.span.0);
+                        self.parse_state_mut().insert(Token {
+                            span: (start, start),
+                            kind,
+                        });
+                    }
+                    Repair::Shift(_) => {}
+                }
+                shift_lookahead(self);
+            }
+
+            error.repairs = repairs;
+            errors.push(error);
+        }
+    }
+
+    /// Parses `token` as the next token of the input, for input that arrives a token at a time, like
+    /// messages from the network or lines of a REPL. The parser keeps its state between tokens instead
+    /// of requesting them from its source, which should be empty, for example `std::iter::empty()`.
+    ///
+    /// Trivia is kept for the next token. A rejected token is discarded, so parsing can continue with
+    /// another token in its place. After the input is accepted, the parser is ready for the next one.
+    fn feed(&mut self, token: Token<T>) -> Feed<Self::Output, T> {
+        let state = self.parse_state_mut();
+        state.end = token.span.1;
+        if state.is_trivia.is_some_and(|is_trivia| is_trivia(&token.kind)) {
+            state.trivia.push(token);
+            return Feed::NeedMore;
+        }
+        state.insert(token);
+
+        while let Some(token) = self.parse_state().lookahead {
+            if let Some(result) = self.action(token) {
+                self.parse_state_mut().lookahead = None;
+                return match result {
+                    Ok(output) => Feed::Accepted(output),
+                    Err(error) => Feed::Error(error),
+                };
+            }
+        }
+        Feed::NeedMore
+    }
+
+    /// Ends the input fed to the parser, by feeding the end of input after the last token.
+    fn finish(&mut self) -> Feed<Self::Output, T> {
+        let end = self.parse_state().end;
+        self.feed(Token {
+            span: (end, end),
+            kind: self.eof(),
+        })
+    }
+
     fn shift(&mut self, shift_state: usize, token: Token<T>) {
-        self.push_token(token);
+        let value = self.token_value(token);
+        self.push_value(value);
         self.push_state(shift_state);
-		self.next_token();
+        self.next_token();
     }
 
-    fn reduce(&mut self, rule_index: usize) {
-        self.push_rule(rule_index);
-        self.pop_state();
+    /// Reduces the last `len` symbols by rule `rule_index`, replacing their values by the value of the rule.
+    fn reduce(&mut self, rule_index: usize, len: usize) {
+        let values = self.pop_values(len);
+        self.pop_states(len);
+        let value = self.reduce_values(rule_index, values);
+        self.push_value(value);
         self.goto(rule_index);
     }
 
-    fn goto(&mut self, rule_index: usize);
+    fn goto(&mut self, rule_index: usize) {
+        let state = self.goto_state(self.state(), rule_index);
+        self.push_state(state);
+    }
+
+    /// Wraps a shifted token into a value.
+    fn token_value(&mut self, token: Token<T>) -> Self::Value;
+
+    /// Runs the semantic action of a rule on the values of its symbols.
+    fn reduce_values(&mut self, rule_index: usize, values: Vec<Self::Value>) -> Self::Value;
+
+    /// Unwraps the value of the start symbol.
+    fn output(&mut self, value: Self::Value) -> Self::Output;
 
-    fn error(&self) -> Result<(), ()> {
-        Err(())
+    /// Fails the parse in the current state on the `found` token, which is `None` at the end of input.
+    fn error(&self, found: Option<Token<T>>) -> Result<Self::Output, ParseError<T>> {
+        Err(ParseError {
+            found: found.filter(|token| token.kind != self.eof()),
+            state: self.state(),
+            expected: self.expected(),
+            message: self.message(self.state()),
+            repairs: Vec::new(),
+        })
     }
 
-    fn accept(&self) -> Result<(), ()> {
-        Ok(())
+    /// Reduces the start rule, whose value is the result of the parse.
+    fn accept(&mut self, rule_index: usize, len: usize) -> Result<Self::Output, ParseError<T>> {
+        let values = self.pop_values(len);
+        self.pop_states(len);
+        let value = self.reduce_values(rule_index, values);
+        Ok(self.output(value))
+    }
+}
+
+/// What became of a terminal passed to the automaton by [`simulate`].
+enum Outcome {
+    Shifted,
+    Accepted,
+    Rejected,
+}
+
+/// Performs the reductions for a terminal of `kind` on `stack` using the parse table of `parser`, and
+/// shifts it, without running any actions.
+fn simulate<I, T, P>(parser: &P, stack: &mut Vec<usize>, kind: T) -> Outcome
+where
+    P: Parse<I, T> + ?Sized,
+    I: TokenSource<T>,
+    T: Copy + PartialEq + 'static,
+{
+    loop {
+        match parser.step(*stack.last().unwrap(), kind) {
+            Some(Step::Shift(state)) => {
+                stack.push(state);
+                return Outcome::Shifted;
+            }
+            Some(Step::Reduce(rule, len)) => {
+                stack.truncate(stack.len() - len);
+                let state = parser.goto_state(*stack.last().unwrap(), rule);
+                stack.push(state);
+            }
+            Some(Step::Accept(..)) => return Outcome::Accepted,
+            None => return Outcome::Rejected,
+        }
+    }
+}
+
+/// Performs the actions for the lookahead until it is shifted. The lookahead must be acceptable in the
+/// current state, as it is after a repair, so the parse cannot end.
+fn shift_lookahead<I, T, P>(parser: &mut P)
+where
+    P: Parse<I, T> + ?Sized,
+    I: TokenSource<T>,
+    T: Copy + PartialEq + 'static,
+{
+    while let Some(token) = parser.current_token() {
+        if parser.action(token).is_some() {
+            unreachable!("the lookahead was not acceptable");
+        }
+        if parser.parse_state().lookahead.is_none() {
+            break;
+        }
     }
 }
@@ -0,0 +1,423 @@
+//! A lossless concrete syntax tree, holding every token of the input including trivia.
+//!
+//! The tree comes in two layers. The green tree is immutable and only knows the kinds, texts and
+//! lengths of its elements, so subtrees can be shared and cloned cheaply. The red tree is built on
+//! demand on top of it and adds parent pointers and absolute offsets.
+//!
+//! Parsers generated in CST mode build the green tree from their value stack: shifting a token
+//! pushes it together with the trivia before it, reducing a rule wraps the elements of its symbols
+//! in a node tagged with the non-terminal. Trivia in front of a node is kept outside of it, so the
+//! range of a node starts at its first token.
+
+use crate::runtime::Token;
+use std::fmt::{self, Debug};
+use std::ops::Range;
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct GreenToken<T> {
+    kind: T,
+    text: Box<str>,
+}
+
+impl<T: Copy> GreenToken<T> {
+    pub fn kind(&self) -> T {
+        self.kind
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct GreenNode<T, N> {
+    kind: N,
+    len: usize,
+    children: Vec<GreenElement<T, N>>,
+}
+
+impl<T: Copy, N: Copy> GreenNode<T, N> {
+    pub fn new(kind: N, children: Vec<GreenElement<T, N>>) -> Self {
+        Self {
+            kind,
+            len: children.iter().map(GreenElement::len).sum(),
+            children,
+        }
+    }
+
+    pub fn kind(&self) -> N {
+        self.kind
+    }
+
+    /// The length of the text of this node in bytes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn children(&self) -> &[GreenElement<T, N>] {
+        &self.children
+    }
+
+    fn write_text(&self, out: &mut String) {
+        for child in &self.children {
+            match child {
+                GreenElement::Node(node) => node.write_text(out),
+                GreenElement::Token(token) => out.push_str(&token.text),
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub enum GreenElement<T, N> {
+    Node(Arc<GreenNode<T, N>>),
+    Token(Arc<GreenToken<T>>),
+}
+
+impl<T, N> Clone for GreenElement<T, N> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Node(node) => Self::Node(Arc::clone(node)),
+            Self::Token(token) => Self::Token(Arc::clone(token)),
+        }
+    }
+}
+
+impl<T: Copy, N: Copy> GreenElement<T, N> {
+    pub fn node(kind: N, children: Vec<GreenElement<T, N>>) -> Self {
+        Self::Node(Arc::new(GreenNode::new(kind, children)))
+    }
+
+    pub fn token(kind: T, text: &str) -> Self {
+        Self::Token(Arc::new(GreenToken {
+            kind,
+            text: text.into(),
+        }))
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Node(node) => node.len,
+            Self::Token(token) => token.text.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// The kinds of the nodes an error-tolerant parse adds to the tree, see [`crate::runtime::tolerant`].
+/// Parsers generated in CST mode implement it for their node kinds.
+pub trait RecoveryKinds: Copy + PartialEq {
+    /// A node holding tokens and nodes that could not be parsed.
+    fn error() -> Self;
+    /// A node holding an empty token that was expected but not found.
+    fn missing() -> Self;
+}
+
+/// The elements for a shifted `token` of `source`, preceded by the trivia that was skipped before it.
+pub fn token_elements<T: Copy, N: Copy>(
+    source: &str,
+    trivia: Vec<Token<T>>,
+    token: Token<T>,
+) -> Vec<GreenElement<T, N>> {
+    trivia
+        .into_iter()
+        .chain(std::iter::once(token))
+        .map(|t| GreenElement::token(t.kind, &source[t.span.0..t.span.1]))
+        .collect()
+}
+
+/// The elements for a reduced rule: the trivia in front of it followed by a node holding the elements of
+/// all of its symbols.
+pub fn node_elements<T: Copy, N: Copy>(
+    kind: N,
+    values: Vec<Vec<GreenElement<T, N>>>,
+    is_trivia: impl Fn(&T) -> bool,
+) -> Vec<GreenElement<T, N>> {
+    let mut children = values.into_iter().flatten().collect::<Vec<_>>();
+    let leading = children
+        .iter()
+        .take_while(|e| matches!(e, GreenElement::Token(t) if is_trivia(&t.kind)))
+        .count();
+    let node = GreenElement::node(kind, children.split_off(leading));
+
+    children.push(node);
+    children
+}
+
+/// Turns the elements of the start rule into the root of the tree. The trivia at the start and the end
+/// of the input is added to the root, which makes the text of the tree equal to the source.
+pub fn root<T: Copy, N: Copy>(
+    source: &str,
+    mut value: Vec<GreenElement<T, N>>,
+    trivia: Vec<Token<T>>,
+) -> SyntaxNode<T, N> {
+    let node = match value.pop() {
+        Some(GreenElement::Node(node)) => node,
+        _ => panic!("the start rule should reduce to a node"),
+    };
+
+    let mut children = value;
+    children.extend(node.children.iter().cloned());
+    for token in trivia {
+        children.push(GreenElement::token(
+            token.kind,
+            &source[token.span.0..token.span.1],
+        ));
+    }
+
+    SyntaxNode::new_root(Arc::new(GreenNode::new(node.kind, children)))
+}
+
+struct NodeData<T, N> {
+    green: Arc<GreenNode<T, N>>,
+    parent: Option<SyntaxNode<T, N>>,
+    offset: usize,
+}
+
+/// A node of the red tree, which is a cheap handle to a green node together with its position.
+pub struct SyntaxNode<T, N>(Rc<NodeData<T, N>>);
+
+impl<T, N> Clone for SyntaxNode<T, N> {
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+/// Handles are equal when they refer to the same green node at the same offset.
+impl<T, N> PartialEq for SyntaxNode<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0.green, &other.0.green) && self.0.offset == other.0.offset
+    }
+}
+
+impl<T: Copy, N: Copy> SyntaxNode<T, N> {
+    pub fn new_root(green: Arc<GreenNode<T, N>>) -> Self {
+        Self(Rc::new(NodeData {
+            green,
+            parent: None,
+            offset: 0,
+        }))
+    }
+
+    pub fn kind(&self) -> N {
+        self.0.green.kind
+    }
+
+    pub fn green(&self) -> &Arc<GreenNode<T, N>> {
+        &self.0.green
+    }
+
+    pub fn parent(&self) -> Option<SyntaxNode<T, N>> {
+        self.0.parent.clone()
+    }
+
+    /// This node followed by its parent, grandparent and so on.
+    pub fn ancestors(&self) -> impl Iterator<Item = SyntaxNode<T, N>> {
+        std::iter::successors(Some(self.clone()), SyntaxNode::parent)
+    }
+
+    pub fn text_range(&self) -> Range<usize> {
+        self.0.offset..self.0.offset + self.0.green.len
+    }
+
+    pub fn text(&self) -> String {
+        let mut text = String::with_capacity(self.0.green.len);
+        self.0.green.write_text(&mut text);
+        text
+    }
+
+    pub fn children(&self) -> Vec<SyntaxElement<T, N>> {
+        let mut offset = self.0.offset;
+
+        self.0
+            .green
+            .children
+            .iter()
+            .map(|child| {
+                let element = match child {
+                    GreenElement::Node(green) => SyntaxElement::Node(Self(Rc::new(NodeData {
+                        green: Arc::clone(green),
+                        parent: Some(self.clone()),
+                        offset,
+                    }))),
+                    GreenElement::Token(green) => SyntaxElement::Token(SyntaxToken {
+                        green: Arc::clone(green),
+                        parent: self.clone(),
+                        offset,
+                    }),
+                };
+                offset += child.len();
+                element
+            })
+            .collect()
+    }
+
+    pub fn child_nodes(&self) -> impl Iterator<Item = SyntaxNode<T, N>> {
+        self.children()
+            .into_iter()
+            .filter_map(SyntaxElement::into_node)
+    }
+
+    /// This node and all nodes below it, in preorder.
+    pub fn descendants(&self) -> Vec<SyntaxNode<T, N>> {
+        let mut nodes = vec![self.clone()];
+        for child in self.child_nodes() {
+            nodes.extend(child.descendants());
+        }
+        nodes
+    }
+
+    /// All tokens below this node in the order of the text, trivia included.
+    pub fn tokens(&self) -> Vec<SyntaxToken<T, N>> {
+        self.children()
+            .into_iter()
+            .flat_map(|child| match child {
+                SyntaxElement::Node(node) => node.tokens(),
+                SyntaxElement::Token(token) => vec![token],
+            })
+            .collect()
+    }
+
+    /// The token whose range contains `offset`, preferring the token that starts there over one that ends there.
+    pub fn token_at_offset(&self, offset: usize) -> Option<SyntaxToken<T, N>> {
+        self.tokens()
+            .into_iter()
+            .find(|token| token.text_range().contains(&offset))
+    }
+
+    /// The smallest element whose range contains `range`, or `None` when it lies outside of this node.
+    pub fn covering_element(&self, range: Range<usize>) -> Option<SyntaxElement<T, N>> {
+        let own = self.text_range();
+        if range.start < own.start || range.end > own.end {
+            return None;
+        }
+
+        for child in self.children() {
+            let child_range = child.text_range();
+            if !child_range.is_empty()
+                && child_range.start <= range.start
+                && range.end <= child_range.end
+            {
+                return match child {
+                    SyntaxElement::Node(node) => node.covering_element(range),
+                    token => Some(token),
+                };
+            }
+        }
+
+        Some(SyntaxElement::Node(self.clone()))
+    }
+}
+
+impl<T: Copy + Debug, N: Copy + Debug> Debug for SyntaxNode<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}@{:?}", self.kind(), self.text_range())
+    }
+}
+
+/// A token of the red tree.
+pub struct SyntaxToken<T, N> {
+    green: Arc<GreenToken<T>>,
+    parent: SyntaxNode<T, N>,
+    offset: usize,
+}
+
+impl<T, N> Clone for SyntaxToken<T, N> {
+    fn clone(&self) -> Self {
+        Self {
+            green: Arc::clone(&self.green),
+            parent: self.parent.clone(),
+            offset: self.offset,
+        }
+    }
+}
+
+impl<T, N> PartialEq for SyntaxToken<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.parent == other.parent && self.offset == other.offset
+    }
+}
+
+impl<T: Copy, N: Copy> SyntaxToken<T, N> {
+    pub fn kind(&self) -> T {
+        self.green.kind
+    }
+
+    pub fn text(&self) -> &str {
+        &self.green.text
+    }
+
+    pub fn text_range(&self) -> Range<usize> {
+        self.offset..self.offset + self.green.text.len()
+    }
+
+    pub fn parent(&self) -> SyntaxNode<T, N> {
+        self.parent.clone()
+    }
+}
+
+impl<T: Copy + Debug, N: Copy + Debug> Debug for SyntaxToken<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:?}@{:?} {:?}",
+            self.kind(),
+            self.text_range(),
+            self.text()
+        )
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub enum SyntaxElement<T, N> {
+    Node(SyntaxNode<T, N>),
+    Token(SyntaxToken<T, N>),
+}
+
+impl<T: Copy, N: Copy> SyntaxElement<T, N> {
+    pub fn text_range(&self) -> Range<usize> {
+        match self {
+            Self::Node(node) => node.text_range(),
+            Self::Token(token) => token.text_range(),
+        }
+    }
+
+    pub fn parent(&self) -> Option<SyntaxNode<T, N>> {
+        match self {
+            Self::Node(node) => node.parent(),
+            Self::Token(token) => Some(token.parent()),
+        }
+    }
+
+    pub fn into_node(self) -> Option<SyntaxNode<T, N>> {
+        match self {
+            Self::Node(node) => Some(node),
+            Self::Token(_) => None,
+        }
+    }
+
+    pub fn into_token(self) -> Option<SyntaxToken<T, N>> {
+        match self {
+            Self::Node(_) => None,
+            Self::Token(token) => Some(token),
+        }
+    }
+}
+
+impl<T: Copy + Debug, N: Copy + Debug> Debug for SyntaxElement<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Node(node) => node.fmt(f),
+            Self::Token(token) => token.fmt(f),
+        }
+    }
+}
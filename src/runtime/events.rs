@@ -0,0 +1,135 @@
+//! Event based parse output, describing the structure of the input without building a tree.
+//!
+//! An LR parser reduces bottom-up, so it only learns where a node starts once the node is complete.
+//! Parsers generated in events mode therefore record a flat log while parsing: every shifted token
+//! and every reduction together with its number of children. When the input is accepted the log is
+//! turned into the nested `StartNode`, `Token` and `FinishNode` events in the order of the text.
+
+use crate::runtime::Token;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Event<T, N> {
+    StartNode(N),
+    Token(Token<T>),
+    FinishNode,
+}
+
+enum Entry<T, N> {
+    Token(Token<T>),
+    /// A node with the given number of entries as children.
+    Node(N, usize),
+}
+
+/// Records the shifts and reductions of a parse in postorder.
+pub struct Recorder<T, N> {
+    log: Vec<Entry<T, N>>,
+    is_trivia: fn(&T) -> bool,
+}
+
+impl<T: Copy, N: Copy> Recorder<T, N> {
+    /// Trivia in front of a node is kept outside of it, so a node starts at its first real token.
+    pub fn new(is_trivia: fn(&T) -> bool) -> Self {
+        Self {
+            log: Vec::new(),
+            is_trivia,
+        }
+    }
+
+    /// Records a shifted token preceded by the trivia skipped before it. Returns the number of
+    /// recorded entries, which is the value of the token on the value stack.
+    pub fn token(&mut self, trivia: Vec<Token<T>>, token: Token<T>) -> usize {
+        let count = trivia.len() + 1;
+        self.log.extend(
+            trivia
+                .into_iter()
+                .map(Entry::Token)
+                .chain(Some(Entry::Token(token))),
+        );
+        count
+    }
+
+    /// Records a reduction to a node of `kind`, whose symbols have the given values.
+    pub fn node(&mut self, kind: N, values: Vec<usize>) -> usize {
+        self.log.push(Entry::Node(kind, values.into_iter().sum()));
+        1
+    }
+
+    /// Records the trivia at the end of the input and returns the events of the whole parse.
+    pub fn finish(&mut self, trivia: Vec<Token<T>>) -> Vec<Event<T, N>> {
+        self.log.extend(trivia.into_iter().map(Entry::Token));
+        let log = std::mem::take(&mut self.log);
+
+        // Find the first entry of every node, by replaying the reductions on a stack of starts.
+        let mut opens = vec![Vec::new(); log.len()];
+        let mut starts: Vec<usize> = Vec::new();
+        for (i, entry) in log.iter().enumerate() {
+            let start = match entry {
+                Entry::Token(_) => i,
+                Entry::Node(kind, children) => {
+                    let first = starts.len() - children;
+                    let mut start = starts.get(first).copied().unwrap_or(i);
+                    starts.truncate(first);
+
+                    while let Entry::Token(token) = &log[start] {
+                        if !(self.is_trivia)(&token.kind) {
+                            break;
+                        }
+                        start += 1;
+                    }
+                    opens[start].push(*kind);
+                    start
+                }
+            };
+            starts.push(start);
+        }
+
+        let mut events = Vec::with_capacity(log.len() * 2);
+        for (entry, opens) in log.into_iter().zip(opens) {
+            // Nodes that start at the same entry are reduced from the inside out.
+            events.extend(opens.into_iter().rev().map(Event::StartNode));
+            events.push(match entry {
+                Entry::Token(token) => Event::Token(token),
+                Entry::Node(..) => Event::FinishNode,
+            });
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn token(kind: char) -> Token<char> {
+        Token { span: (0, 0), kind }
+    }
+
+    #[test]
+    fn nests_reductions() {
+        use Event::*;
+        let mut recorder = Recorder::new(|c: &char| *c == ' ');
+
+        // S -> A b, A -> (empty) a, with a space in front of `a`.
+        let empty = recorder.node("E", vec![]);
+        let a = recorder.token(vec![token(' ')], token('a'));
+        let node_a = recorder.node("A", vec![empty, a]);
+        let b = recorder.token(vec![], token('b'));
+        recorder.node("S", vec![node_a, b]);
+
+        assert_eq!(
+            recorder.finish(vec![token(' ')]),
+            [
+                StartNode("S"),
+                StartNode("A"),
+                StartNode("E"),
+                FinishNode,
+                Token(token(' ')),
+                Token(token('a')),
+                FinishNode,
+                Token(token('b')),
+                FinishNode,
+                Token(token(' ')),
+            ]
+        );
+    }
+}
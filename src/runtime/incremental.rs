@@ -0,0 +1,330 @@
+//! Incremental reparsing of a syntax tree after an edit of its text, for editors that reparse on every
+//! keystroke.
+//!
+//! A [`Tree`] keeps the tokens of the text next to the syntax tree, and for every node the state of
+//! the parser before it and the kind of the token after it. After an edit, only the tokens from the
+//! one touching the edit up to the first token that starts where an old token of the same kind
+//! started are relexed. The parser then reuses a node of the old tree in place of its tokens when
+//! they are unchanged, the parser is in the state it was in before the node, and the token after the
+//! node is of the same kind. A canonical LR parser takes the same actions on the same tokens from
+//! the same state, up to the reduction of the node, so the tree is the same as after parsing the
+//! whole text again.
+//!
+//! The lexer must not depend on the state of the parser, and must lex the text after the start of a
+//! token the same way whatever came before it.
+
+use crate::runtime::cst::{GreenElement, GreenNode, SyntaxNode};
+use crate::runtime::{Parse, ParseError, Step, Token, TokenSource};
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// A replacement of the text in `range`, in bytes, by `text`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edit {
+    pub range: Range<usize>,
+    pub text: String,
+}
+
+impl Edit {
+    /// The text after the edit.
+    pub fn apply(&self, text: &str) -> String {
+        let mut edited = String::with_capacity(text.len() + self.text.len());
+        edited.push_str(&text[..self.range.start]);
+        edited.push_str(&self.text);
+        edited.push_str(&text[self.range.end..]);
+        edited
+    }
+}
+
+/// A node of a tree with what is needed to reuse it.
+#[derive(Clone)]
+struct Subtree<T, N> {
+    node: Arc<GreenNode<T, N>>,
+    /// The indices of the first token of the node and of the token after its last one.
+    start: usize,
+    end: usize,
+    /// The state of the parser before the node, and the state it went to after reducing it.
+    state: usize,
+    goto: usize,
+    /// The kind of the first token after the node that is not trivia.
+    lookahead: T,
+}
+
+/// A syntax tree that can be reparsed incrementally after its text is edited.
+pub struct Tree<T, N> {
+    root: SyntaxNode<T, N>,
+    /// The tokens of the text, trivia and the end of input included.
+    tokens: Vec<Token<T>>,
+    /// The nodes that can be reused, in the order they were reduced, so the nodes in a node come right
+    /// before it.
+    subtrees: Vec<Subtree<T, N>>,
+}
+
+impl<T, N> Tree<T, N>
+where
+    T: Copy + PartialEq + 'static,
+    N: Copy,
+{
+    /// Parses all `tokens` with a parser generated in CST mode, which must have an empty source, for
+    /// example `std::iter::empty()`. Tokens are requested from the source without expected terminals.
+    pub fn parse<P, I, S>(parser: &mut P, mut tokens: S) -> Result<Self, ParseError<T>>
+    where
+        P: Parse<I, T, Value = Vec<GreenElement<T, N>>, Output = SyntaxNode<T, N>>,
+        I: TokenSource<T>,
+        S: TokenSource<T>,
+    {
+        let tokens = std::iter::from_fn(|| tokens.next_token(&[])).collect();
+        run(parser, tokens, None)
+    }
+
+    /// Parses the text after `edit` with a parser for the new text, reusing what it can of this tree.
+    /// Relexes the changed part of the text with the tokens of `lex`, which is given the new text from
+    /// a token onwards. The offsets of the tokens it returns are relative to the text it is given.
+    pub fn reparse<P, I, S>(
+        &self,
+        parser: &mut P,
+        edit: &Edit,
+        mut lex: impl FnMut(&str) -> S,
+    ) -> Result<Self, ParseError<T>>
+    where
+        P: Parse<I, T, Value = Vec<GreenElement<T, N>>, Output = SyntaxNode<T, N>>,
+        I: TokenSource<T>,
+        S: TokenSource<T>,
+    {
+        let text = edit.apply(&self.root.text());
+        let new_end = edit.range.start + edit.text.len();
+        let shift = |offset: usize| offset + new_end - edit.range.end;
+
+        // The tokens before the edit are kept, except the one touching it and the zero-width tokens
+        // right before that one.
+        let touching = self
+            .tokens
+            .iter()
+            .position(|token| token.span.1 >= edit.range.start)
+            .unwrap_or(self.tokens.len());
+        let restart = self
+            .tokens
+            .get(touching)
+            .map_or(edit.range.start, |token| token.span.0.min(edit.range.start));
+        let prefix = self.tokens[..touching].partition_point(|token| token.span.0 < restart);
+        let mut tokens = self.tokens[..prefix].to_vec();
+
+        // Relexing stops at the first token after the edit that starts where an old token of the same
+        // kind started, from which the rest of the text is lexed as before. Zero-width tokens start
+        // where the token after them does, so the first old token with that start and kind is taken.
+        let mut source = lex(&text[restart..]);
+        let mut suffix = self.tokens.len();
+        while let Some(token) = source.next_token(&[]) {
+            let span = (token.span.0 + restart, token.span.1 + restart);
+            if span.0 >= new_end {
+                let old = span.0 + edit.range.end - new_end;
+                let first = prefix + self.tokens[prefix..].partition_point(|t| t.span.0 < old);
+                let same = self.tokens[first..]
+                    .iter()
+                    .take_while(|t| t.span.0 == old)
+                    .position(|t| t.kind == token.kind);
+                if let Some(index) = same {
+                    suffix = first + index;
+                    break;
+                }
+            }
+            tokens.push(Token { span, ..token });
+        }
+
+        let reuse = Reuse {
+            subtrees: &self.subtrees,
+            by_start: self.subtrees.iter().enumerate().fold(
+                HashMap::<_, Vec<_>>::new(),
+                |mut by_start, (i, subtree)| {
+                    by_start.entry(subtree.start).or_default().push(i);
+                    by_start
+                },
+            ),
+            prefix,
+            suffix: (tokens.len(), suffix),
+        };
+        tokens.extend(self.tokens[suffix..].iter().map(|token| Token {
+            span: (shift(token.span.0), shift(token.span.1)),
+            kind: token.kind,
+        }));
+
+        run(parser, tokens, Some(reuse))
+    }
+
+    pub fn root(&self) -> &SyntaxNode<T, N> {
+        &self.root
+    }
+
+    pub fn tokens(&self) -> &[Token<T>] {
+        &self.tokens
+    }
+}
+
+/// The nodes of an old tree and where its tokens are in the new tokens.
+struct Reuse<'t, T, N> {
+    subtrees: &'t [Subtree<T, N>],
+    /// The indices of the subtrees starting at each token.
+    by_start: HashMap<usize, Vec<usize>>,
+    /// The number of tokens before the edit that were kept.
+    prefix: usize,
+    /// The indices of the first token after the edit that was kept, among the new and the old tokens.
+    suffix: (usize, usize),
+}
+
+impl<'t, T: Copy + PartialEq, N> Reuse<'t, T, N> {
+    /// The index of the largest subtree that can be reused at the new token `index` in `state`.
+    fn find(
+        &self,
+        tokens: &[Token<T>],
+        index: usize,
+        state: usize,
+        is_trivia: impl Fn(&T) -> bool,
+    ) -> Option<usize> {
+        let old = if index < self.prefix {
+            index
+        } else if index >= self.suffix.0 {
+            index - self.suffix.0 + self.suffix.1
+        } else {
+            return None;
+        };
+
+        self.by_start.get(&old)?.iter().rev().copied().find(|&i| {
+            let subtree = &self.subtrees[i];
+            let end = index + subtree.end - subtree.start;
+            let lookahead = tokens[end.min(tokens.len())..]
+                .iter()
+                .find(|token| !is_trivia(&token.kind));
+            subtree.state == state
+                && (index >= self.suffix.0 || end <= self.prefix)
+                && lookahead.is_some_and(|token| token.kind == subtree.lookahead)
+        })
+    }
+}
+
+/// Parses `tokens`, reusing the subtrees of `reuse` where possible.
+fn run<P, I, T, N>(
+    parser: &mut P,
+    tokens: Vec<Token<T>>,
+    reuse: Option<Reuse<'_, T, N>>,
+) -> Result<Tree<T, N>, ParseError<T>>
+where
+    P: Parse<I, T, Value = Vec<GreenElement<T, N>>, Output = SyntaxNode<T, N>>,
+    I: TokenSource<T>,
+    T: Copy + PartialEq + 'static,
+    N: Copy,
+{
+    let is_trivia = parser.parse_state().is_trivia;
+    let is_trivia = |kind: &T| is_trivia.is_some_and(|is_trivia| is_trivia(kind));
+    let mut subtrees = Vec::new();
+    // The index of the first token of each value on the stack.
+    let mut starts = Vec::new();
+    // The index of the next token, and of the token after the last one shifted.
+    let mut index = 0;
+    let mut end = 0;
+
+    let root = loop {
+        let token = match tokens.get(index) {
+            Some(&token) if is_trivia(&token.kind) => {
+                parser.parse_state_mut().trivia.push(token);
+                index += 1;
+                continue;
+            }
+            Some(&token) => token,
+            // The tokens stopped before the end of input.
+            None => break parser.error(None),
+        };
+
+        let reused = reuse.as_ref().and_then(|reuse| {
+            Some((
+                reuse,
+                reuse.find(&tokens, index, parser.state(), is_trivia)?,
+            ))
+        });
+        if let Some((reuse, i)) = reused {
+            let subtree = &reuse.subtrees[i];
+            let nested = reuse.subtrees[..i]
+                .iter()
+                .rev()
+                .take_while(|nested| nested.start >= subtree.start)
+                .count();
+            for nested in &reuse.subtrees[i - nested..=i] {
+                subtrees.push(Subtree {
+                    start: nested.start - subtree.start + index,
+                    end: nested.end - subtree.start + index,
+                    ..nested.clone()
+                });
+            }
+
+            // The trivia before the node is kept outside of it, like when the node is reduced.
+            let mut value = Vec::new();
+            for trivia in parser.parse_state_mut().take_trivia() {
+                value.extend(parser.token_value(trivia));
+            }
+            value.push(GreenElement::Node(Arc::clone(&subtree.node)));
+            parser.push_value(value);
+            parser.push_state(subtree.goto);
+            starts.push(index);
+            index += subtree.end - subtree.start;
+            end = index;
+            continue;
+        }
+
+        match parser.step(parser.state(), token.kind) {
+            Some(Step::Shift(state)) => {
+                let value = parser.token_value(token);
+                parser.push_value(value);
+                parser.push_state(state);
+                starts.push(index);
+                index += 1;
+                end = index;
+            }
+            Some(Step::Reduce(rule, len)) => {
+                let start = starts.len() - len;
+                let start = starts.get(start).copied().unwrap_or(index);
+                starts.truncate(starts.len() - len);
+                starts.push(start);
+                let states = parser.parse_state().states();
+                let state = states[states.len() - 1 - len];
+                parser.reduce(rule, len);
+
+                let node = match parser.parse_state().values().last().and_then(|v| v.last()) {
+                    Some(GreenElement::Node(node)) => Arc::clone(node),
+                    _ => continue,
+                };
+                if starts_with_token(&node) {
+                    subtrees.push(Subtree {
+                        node,
+                        start,
+                        end,
+                        state,
+                        goto: parser.state(),
+                        lookahead: token.kind,
+                    });
+                }
+            }
+            Some(Step::Accept(rule, len)) => break parser.accept(rule, len),
+            None => break parser.error(Some(token)),
+        }
+    }?;
+
+    Ok(Tree {
+        root,
+        tokens,
+        subtrees,
+    })
+}
+
+/// Whether the first element of `node` is a token. A node that starts with an empty node holds the
+/// trivia before its first token, which depends on the text before it, so it is not reused.
+fn starts_with_token<T, N>(node: &GreenNode<T, N>) -> bool
+where
+    T: Copy,
+    N: Copy,
+{
+    match node.children().first() {
+        Some(GreenElement::Token(_)) => true,
+        Some(GreenElement::Node(child)) => !child.is_empty() && starts_with_token(child),
+        None => false,
+    }
+}
@@ -0,0 +1,301 @@
+//! Indentation sensitive token streams.
+//!
+//! A [`Layout`] sits between a lexer and the parser and turns the indentation of lines into tokens, like
+//! the tokenizer of Python. The first token on a line that is indented deeper than the line before it
+//! is preceded by an indent token, and a line that is indented less closes every block it leaves
+//! with a dedent token. Every line with a token ends in a newline token, except inside brackets, where
+//! lines are joined when the brackets are configured. Lines holding only trivia are ignored. The
+//! inserted tokens have an empty span, at the end of the last token of a line for newlines and at
+//! the start of the next token for indents and dedents.
+//!
+//! A newline within a trivia token ends the line only when nothing but whitespace follows it in the
+//! token, so a comment spanning lines in the middle of a line continues that line.
+//!
+//! The first line sets the indentation of the whole source, so a source that is indented as a whole,
+//! like a block cut out of a larger file, does not start with an indent. A later line that is indented
+//! less than the first one is an unmatched dedent.
+//!
+//! Tabs advance to the next multiple of 8 columns. Indentation that compares differently when tabs are
+//! taken as a single column is rejected as ambiguous.
+
+use crate::runtime::{Token, TokenSource};
+use std::collections::VecDeque;
+use std::fmt;
+
+const TAB_WIDTH: usize = 8;
+
+/// Indentation that does not match the enclosing blocks. It stops the token stream, so it is a lexing
+/// error to the parser.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IndentError {
+    /// A line is dedented to a column at which no enclosing block starts.
+    UnmatchedDedent { offset: usize },
+    /// The indentation of a line depends on the width of a tab.
+    AmbiguousTabs { offset: usize },
+}
+
+impl IndentError {
+    /// The byte offset of the first token of the line.
+    pub fn offset(&self) -> usize {
+        match *self {
+            Self::UnmatchedDedent { offset } | Self::AmbiguousTabs { offset } => offset,
+        }
+    }
+}
+
+impl fmt::Display for IndentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let reason = match self {
+            Self::UnmatchedDedent { .. } => "the dedent does not match any outer indentation",
+            Self::AmbiguousTabs { .. } => "tabs and spaces are mixed ambiguously",
+        };
+        write!(
+            f,
+            "inconsistent indentation at offset {}, {}",
+            self.offset(),
+            reason
+        )
+    }
+}
+
+impl std::error::Error for IndentError {}
+
+/// The kinds of the tokens a layout inserts, and the end of input before which it closes all blocks.
+#[derive(Debug, Clone, Copy)]
+pub struct LayoutTokens<T> {
+    pub indent: T,
+    pub dedent: T,
+    pub newline: T,
+    pub eof: T,
+}
+
+/// The width of an indentation, with tabs to the next multiple of 8 and as a single column.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Indentation(usize, usize);
+
+impl Indentation {
+    /// The indentation of the line containing `offset`.
+    fn of_line(source: &str, offset: usize) -> Self {
+        let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let mut indentation = Indentation(0, 0);
+
+        for c in source[line_start..offset].chars() {
+            match c {
+                ' ' => indentation.0 += 1,
+                '\t' => indentation.0 += TAB_WIDTH - indentation.0 % TAB_WIDTH,
+                _ => break,
+            }
+            indentation.1 += 1;
+        }
+
+        indentation
+    }
+}
+
+/// A source of tokens inserting indents, dedents and newlines into the tokens of another source, of
+/// which it reads the line structure from `source`. Trivia is passed on unchanged. After inconsistent
+/// indentation it stops, and the cause is available from [`Self::error`].
+///
+/// The next token is requested from the inner source when the parser asks for an inserted token, with
+/// the terminals expected before it.
+pub struct Layout<'s, I, T> {
+    tokens: I,
+    source: &'s str,
+    kinds: LayoutTokens<T>,
+    is_trivia: Option<fn(&T) -> bool>,
+    /// The pairs of opening and closing brackets within which lines are joined.
+    brackets: Vec<(T, T)>,
+    depth: usize,
+    /// The indentation of the enclosing blocks, starting with the indentation of the whole source.
+    stack: Vec<Indentation>,
+    /// The end of the last token that is not trivia.
+    last_end: Option<usize>,
+    /// The end of the last token, trivia included.
+    last_token_end: usize,
+    /// Whether a line ended since the last token that is not trivia.
+    line_ended: bool,
+    pending: VecDeque<Token<T>>,
+    error: Option<IndentError>,
+    finished: bool,
+}
+
+impl<'s, I, T> Layout<'s, I, T>
+where
+    I: TokenSource<T>,
+    T: Copy + PartialEq,
+{
+    pub fn new(tokens: I, source: &'s str, kinds: LayoutTokens<T>) -> Self {
+        Self {
+            tokens,
+            source,
+            kinds,
+            is_trivia: None,
+            brackets: Vec::new(),
+            depth: 0,
+            stack: vec![Indentation(0, 0)],
+            last_end: None,
+            last_token_end: 0,
+            line_ended: false,
+            pending: VecDeque::new(),
+            error: None,
+            finished: false,
+        }
+    }
+
+    /// Passes on the tokens for which `is_trivia` holds without looking at their lines.
+    pub fn with_trivia(mut self, is_trivia: fn(&T) -> bool) -> Self {
+        self.is_trivia = Some(is_trivia);
+        self
+    }
+
+    /// Joins the lines between each pair of opening and closing brackets.
+    pub fn with_brackets(mut self, brackets: &[(T, T)]) -> Self {
+        self.brackets.extend_from_slice(brackets);
+        self
+    }
+
+    /// The inner source, for example to inspect why it stopped.
+    pub fn tokens(&self) -> &I {
+        &self.tokens
+    }
+
+    pub fn error(&self) -> Option<IndentError> {
+        self.error
+    }
+
+    fn insert(&mut self, kind: T, offset: usize) {
+        self.pending.push_back(Token {
+            span: (offset, offset),
+            kind,
+        });
+    }
+
+    /// Inserts the tokens that precede `token`, which is not trivia.
+    fn layout(&mut self, token: &Token<T>) -> Result<(), IndentError> {
+        let start = token.span.0;
+
+        if token.kind == self.kinds.eof {
+            if let Some(end) = self.last_end {
+                self.insert(self.kinds.newline, end);
+            }
+            for _ in 1..self.stack.len() {
+                self.insert(self.kinds.dedent, start);
+            }
+            self.stack.truncate(1);
+            return Ok(());
+        }
+
+        let new_line = match self.last_end {
+            Some(_) => self.depth == 0 && self.line_ended,
+            None => true,
+        };
+        if !new_line {
+            return Ok(());
+        }
+
+        if let Some(end) = self.last_end {
+            self.insert(self.kinds.newline, end);
+        }
+
+        let indentation = Indentation::of_line(self.source, start);
+        if self.last_end.is_none() {
+            self.stack[0] = indentation;
+            return Ok(());
+        }
+        let ambiguous = IndentError::AmbiguousTabs { offset: start };
+        let top = *self.stack.last().unwrap();
+
+        if indentation.0 > top.0 {
+            if indentation.1 <= top.1 {
+                return Err(ambiguous);
+            }
+            self.stack.push(indentation);
+            self.insert(self.kinds.indent, start);
+        } else {
+            while self.stack.len() > 1 && self.stack.last().unwrap().0 > indentation.0 {
+                self.stack.pop();
+                self.insert(self.kinds.dedent, start);
+            }
+            let top = *self.stack.last().unwrap();
+            if top.0 != indentation.0 {
+                return Err(IndentError::UnmatchedDedent { offset: start });
+            } else if top.1 != indentation.1 {
+                return Err(ambiguous);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'s, I, T> TokenSource<T> for Layout<'s, I, T>
+where
+    I: TokenSource<T>,
+    T: Copy + PartialEq,
+{
+    fn next_token(&mut self, expected: &[T]) -> Option<Token<T>> {
+        while self.pending.is_empty() {
+            if self.finished {
+                return None;
+            }
+
+            let token = match self.tokens.next_token(expected) {
+                Some(token) => token,
+                None => {
+                    self.finished = true;
+                    return None;
+                }
+            };
+            // Text the inner source skipped between tokens ends the line at any newline, trivia only
+            // at a newline which is not followed by more of it, like the end of a line comment.
+            if self.source[self.last_token_end..token.span.0].contains('\n') {
+                self.line_ended = true;
+            }
+            self.last_token_end = token.span.1;
+            if self
+                .is_trivia
+                .is_some_and(|is_trivia| is_trivia(&token.kind))
+            {
+                let text = &self.source[token.span.0..token.span.1];
+                if text
+                    .rfind('\n')
+                    .is_some_and(|i| text[i..].trim().is_empty())
+                {
+                    self.line_ended = true;
+                }
+                return Some(token);
+            }
+
+            if let Err(error) = self.layout(&token) {
+                self.error = Some(error);
+                self.finished = true;
+                self.pending.clear();
+                return None;
+            }
+
+            if self.brackets.iter().any(|&(open, _)| open == token.kind) {
+                self.depth += 1;
+            } else if self.brackets.iter().any(|&(_, close)| close == token.kind) {
+                self.depth = self.depth.saturating_sub(1);
+            }
+            self.last_end = Some(token.span.1);
+            self.line_ended = false;
+            self.pending.push_back(token);
+        }
+
+        self.pending.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn measures_tabs() {
+        let source = "a\n  \tb\n\t c";
+        assert_eq!(Indentation::of_line(source, 0), Indentation(0, 0));
+        assert_eq!(Indentation::of_line(source, 5), Indentation(8, 3));
+        assert_eq!(Indentation::of_line(source, 9), Indentation(9, 2));
+    }
+}
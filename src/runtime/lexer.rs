@@ -0,0 +1,339 @@
+//! The runtime of generated lexers, see [`crate::lexer`].
+//!
+//! A lexer either splits a whole source into tokens up front, or is used as the [`TokenSource`] of a
+//! parser through a [`Scanner`]. The scanner lexes a token only when the parser needs it, and then
+//! prefers the tokens the parser expects: when `>>` can be a shift operator or two closing angle
+//! brackets, it is lexed as the brackets in a state that only expects a `>`.
+//!
+//! Tokens can push and pop modes, in which other tokens are recognised, and callbacks registered on
+//! the scanner can take over after a match, for input such as nested comments and heredocs.
+
+use crate::runtime::{Token, TokenSource};
+use std::collections::VecDeque;
+use std::fmt;
+
+/// A text that no token definition matches.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LexError {
+    /// The byte offset of the first character that could not be lexed.
+    pub offset: usize,
+    pub found: char,
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "no token matches {:?} at offset {}",
+            self.found, self.offset
+        )
+    }
+}
+
+impl std::error::Error for LexError {}
+
+/// The tables of a deterministic automaton recognising the tokens of a mode, in which state 0 is the
+/// start state.
+pub struct Mode<T: 'static> {
+    /// Per state, the sorted ranges of characters with the state they lead to.
+    pub transitions: &'static [&'static [(char, char, usize)]],
+    /// Per state, the kinds of token that are matched when a match ends in it, in order of priority.
+    pub accept: &'static [&'static [T]],
+}
+
+/// A change of the mode stack after a token is matched.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ModeAction {
+    Push(usize),
+    /// Returns to the previous mode. The initial mode is never popped.
+    Pop,
+}
+
+/// A lexer with one automaton per mode, of which the first is the initial mode.
+pub struct Lexer<T: 'static> {
+    pub modes: &'static [Mode<T>],
+    /// The kinds of token that change the mode when they are matched.
+    pub actions: &'static [(T, ModeAction)],
+    /// The kinds of token the parser skips, which are always expected.
+    pub trivia: &'static [T],
+    pub eof: T,
+}
+
+impl<T: Copy + PartialEq> Lexer<T> {
+    fn next_state(&self, mode: usize, state: usize, c: char) -> Option<usize> {
+        let row = self.modes[mode].transitions[state];
+        let i = row.partition_point(|&(_, end, _)| end < c);
+        row.get(i)
+            .filter(|&&(start, _, _)| start <= c)
+            .map(|&(_, _, next)| next)
+    }
+
+    /// The kind and length in bytes of the longest token in `mode` at the start of `text`.
+    pub fn longest_match(&self, mode: usize, text: &str) -> Option<(T, usize)> {
+        self.find_match(mode, text, |_| true)
+    }
+
+    /// Like [`Self::longest_match`], but only matches tokens of the `expected` kinds or trivia. When
+    /// none of them matches, the text is matched as if nothing was expected, so the parser can report
+    /// the unexpected token.
+    pub fn longest_expected_match(
+        &self,
+        mode: usize,
+        text: &str,
+        expected: &[T],
+    ) -> Option<(T, usize)> {
+        self.find_match(mode, text, |kind| {
+            expected.contains(kind) || self.trivia.contains(kind)
+        })
+        .or_else(|| self.longest_match(mode, text))
+    }
+
+    fn find_match(
+        &self,
+        mode: usize,
+        text: &str,
+        allowed: impl Fn(&T) -> bool,
+    ) -> Option<(T, usize)> {
+        let mut state = 0;
+        let mut found = None;
+
+        for (i, c) in text.char_indices() {
+            state = match self.next_state(mode, state, c) {
+                Some(next) => next,
+                None => break,
+            };
+            if let Some(&kind) = self.modes[mode].accept[state]
+                .iter()
+                .find(|kind| allowed(kind))
+            {
+                found = Some((kind, i + c.len_utf8()));
+            }
+        }
+
+        found
+    }
+
+    /// Lexes `source` on demand of a parser.
+    pub fn scan<'s>(&'s self, source: &'s str) -> Scanner<'s, T> {
+        Scanner {
+            lexer: self,
+            source,
+            offset: 0,
+            modes: vec![0],
+            pending: VecDeque::new(),
+            callbacks: Vec::new(),
+            error: None,
+            finished: false,
+        }
+    }
+
+    /// Splits all of `source` into tokens, ending with an empty end of input token.
+    pub fn tokenize(&self, source: &str) -> Result<Vec<Token<T>>, LexError> {
+        self.scan(source).tokenize()
+    }
+}
+
+/// Called when a token of a kind it is registered for is matched, after the mode action of the token.
+pub type Callback<T> = fn(&mut Matched<'_, T>);
+
+/// A token that has just been matched, which a callback can extend, replace or drop.
+///
+/// The matched token is produced, unless it is skipped, followed by the emitted tokens. Lexing goes
+/// on after the consumed input.
+pub struct Matched<'a, T> {
+    source: &'a str,
+    kind: T,
+    start: usize,
+    end: usize,
+    skipped: bool,
+    emitted: Vec<Token<T>>,
+    modes: &'a mut Vec<usize>,
+}
+
+impl<'a, T: Copy> Matched<'a, T> {
+    pub fn kind(&self) -> T {
+        self.kind
+    }
+
+    pub fn set_kind(&mut self, kind: T) {
+        self.kind = kind;
+    }
+
+    pub fn span(&self) -> (usize, usize) {
+        (self.start, self.end)
+    }
+
+    /// The text of the token, including the input consumed so far.
+    pub fn text(&self) -> &'a str {
+        &self.source[self.start..self.end]
+    }
+
+    /// The input after the token.
+    pub fn rest(&self) -> &'a str {
+        &self.source[self.end..]
+    }
+
+    /// Adds the next `len` bytes of the input to the token.
+    ///
+    /// # Panics
+    ///
+    /// When `len` exceeds the rest of the input or does not end on a character boundary.
+    pub fn consume(&mut self, len: usize) {
+        assert!(
+            self.rest().is_char_boundary(len),
+            "cannot consume {} bytes of the input",
+            len
+        );
+        self.end += len;
+    }
+
+    /// Drops the matched token, so only the emitted tokens are produced.
+    pub fn skip(&mut self) {
+        self.skipped = true;
+    }
+
+    /// Produces `token` after the matched token and the tokens emitted before it.
+    pub fn emit(&mut self, token: Token<T>) {
+        self.emitted.push(token);
+    }
+
+    /// The index of the current mode.
+    pub fn mode(&self) -> usize {
+        *self.modes.last().unwrap()
+    }
+
+    pub fn push_mode(&mut self, mode: usize) {
+        self.modes.push(mode);
+    }
+
+    /// Returns to the previous mode. The initial mode is never popped.
+    pub fn pop_mode(&mut self) {
+        if self.modes.len() > 1 {
+            self.modes.pop();
+        }
+    }
+}
+
+/// A source of tokens lexing the next token when the parser asks for it. It stops at the first text it
+/// cannot lex, which fails the parse, after which the cause is available from [`Self::error`].
+pub struct Scanner<'s, T: 'static> {
+    lexer: &'s Lexer<T>,
+    source: &'s str,
+    offset: usize,
+    /// The stack of modes, starting with the initial mode.
+    modes: Vec<usize>,
+    /// Tokens emitted by a callback that have not been requested yet.
+    pending: VecDeque<Token<T>>,
+    callbacks: Vec<(T, Callback<T>)>,
+    error: Option<LexError>,
+    finished: bool,
+}
+
+impl<'s, T: Copy + PartialEq> Scanner<'s, T> {
+    /// Calls `callback` whenever a token of `kind` is matched.
+    pub fn with_callback(mut self, kind: T, callback: Callback<T>) -> Self {
+        self.callbacks.push((kind, callback));
+        self
+    }
+
+    pub fn error(&self) -> Option<LexError> {
+        self.error
+    }
+
+    /// Splits the rest of the source into tokens, ending with an empty end of input token.
+    pub fn tokenize(mut self) -> Result<Vec<Token<T>>, LexError> {
+        let mut tokens = Vec::new();
+        while let Some(token) = self.lex(None)? {
+            tokens.push(token);
+        }
+        Ok(tokens)
+    }
+
+    /// Lexes the next token, of the `expected` kinds when possible, or returns `None` after the end of
+    /// input.
+    fn lex(&mut self, expected: Option<&[T]>) -> Result<Option<Token<T>>, LexError> {
+        loop {
+            if let Some(token) = self.pending.pop_front() {
+                return Ok(Some(token));
+            }
+            if self.finished {
+                return Ok(None);
+            }
+
+            let rest = &self.source[self.offset..];
+            if rest.is_empty() {
+                self.finished = true;
+                return Ok(Some(Token {
+                    span: (self.offset, self.offset),
+                    kind: self.lexer.eof,
+                }));
+            }
+
+            let mode = *self.modes.last().unwrap();
+            let found = match expected {
+                Some(expected) => self.lexer.longest_expected_match(mode, rest, expected),
+                None => self.lexer.longest_match(mode, rest),
+            };
+            let (kind, len) = match found {
+                Some(found) => found,
+                None => {
+                    self.finished = true;
+                    return Err(LexError {
+                        offset: self.offset,
+                        found: rest.chars().next().unwrap(),
+                    });
+                }
+            };
+
+            match self.lexer.actions.iter().find(|(k, _)| *k == kind) {
+                Some((_, ModeAction::Push(mode))) => self.modes.push(*mode),
+                Some((_, ModeAction::Pop)) if self.modes.len() > 1 => {
+                    self.modes.pop();
+                }
+                _ => {}
+            }
+
+            let mut matched = Matched {
+                source: self.source,
+                kind,
+                start: self.offset,
+                end: self.offset + len,
+                skipped: false,
+                emitted: Vec::new(),
+                modes: &mut self.modes,
+            };
+            if let Some((_, callback)) = self.callbacks.iter().find(|(k, _)| *k == kind) {
+                callback(&mut matched);
+            }
+
+            let Matched {
+                kind,
+                start,
+                end,
+                skipped,
+                emitted,
+                ..
+            } = matched;
+            if !skipped {
+                self.pending.push_back(Token {
+                    span: (start, end),
+                    kind,
+                });
+            }
+            self.pending.extend(emitted);
+            self.offset = end;
+        }
+    }
+}
+
+impl<'s, T: Copy + PartialEq> TokenSource<T> for Scanner<'s, T> {
+    fn next_token(&mut self, expected: &[T]) -> Option<Token<T>> {
+        match self.lex(Some(expected)) {
+            Ok(token) => token,
+            Err(error) => {
+                self.error = Some(error);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,168 @@
+//! Automatic repair of syntax errors.
+//!
+//! When the parser finds a token it has no action for, the repair searches for the cheapest sequence of
+//! edits to the upcoming tokens after which parsing can continue, in the style of the CPCT+ algorithm.
+//! An edit inserts a terminal, deletes a token or shifts a token unchanged. Insertions and deletions
+//! have a cost per terminal, and shifts are free. A sequence is a repair when the tokens following its
+//! last edit can be shifted, or when it lets the parser accept the input.
+//!
+//! The edits are only simulated on the state stack of the parser, using its table, so no semantic
+//! actions run while searching. The configurations are explored by increasing cost, and in the order
+//! they were found within the same cost, which makes the chosen repair deterministic.
+
+use crate::runtime::{simulate, Outcome, Parse, Token, TokenSource};
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+
+/// The number of tokens after the last edit that must be shifted for a repair to succeed.
+pub const SHIFTS: usize = 3;
+
+/// The number of configurations explored before giving up, so searching for a repair never takes long.
+const MAX_CONFIGURATIONS: usize = 10_000;
+
+/// An edit of the tokens at a syntax error.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Repair<T> {
+    /// Inserts a token of the kind, with an empty span before the next token.
+    Insert(T),
+    Delete(Token<T>),
+    /// Keeps the token, which may be needed between other edits.
+    Shift(Token<T>),
+}
+
+impl<T: fmt::Display> fmt::Display for Repair<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Insert(kind) => write!(f, "insert `{}`", kind),
+            Self::Delete(token) => write!(f, "delete `{}`", token.kind),
+            Self::Shift(token) => write!(f, "keep `{}`", token.kind),
+        }
+    }
+}
+
+/// The costs of the edits of a repair. A terminal without a cost is never inserted or deleted.
+#[derive(Debug, Clone, Copy)]
+pub struct RepairOptions<T> {
+    pub insert_cost: fn(&T) -> Option<usize>,
+    pub delete_cost: fn(&T) -> Option<usize>,
+    /// The highest total cost of a repair, above which the parse stops at the error.
+    pub max_cost: usize,
+}
+
+impl<T> Default for RepairOptions<T> {
+    /// Every insertion and deletion costs 1, and repairs take at most 3 edits.
+    fn default() -> Self {
+        Self {
+            insert_cost: |_| Some(1),
+            delete_cost: |_| Some(1),
+            max_cost: 3,
+        }
+    }
+}
+
+struct Configuration<T> {
+    stack: Vec<usize>,
+    /// The index of the next token.
+    position: usize,
+    repairs: Vec<Repair<T>>,
+    /// The number of tokens shifted since the last insertion or deletion.
+    shifted: usize,
+}
+
+/// Searches the cheapest repair for the error at the first of `tokens`, from the states of `parser`.
+/// The tokens should reach a few tokens past the error, as a repair cannot edit beyond them. Returns
+/// the edits without the shifts after the last insertion or deletion.
+pub fn search<I, T, P>(
+    parser: &P,
+    tokens: &[Token<T>],
+    options: &RepairOptions<T>,
+) -> Option<Vec<Repair<T>>>
+where
+    P: Parse<I, T> + ?Sized,
+    I: TokenSource<T>,
+    T: Copy + PartialEq + 'static,
+{
+    let eof = parser.eof();
+    let error_terminal = parser.error_terminal();
+
+    // The configurations still to explore, by their cost.
+    let mut queues = (0..=options.max_cost)
+        .map(|_| VecDeque::new())
+        .collect::<Vec<_>>();
+    queues[0].push_back(Configuration {
+        stack: parser.parse_state().states().to_vec(),
+        position: 0,
+        repairs: Vec::new(),
+        shifted: 0,
+    });
+    let mut visited = HashSet::new();
+
+    for cost in 0..=options.max_cost {
+        while let Some(configuration) = queues[cost].pop_front() {
+            // The source stopped before the end of input when no tokens are left to shift.
+            let done = configuration.shifted == SHIFTS || configuration.position == tokens.len();
+            if done && !configuration.repairs.is_empty() {
+                return Some(trim(configuration.repairs));
+            }
+            if !visited.insert((configuration.stack.clone(), configuration.position)) {
+                continue;
+            }
+            if visited.len() > MAX_CONFIGURATIONS {
+                return None;
+            }
+
+            let mut edits = Vec::new();
+            if let Some(&token) = tokens.get(configuration.position) {
+                edits.push((Repair::Shift(token), Some(0)));
+                if token.kind != eof {
+                    edits.push((Repair::Delete(token), (options.delete_cost)(&token.kind)));
+                }
+            }
+            let state = *configuration.stack.last().unwrap();
+            for &kind in parser.expected_terminals(state) {
+                if kind != eof && Some(kind) != error_terminal {
+                    edits.push((Repair::Insert(kind), (options.insert_cost)(&kind)));
+                }
+            }
+
+            for (edit, edit_cost) in edits {
+                let cost = match edit_cost {
+                    Some(edit_cost) if cost + edit_cost <= options.max_cost => cost + edit_cost,
+                    _ => continue,
+                };
+                let mut stack = configuration.stack.clone();
+                let (position, shifted) = match edit {
+                    Repair::Shift(_) => (configuration.position + 1, configuration.shifted + 1),
+                    Repair::Delete(_) => (configuration.position + 1, 0),
+                    Repair::Insert(_) => (configuration.position, 0),
+                };
+                let mut repairs = configuration.repairs.clone();
+                repairs.push(edit);
+
+                if let Repair::Shift(Token { kind, .. }) | Repair::Insert(kind) = edit {
+                    match simulate(parser, &mut stack, kind) {
+                        Outcome::Shifted => {}
+                        Outcome::Accepted => return Some(trim(repairs)),
+                        Outcome::Rejected => continue,
+                    }
+                }
+                queues[cost].push_back(Configuration {
+                    stack,
+                    position,
+                    repairs,
+                    shifted,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Removes the shifts after the last insertion or deletion.
+fn trim<T>(mut repairs: Vec<Repair<T>>) -> Vec<Repair<T>> {
+    while let Some(Repair::Shift(_)) = repairs.last() {
+        repairs.pop();
+    }
+    repairs
+}
@@ -0,0 +1,30 @@
+//! Parsing tokens from an asynchronous stream, such as tokens lexed from data arriving over a socket.
+//!
+//! The tokens are fed to the parser as they arrive, see [`Parse::feed`], so the parse suspends while
+//! waiting for the next token instead of blocking a thread. Any executor can run it, as it only
+//! depends on the `Stream` trait.
+
+use crate::runtime::{Feed, Parse, ParseError, Token, TokenSource};
+use futures_core::Stream;
+use std::future;
+use std::pin::Pin;
+
+/// Parses the tokens of `tokens`, which should end with the end of input like the tokens of an
+/// iterator. The parser should be created with an empty source, for example `std::iter::empty()`.
+/// When the stream ends before the end of input, the parse fails like when a source stops early.
+pub async fn parse<P, I, T, S>(parser: &mut P, mut tokens: S) -> Result<P::Output, ParseError<T>>
+where
+    P: Parse<I, T>,
+    I: TokenSource<T>,
+    T: Copy + PartialEq + 'static,
+    S: Stream<Item = Token<T>> + Unpin,
+{
+    while let Some(token) = future::poll_fn(|cx| Pin::new(&mut tokens).poll_next(cx)).await {
+        match parser.feed(token) {
+            Feed::NeedMore => {}
+            Feed::Accepted(output) => return Ok(output),
+            Feed::Error(error) => return Err(error),
+        }
+    }
+    parser.error(None)
+}
@@ -0,0 +1,225 @@
+//! Error-tolerant parsing into a syntax tree, for editors that need a tree of every input.
+//!
+//! A tolerant parse never fails. When the parser finds a token it has no action for, it first tries to
+//! continue as if a single token was missing before it, or up to three at the end of input, by
+//! searching insertions like [`crate::runtime::repair`]. Each inserted token is added to the tree as an
+//! empty token wrapped in a MISSING node. Otherwise it resynchronises on the FOLLOW sets of the
+//! constructs being parsed: states are popped until one is found with a goto on a non-terminal the
+//! token may follow, after which the parser can shift it. The tokens and nodes of the popped states are
+//! wrapped in an ERROR node, which stands in for the non-terminal, and the parse continues after its
+//! goto. A FOLLOW set may hold terminals the canonical LR(1) automaton has no action for in that
+//! context, so only gotos after which the token is shifted are taken. A token that cannot follow any
+//! of the constructs is skipped into an ERROR node.
+//!
+//! ERROR nodes are added after the last complete element before them, so they end up in the node of
+//! the construct that was being parsed around them. Adjacent ERROR nodes are merged.
+
+use crate::runtime::cst::{self, GreenElement, GreenNode, RecoveryKinds, SyntaxNode};
+use crate::runtime::repair::{self, Repair, RepairOptions};
+use crate::runtime::{shift_lookahead, simulate, Outcome, Parse, ParseError, Token, TokenSource};
+use std::sync::Arc;
+
+/// The number of tokens that may be missing before a token, and at the end of input.
+const MISSING: usize = 1;
+const MISSING_AT_END: usize = 3;
+
+/// Parses the input of a parser generated in CST mode into a tree, recovering from every error. Returns
+/// the tree, whose text is the input, with the errors found. When the source stops before the end of
+/// input, the tree ends where it stopped.
+pub fn parse<P, I, T, N>(parser: &mut P) -> (SyntaxNode<T, N>, Vec<ParseError<T>>)
+where
+    P: Parse<I, T, Value = Vec<GreenElement<T, N>>, Output = SyntaxNode<T, N>>,
+    I: TokenSource<T>,
+    T: Copy + PartialEq + 'static,
+    N: RecoveryKinds,
+{
+    let mut errors = Vec::new();
+    // The elements of errors before the first value on the stack.
+    let mut leading = Vec::new();
+    // Whether tokens are being skipped, during which further errors are not reported.
+    let mut skipping = false;
+    let mut end = 0;
+
+    loop {
+        let token = match parser.current_token() {
+            Some(token) => token,
+            None => {
+                errors.extend(parser.error(None).err());
+                let eof = Token {
+                    span: (end, end),
+                    kind: parser.eof(),
+                };
+                parser.parse_state_mut().insert(eof);
+                continue;
+            }
+        };
+        end = token.span.1;
+
+        let error = match parser.action(token) {
+            Some(Ok(root)) => return (prepend(root, leading), errors),
+            Some(Err(error)) => error,
+            None => {
+                skipping &= parser.parse_state().lookahead.is_some();
+                continue;
+            }
+        };
+        if !skipping {
+            errors.push(error);
+        }
+
+        let at_end = token.kind == parser.eof();
+        if insert_missing(parser, at_end) {
+            continue;
+        }
+
+        match resynchronise(parser, token.kind) {
+            Some((depth, goto)) => {
+                let count = parser.parse_state().states().len() - 1 - depth;
+                let values = parser.pop_values(count);
+                parser.pop_states(count);
+                add_error(parser, &mut leading, values.into_iter().flatten().collect());
+                // The recovered non-terminal is empty, as its elements are in the ERROR node.
+                parser.push_value(Vec::new());
+                parser.push_state(goto);
+            }
+            None if at_end => {
+                // The end of input cannot follow anything on the stack, so all of it is an error.
+                let mut elements = leading;
+                let values = std::mem::take(&mut parser.parse_state_mut().value_stack);
+                elements.extend(values.into_iter().flatten());
+                let mut trailing = parser.token_value(token);
+                // The end of input is not part of the tree.
+                trailing.pop();
+
+                let children = vec![GreenElement::node(N::error(), elements)];
+                let root = GreenNode::new(N::error(), children.into_iter().chain(trailing).collect());
+                return (SyntaxNode::new_root(Arc::new(root)), errors);
+            }
+            None => {
+                parser.next_token();
+                let elements = parser.token_value(token);
+                add_error(parser, &mut leading, elements);
+                skipping = true;
+            }
+        }
+    }
+}
+
+/// Finds the state closest to the top of the stack, below the top, with a goto on a non-terminal that
+/// `kind` can follow and after which it is shifted. Returns its depth with the state of the goto.
+fn resynchronise<P, I, T>(parser: &P, kind: T) -> Option<(usize, usize)>
+where
+    P: Parse<I, T> + ?Sized,
+    I: TokenSource<T>,
+    T: Copy + PartialEq + 'static,
+{
+    let states = parser.parse_state().states();
+    (0..states.len() - 1).rev().find_map(|depth| {
+        let follows = parser.follow_gotos(states[depth]);
+        follows.iter().find_map(|&(goto, follow)| {
+            let mut stack = states[..=depth].to_vec();
+            stack.push(goto);
+            let shifted = follow.contains(&kind)
+                && !matches!(simulate(parser, &mut stack, kind), Outcome::Rejected);
+            shifted.then_some((depth, goto))
+        })
+    })
+}
+
+/// Inserts the tokens missing before the lookahead, if few enough are, and shifts them as MISSING
+/// nodes. Returns whether any were inserted.
+fn insert_missing<P, I, T, N>(parser: &mut P, at_end: bool) -> bool
+where
+    P: Parse<I, T, Value = Vec<GreenElement<T, N>>> + ?Sized,
+    I: TokenSource<T>,
+    T: Copy + PartialEq + 'static,
+    N: RecoveryKinds,
+{
+    let options = RepairOptions {
+        insert_cost: |_| Some(1),
+        delete_cost: |_| None,
+        max_cost: if at_end { MISSING_AT_END } else { MISSING },
+    };
+    let expected = parser.expected();
+    let tokens = parser
+        .parse_state_mut()
+        .peek(options.max_cost + repair::SHIFTS + 1, expected);
+    let repairs = match repair::search(parser, &tokens, &options) {
+        Some(repairs) => repairs,
+        None => return false,
+    };
+
+    for repair in repairs {
+        if let Repair::Insert(kind) = repair {
+            let start = tokens[0].span.0;
+            parser.parse_state_mut().insert(Token {
+                span: (start, start),
+                kind,
+            });
+            shift_lookahead(parser);
+
+            // The trivia before the missing token stays outside of its node.
+            let mut value = parser.pop_values(1).pop().unwrap();
+            let token = value.pop().unwrap();
+            value.push(GreenElement::node(N::missing(), vec![token]));
+            parser.push_value(value);
+        } else {
+            shift_lookahead(parser);
+        }
+    }
+    true
+}
+
+/// Adds `elements` in an ERROR node after the value on top of the stack, or to the `leading` elements
+/// when the stack is empty. Trivia in front of them is kept outside of the node.
+fn add_error<P, I, T, N>(
+    parser: &mut P,
+    leading: &mut Vec<GreenElement<T, N>>,
+    elements: Vec<GreenElement<T, N>>,
+) where
+    P: Parse<I, T, Value = Vec<GreenElement<T, N>>> + ?Sized,
+    I: TokenSource<T>,
+    T: Copy + PartialEq + 'static,
+    N: RecoveryKinds,
+{
+    let is_trivia = parser.parse_state().is_trivia;
+    let mut elements = cst::node_elements(N::error(), vec![elements], |kind| {
+        is_trivia.is_some_and(|is_trivia| is_trivia(kind))
+    });
+    let target = match parser.parse_state_mut().value_stack.last_mut() {
+        Some(value) => value,
+        None => leading,
+    };
+
+    // An error right after another one extends it, together with the trivia between them.
+    if let Some(GreenElement::Node(previous)) = target.last() {
+        if previous.kind() == N::error() {
+            let previous = previous.clone();
+            target.pop();
+            let mut children = previous.children().to_vec();
+            for element in elements.drain(..) {
+                match element {
+                    GreenElement::Node(node) if node.kind() == N::error() => {
+                        children.extend(node.children().iter().cloned())
+                    }
+                    element => children.push(element),
+                }
+            }
+            elements.push(GreenElement::node(N::error(), children));
+        }
+    }
+    target.extend(elements);
+}
+
+/// Adds the `leading` elements before the children of the root.
+fn prepend<T: Copy, N: Copy>(
+    root: SyntaxNode<T, N>,
+    leading: Vec<GreenElement<T, N>>,
+) -> SyntaxNode<T, N> {
+    if leading.is_empty() {
+        return root;
+    }
+    let green = root.green();
+    let children = leading.into_iter().chain(green.children().iter().cloned());
+    SyntaxNode::new_root(Arc::new(GreenNode::new(green.kind(), children.collect())))
+}
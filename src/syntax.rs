@@ -0,0 +1,1003 @@
+//! Reader for `.yaw` grammar files.
+//!
+//! A grammar file is a list of rules, each naming a non-terminal followed by its alternatives:
+//!
+//! ```text
+//! // The first rule is the start rule.
+//! Start: Expression;
+//! Expression: Identifier | FunctionCall;
+//! FunctionCall: Expression LParen Arguments RParen | Expression LParen RParen;
+//! Arguments: Arguments Comma Expression | Expression;
+//! ```
+//!
+//! Every name that appears on the left hand side of a rule is a non-terminal, all other names are terminals.
+//!
+//! A non-terminal can declare the Rust type of its value between angle brackets, and every alternative
+//! can end in a semantic action computing that value. Within the action the value of each symbol is
+//! bound to its label, or to `_1`, `_2`, ... by position when it has none. Terminals have a
+//! `Token` as value.
+//!
+//! ```text
+//! Sum<i64>: lhs:Sum Plus rhs:Number => { lhs + rhs } | Number;
+//! Number<i64>: Digits => { source[_1.span.0.._1.span.1].parse().unwrap() };
+//! ```
+//!
+//! An alternative without action passes on the value of its only symbol when that has the type of the
+//! rule, and otherwise results in `()`.
+//!
+//! Instead of code, an alternative can be given a name after the arrow. Parsers generated with an
+//! actions trait use it to name the method reducing that alternative.
+//!
+//! ```text
+//! FunctionCall: Expression LParen Arguments RParen => call | Expression LParen RParen => call_without_arguments;
+//! ```
+//!
+//! Terminals can be defined by a literal or a regular expression, from which a lexer is generated, see
+//! [`crate::lexer`]. Definitions are listed between the rules.
+//!
+//! ```text
+//! If = "if";
+//! Identifier = /[a-z_][a-z0-9_]*/;
+//! ```
+//!
+//! Rules can also use literals directly. A literal that is not defined as a token is added to the lexer
+//! as a terminal named after its text, such as `Else` for `"else"` and `LtEquals` for `"<="`.
+//!
+//! ```text
+//! Statement: "if" Expression Block "else" Block;
+//! ```
+//!
+//! Rules can recover from syntax errors with the `error` terminal, as in yacc. When the parser finds
+//! an error, it returns to the last state in which `error` can be shifted, skips the input up to a token
+//! that can follow it and reduces the rule, of which the action can build a placeholder value. The
+//! terminal is named `Error` in the generated code.
+//!
+//! ```text
+//! Statement: Expression ";" | error ";";
+//! ```
+//!
+//! Definitions can be restricted to lexer modes between angle brackets, and can push or pop a mode when
+//! they are matched. Definitions without modes are lexed in the `Initial` mode.
+//!
+//! ```text
+//! Quote = "\"" => push String;
+//! <String> Text = /[^"]+/;
+//! <String> EndQuote = "\"" => pop;
+//! ```
+
+use crate::common::{Grammar, NonTerminal, Rule, Symbol, Terminal};
+use crate::lexer::{ModeAction, Pattern, TokenDefinition};
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Mutex;
+
+/// The name of the terminal standing for a syntax error, which is written `error` in rules.
+pub const ERROR: &str = "Error";
+
+/// An interned symbol name, used both as terminal and non-terminal type for grammars read from a file.
+#[derive(Eq, PartialEq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub struct Name(&'static str);
+
+impl Name {
+    pub fn new(name: &str) -> Self {
+        static NAMES: Mutex<Option<HashSet<&'static str>>> = Mutex::new(None);
+
+        let mut names = NAMES.lock().unwrap();
+        let names = names.get_or_insert_with(HashSet::new);
+
+        match names.get(name) {
+            Some(interned) => Self(interned),
+            None => {
+                let interned: &'static str = Box::leak(name.to_owned().into_boxed_str());
+                names.insert(interned);
+                Self(interned)
+            }
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl fmt::Debug for Name {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl Terminal for Name {
+    fn eof() -> Self {
+        Self::new("Eof")
+    }
+
+    fn is_error(&self) -> bool {
+        self.0 == ERROR
+    }
+}
+
+impl NonTerminal for Name {}
+
+pub struct GrammarFile {
+    grammar: Grammar<Name, Name>,
+    tokens: Vec<TokenDefinition>,
+}
+
+impl GrammarFile {
+    pub fn grammar(&self) -> &Grammar<Name, Name> {
+        &self.grammar
+    }
+
+    /// The token definitions in the file, in order of priority.
+    pub fn tokens(&self) -> &[TokenDefinition] {
+        &self.tokens
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for SyntaxError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Ident(String),
+    /// The text between a pair of angle brackets.
+    Type(String),
+    /// The text of a block between braces, including the braces.
+    Code(String),
+    /// The text of a string literal, with its escapes resolved.
+    Literal(String),
+    /// The text of a regular expression between slashes.
+    Regex(String),
+    Arrow,
+    Equals,
+    Colon,
+    Pipe,
+    Semicolon,
+    Eof,
+}
+
+struct Lexeme {
+    kind: TokenKind,
+    line: usize,
+    column: usize,
+}
+
+struct Reader<'s> {
+    chars: std::iter::Peekable<std::str::Chars<'s>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'s> Reader<'s> {
+    fn new(source: &'s str) -> Self {
+        Self {
+            chars: source.chars().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn error<R>(&self, message: impl Into<String>) -> Result<R, SyntaxError> {
+        Err(SyntaxError {
+            line: self.line,
+            column: self.column,
+            message: message.into(),
+        })
+    }
+
+    fn skip_trivia(&mut self) -> Result<(), SyntaxError> {
+        loop {
+            match self.chars.peek().copied() {
+                Some(c) if c.is_whitespace() => {
+                    self.bump();
+                }
+                Some('/') if self.chars.clone().nth(1) == Some('/') => {
+                    while !matches!(self.chars.peek(), Some('\n') | None) {
+                        self.bump();
+                    }
+                }
+                _ => break Ok(()),
+            }
+        }
+    }
+
+    /// Reads raw text up to and including the `close` that matches an already consumed `open`.
+    /// String and character literals are skipped, so they may contain unbalanced delimiters.
+    fn delimited(&mut self, open: char, close: char) -> Result<String, SyntaxError> {
+        let mut text = String::new();
+        let mut depth = 1;
+
+        while depth > 0 {
+            let c = match self.bump() {
+                Some(c) => c,
+                None => return self.error(format!("unclosed `{}`", open)),
+            };
+            text.push(c);
+
+            if c == open {
+                depth += 1;
+            } else if c == close {
+                depth -= 1;
+            } else if c == '"' {
+                while let Some(c) = self.bump() {
+                    text.push(c);
+                    if c == '\\' {
+                        text.extend(self.bump());
+                    } else if c == '"' {
+                        break;
+                    }
+                }
+            } else if c == '\'' && open == '{' {
+                // Either a character literal or a lifetime, only the former is closed by another quote.
+                let literal = self.chars.clone().take(3).collect::<String>();
+                if literal.starts_with('\\') || literal.chars().nth(1) == Some('\'') {
+                    while let Some(c) = self.bump() {
+                        text.push(c);
+                        if c == '\\' {
+                            text.extend(self.bump());
+                        } else if c == '\'' {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(text)
+    }
+
+    /// Reads the text up to an unescaped `close`. The escapes of string literals are resolved, while in
+    /// regular expressions only an escaped slash is, leaving the others to the regex.
+    fn quoted(&mut self, close: char) -> Result<String, SyntaxError> {
+        let mut text = String::new();
+
+        loop {
+            match self.bump() {
+                None | Some('\n') => {
+                    return self.error(format!("unclosed `{}`", close));
+                }
+                Some(c) if c == close => break Ok(text),
+                Some('\\') => match (close, self.bump()) {
+                    ('"', Some('n')) => text.push('\n'),
+                    ('"', Some('r')) => text.push('\r'),
+                    ('"', Some('t')) => text.push('\t'),
+                    ('"', Some(c @ '"')) | ('"', Some(c @ '\\')) | (_, Some(c @ '/')) => {
+                        text.push(c)
+                    }
+                    ('"', _) => return self.error("unknown escape in string literal"),
+                    (_, Some(c)) => {
+                        text.push('\\');
+                        text.push(c);
+                    }
+                    (_, None) => return self.error(format!("unclosed `{}`", close)),
+                },
+                Some(c) => text.push(c),
+            }
+        }
+    }
+
+    fn next_lexeme(&mut self) -> Result<Lexeme, SyntaxError> {
+        self.skip_trivia()?;
+        let (line, column) = (self.line, self.column);
+
+        let kind = match self.chars.peek().copied() {
+            None => TokenKind::Eof,
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let mut ident = String::new();
+                while let Some(&c) = self.chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    ident.push(c);
+                    self.bump();
+                }
+                TokenKind::Ident(ident)
+            }
+            Some('<') => {
+                self.bump();
+                let ty = self.delimited('<', '>')?;
+                TokenKind::Type(ty[..ty.len() - 1].trim().to_owned())
+            }
+            Some('{') => {
+                self.bump();
+                TokenKind::Code(format!("{{{}", self.delimited('{', '}')?))
+            }
+            Some('"') => {
+                self.bump();
+                TokenKind::Literal(self.quoted('"')?)
+            }
+            Some('/') => {
+                self.bump();
+                TokenKind::Regex(self.quoted('/')?)
+            }
+            Some(c) => {
+                self.bump();
+                match c {
+                    '=' if self.chars.peek() == Some(&'>') => {
+                        self.bump();
+                        TokenKind::Arrow
+                    }
+                    '=' => TokenKind::Equals,
+                    ':' => TokenKind::Colon,
+                    '|' => TokenKind::Pipe,
+                    ';' => TokenKind::Semicolon,
+                    c => return self.error(format!("unexpected character `{}`", c)),
+                }
+            }
+        };
+
+        Ok(Lexeme { kind, line, column })
+    }
+}
+
+fn is_identifier(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && text.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// A symbol as written in a rule.
+enum Reference {
+    Name(String),
+    Literal(String),
+}
+
+struct Alternative {
+    symbols: Vec<(Option<String>, Reference)>,
+    action: Option<String>,
+    name: Option<String>,
+}
+
+struct ParsedRule {
+    lexeme: Lexeme,
+    lhs: String,
+    ty: Option<String>,
+    alternatives: Vec<Alternative>,
+}
+
+struct Parser<'s> {
+    reader: Reader<'s>,
+    current: Lexeme,
+    definitions: Vec<(Lexeme, TokenDefinition)>,
+}
+
+impl<'s> Parser<'s> {
+    fn new(source: &'s str) -> Result<Self, SyntaxError> {
+        let mut reader = Reader::new(source);
+        let current = reader.next_lexeme()?;
+        Ok(Self {
+            reader,
+            current,
+            definitions: Vec::new(),
+        })
+    }
+
+    fn advance(&mut self) -> Result<Lexeme, SyntaxError> {
+        let next = self.reader.next_lexeme()?;
+        Ok(std::mem::replace(&mut self.current, next))
+    }
+
+    fn error<R>(&self, message: impl Into<String>) -> Result<R, SyntaxError> {
+        Err(SyntaxError {
+            line: self.current.line,
+            column: self.current.column,
+            message: message.into(),
+        })
+    }
+
+    fn expect(&mut self, kind: TokenKind, what: &str) -> Result<Lexeme, SyntaxError> {
+        if self.current.kind == kind {
+            self.advance()
+        } else {
+            self.error(format!("expected {}", what))
+        }
+    }
+
+    fn reference(&mut self) -> Result<Reference, SyntaxError> {
+        match &self.current.kind {
+            TokenKind::Literal(text) => {
+                let text = text.clone();
+                self.advance()?;
+                Ok(Reference::Literal(text))
+            }
+            _ => Ok(Reference::Name(self.ident()?.0)),
+        }
+    }
+
+    fn ident(&mut self) -> Result<(String, Lexeme), SyntaxError> {
+        match &self.current.kind {
+            TokenKind::Ident(name) => {
+                let name = name.clone();
+                Ok((name, self.advance()?))
+            }
+            _ => self.error("expected a symbol name"),
+        }
+    }
+
+    /// Reads `push Mode` or `pop` after the arrow of a token definition.
+    fn mode_action(&mut self) -> Result<ModeAction, SyntaxError> {
+        let (action, _) = self.ident()?;
+        match action.as_str() {
+            "push" => Ok(ModeAction::Push(self.ident()?.0)),
+            "pop" => Ok(ModeAction::Pop),
+            _ => self.error("expected `push` or `pop` after `=>`"),
+        }
+    }
+
+    fn rules(&mut self) -> Result<Vec<ParsedRule>, SyntaxError> {
+        let mut rules = Vec::new();
+
+        while self.current.kind != TokenKind::Eof {
+            // Token definitions can be preceded by the modes they are lexed in.
+            let modes = match &self.current.kind {
+                TokenKind::Type(modes) => {
+                    let modes = modes
+                        .split(',')
+                        .map(|mode| mode.trim().to_owned())
+                        .collect::<Vec<_>>();
+                    if !modes.iter().all(|mode| is_identifier(mode)) {
+                        return self.error("expected mode names separated by `,`");
+                    }
+                    self.advance()?;
+                    Some(modes)
+                }
+                _ => None,
+            };
+            let (lhs, lexeme) = self.ident()?;
+
+            if self.current.kind == TokenKind::Equals {
+                self.advance()?;
+                let pattern = match self.advance()?.kind {
+                    TokenKind::Literal(text) => Pattern::Literal(text),
+                    TokenKind::Regex(regex) => Pattern::Regex(regex),
+                    _ => return self.error("expected a string literal or a regex after `=`"),
+                };
+                let action = if self.current.kind == TokenKind::Arrow {
+                    self.advance()?;
+                    Some(self.mode_action()?)
+                } else {
+                    None
+                };
+                self.expect(TokenKind::Semicolon, "`;` after the token definition")?;
+                self.definitions.push((
+                    lexeme,
+                    TokenDefinition {
+                        name: lhs,
+                        pattern,
+                        modes: modes.unwrap_or_default(),
+                        action,
+                    },
+                ));
+                continue;
+            } else if modes.is_some() {
+                return self.error("expected `=`, only token definitions can have modes");
+            }
+
+            let ty = match &self.current.kind {
+                TokenKind::Type(ty) => {
+                    let ty = ty.clone();
+                    self.advance()?;
+                    Some(ty)
+                }
+                _ => None,
+            };
+            self.expect(TokenKind::Colon, "`:` or `=` after the name")?;
+
+            let new_alternative = || Alternative {
+                symbols: Vec::new(),
+                action: None,
+                name: None,
+            };
+            let mut alternatives = vec![new_alternative()];
+            loop {
+                let alternative = alternatives.last_mut().unwrap();
+
+                match &self.current.kind {
+                    TokenKind::Ident(_) | TokenKind::Literal(_)
+                        if alternative.action.is_some() || alternative.name.is_some() =>
+                    {
+                        return self.error("expected `|` or `;` after the action")
+                    }
+                    TokenKind::Ident(_) | TokenKind::Literal(_) => {
+                        let symbol = self.reference()?;
+
+                        // A name followed by a colon labels the symbol after it.
+                        match symbol {
+                            Reference::Name(label) if self.current.kind == TokenKind::Colon => {
+                                self.advance()?;
+                                let symbol = self.reference()?;
+                                alternative.symbols.push((Some(label), symbol));
+                            }
+                            symbol => alternative.symbols.push((None, symbol)),
+                        }
+                    }
+                    TokenKind::Arrow
+                        if alternative.action.is_none() && alternative.name.is_none() =>
+                    {
+                        self.advance()?;
+                        match self.advance()?.kind {
+                            TokenKind::Code(code) => alternative.action = Some(code),
+                            TokenKind::Ident(name) => alternative.name = Some(name),
+                            _ => {
+                                return self.error(
+                                    "expected an action between braces or a name after `=>`",
+                                )
+                            }
+                        }
+                    }
+                    TokenKind::Pipe => {
+                        self.advance()?;
+                        alternatives.push(new_alternative());
+                    }
+                    TokenKind::Semicolon => {
+                        self.advance()?;
+                        break;
+                    }
+                    _ => return self.error("expected a symbol, `|` or `;`"),
+                }
+            }
+
+            rules.push(ParsedRule {
+                lexeme,
+                lhs,
+                ty,
+                alternatives,
+            });
+        }
+
+        Ok(rules)
+    }
+}
+
+/// Reads a grammar from the contents of a `.yaw` file.
+pub fn parse(source: &str) -> Result<GrammarFile, SyntaxError> {
+    let mut parser = Parser::new(source)?;
+    let parsed = parser.rules()?;
+
+    let error = |lexeme: &Lexeme, message: String| SyntaxError {
+        line: lexeme.line,
+        column: lexeme.column,
+        message,
+    };
+
+    let start = match parsed.first() {
+        Some(rule) => rule,
+        None => return Err(error(&parser.current, "grammar contains no rules".into())),
+    };
+    let start_name = &start.lhs;
+
+    let nonterminals = parsed
+        .iter()
+        .map(|rule| rule.lhs.as_str())
+        .collect::<HashSet<_>>();
+    let eof = Name::eof();
+    let mut rules = Vec::new();
+
+    let mut tokens: Vec<TokenDefinition> = Vec::new();
+    for (lexeme, definition) in parser.definitions {
+        let name = definition.name.as_str();
+
+        if name == eof.as_str() {
+            return Err(error(
+                &lexeme,
+                format!("`{}` is reserved for the end of input", eof),
+            ));
+        } else if name == ERROR {
+            return Err(error(
+                &lexeme,
+                format!("`{}` is reserved for error recovery", ERROR),
+            ));
+        } else if nonterminals.contains(name) {
+            return Err(error(
+                &lexeme,
+                format!("`{}` is both a rule and a token", name),
+            ));
+        } else if tokens.iter().any(|t| t.name == name) {
+            return Err(error(&lexeme, format!("token `{}` is defined twice", name)));
+        }
+        tokens.push(definition);
+    }
+
+    // Literals that are not defined as tokens get a definition named after their text, which must not
+    // be used for anything else.
+    let names = parsed
+        .iter()
+        .flat_map(|rule| &rule.alternatives)
+        .flat_map(|alternative| &alternative.symbols)
+        .filter_map(|(_, symbol)| match symbol {
+            Reference::Name(name) => Some(name.as_str()),
+            Reference::Literal(_) => None,
+        })
+        .chain(tokens.iter().map(|t| t.name.as_str()))
+        .chain(nonterminals.iter().copied())
+        .chain([eof.as_str(), ERROR])
+        .collect::<HashSet<_>>();
+    let mut implicit: Vec<TokenDefinition> = Vec::new();
+
+    let mut types = Vec::new();
+
+    for ParsedRule {
+        lexeme,
+        lhs,
+        ty,
+        alternatives,
+    } in &parsed
+    {
+        if let Some(ty) = ty {
+            if types.iter().any(|(nt, _)| nt == lhs) {
+                return Err(error(
+                    lexeme,
+                    format!("the type of `{}` is declared twice", lhs),
+                ));
+            }
+            types.push((lhs.clone(), ty.clone()));
+        }
+
+        if lhs == eof.as_str() {
+            return Err(error(
+                lexeme,
+                format!("`{}` is reserved for the end of input", eof),
+            ));
+        } else if lhs == ERROR || lhs == "error" {
+            return Err(error(
+                lexeme,
+                format!("`{}` is reserved for error recovery", lhs),
+            ));
+        }
+
+        for alternative in alternatives {
+            let mut symbols = Vec::new();
+            let mut labels = Vec::new();
+
+            for (label, symbol) in &alternative.symbols {
+                labels.push(label.clone());
+
+                let text = match symbol {
+                    Reference::Name(name) if name == "error" || name == ERROR => {
+                        symbols.push(Symbol::Terminal(Name::new(ERROR)));
+                        continue;
+                    }
+                    Reference::Name(name) => name,
+                    Reference::Literal(text) => {
+                        let defined = tokens.iter().chain(&implicit).find(|t| match &t.pattern {
+                            Pattern::Literal(literal) => literal == text,
+                            Pattern::Regex(_) => false,
+                        });
+                        if let Some(definition) = defined {
+                            symbols.push(Symbol::Terminal(Name::new(&definition.name)));
+                            continue;
+                        }
+
+                        let name = match literal_name(text) {
+                            Some(name) => name,
+                            None => {
+                                return Err(error(
+                                    lexeme,
+                                    format!(
+                                        "cannot name the literal {:?}, define it as a token",
+                                        text
+                                    ),
+                                ))
+                            }
+                        };
+                        if names.contains(name.as_str()) || implicit.iter().any(|t| t.name == name)
+                        {
+                            return Err(error(
+                                lexeme,
+                                format!(
+                                    "the literal {:?} would be named `{}`, which is already used, \
+                                     define it as a token",
+                                    text, name
+                                ),
+                            ));
+                        }
+
+                        symbols.push(Symbol::Terminal(Name::new(&name)));
+                        implicit.push(TokenDefinition::new(name, Pattern::Literal(text.clone())));
+                        continue;
+                    }
+                };
+                let name = text;
+
+                if name == eof.as_str() {
+                    return Err(error(
+                        lexeme,
+                        format!("`{}` is reserved for the end of input", eof),
+                    ));
+                } else if name == start_name {
+                    return Err(error(
+                        lexeme,
+                        format!("start symbol `{}` may not be used in a rule", start_name),
+                    ));
+                } else if nonterminals.contains(name.as_str()) {
+                    symbols.push(Symbol::NonTerminal(Name::new(name)));
+                } else {
+                    symbols.push(Symbol::Terminal(Name::new(name)));
+                }
+            }
+
+            let mut rule = Rule::new(Name::new(lhs), symbols).with_labels(labels);
+            if let Some(action) = &alternative.action {
+                rule = rule.with_action(action.clone());
+            }
+            if let Some(name) = &alternative.name {
+                rule = rule.with_name(name.clone());
+            }
+            rules.push(rule);
+        }
+    }
+
+    if start.alternatives.len() != 1 || parsed.iter().filter(|r| &r.lhs == start_name).count() != 1
+    {
+        return Err(error(
+            &start.lexeme,
+            format!(
+                "start rule `{}` must have exactly one alternative",
+                start_name
+            ),
+        ));
+    }
+
+    tokens.extend(implicit);
+
+    let mut grammar = Grammar::new(rules, 0);
+    for (nt, ty) in types {
+        grammar.set_type(Name::new(&nt), ty);
+    }
+
+    Ok(GrammarFile { grammar, tokens })
+}
+
+/// Names a literal by its text: words are capitalized and punctuation is spelled out, so `"else_if"`
+/// becomes `ElseIf` and `"->"` becomes `MinusGt`. Returns `None` for text that cannot be named.
+pub fn literal_name(text: &str) -> Option<String> {
+    let mut name = String::new();
+    let mut capitalize = true;
+
+    for c in text.chars() {
+        let spelled = match c {
+            c if c.is_ascii_alphanumeric() => {
+                name.push(if capitalize {
+                    c.to_ascii_uppercase()
+                } else {
+                    c
+                });
+                capitalize = false;
+                continue;
+            }
+            '_' if !name.is_empty() && !capitalize => {
+                capitalize = true;
+                continue;
+            }
+            '+' => "Plus",
+            '-' => "Minus",
+            '*' => "Star",
+            '/' => "Slash",
+            '%' => "Percent",
+            '=' => "Equals",
+            '<' => "Lt",
+            '>' => "Gt",
+            '!' => "Bang",
+            '&' => "Amp",
+            '|' => "Pipe",
+            '^' => "Caret",
+            '~' => "Tilde",
+            '?' => "Question",
+            '(' => "LParen",
+            ')' => "RParen",
+            '[' => "LBracket",
+            ']' => "RBracket",
+            '{' => "LBrace",
+            '}' => "RBrace",
+            ',' => "Comma",
+            ';' => "Semicolon",
+            ':' => "Colon",
+            '.' => "Dot",
+            '#' => "Hash",
+            '@' => "At",
+            '$' => "Dollar",
+            '\'' => "Quote",
+            '"' => "DoubleQuote",
+            '\\' => "Backslash",
+            '_' => "Underscore",
+            _ => return None,
+        };
+        name.push_str(spelled);
+        capitalize = true;
+    }
+
+    match name.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() => Some(name),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::analysis::Analyser;
+
+    const FUNCTION_CALLS: &str = "
+        // The first rule is the start rule.
+        Start: Expression;
+        Expression: Identifier | FunctionCall;
+        FunctionCall: Expression LParen Arguments RParen | Expression LParen RParen;
+        Arguments: Arguments Comma Expression | Expression;
+    ";
+
+    #[test]
+    fn reads_rules() {
+        let file = parse(FUNCTION_CALLS).unwrap();
+        let grammar = file.grammar();
+
+        assert_eq!(grammar.rules().len(), 7);
+        assert_eq!(grammar.start_rule().lhs(), Name::new("Start"));
+        assert_eq!(
+            grammar.rules()[1].symbols(),
+            &vec![Symbol::Terminal(Name::new("Identifier"))]
+        );
+        assert!(Analyser::table(grammar).conflicts().is_empty());
+    }
+
+    #[test]
+    fn reads_types_labels_and_actions() {
+        let file = parse(
+            "Start<i64>: Sum;
+             Sum<Vec<i64>>: lhs:Sum Plus Num => { { let mut v = lhs; v.push(p(&_3, '}')); v } }
+                 | Num => { vec![\"{\".len() as i64] };",
+        )
+        .unwrap();
+        let grammar = file.grammar();
+        let rule = &grammar.rules()[1];
+
+        assert_eq!(grammar.type_of(Name::new("Sum")), "Vec<i64>");
+        assert_eq!(grammar.type_of(Name::new("Num")), "()");
+        assert_eq!((rule.label(0), rule.label(1)), (Some("lhs"), None));
+        assert_eq!(
+            rule.action(),
+            Some("{ { let mut v = lhs; v.push(p(&_3, '}')); v } }")
+        );
+        assert_eq!(
+            grammar.rules()[2].action(),
+            Some("{ vec![\"{\".len() as i64] }")
+        );
+    }
+
+    #[test]
+    fn reads_alternative_names() {
+        let file = parse("Start: S; S: A => first | B C;").unwrap();
+        let rules = file.grammar().rules();
+
+        assert_eq!(rules[1].name(), Some("first"));
+        assert_eq!(rules[2].name(), None);
+        assert!(parse("Start: S; S: A => first B;").is_err());
+    }
+
+    #[test]
+    fn reads_token_definitions() {
+        let file = parse(
+            r#"Start: Word;
+               Word = /[a-z\/]+/; // A comment.
+               Quote = "\"";"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            file.tokens(),
+            [
+                TokenDefinition::new("Word", Pattern::Regex(r"[a-z/]+".into())),
+                TokenDefinition::new("Quote", Pattern::Literal("\"".into())),
+            ]
+        );
+        assert!(parse("Start: A; A: B; A = \"a\";").is_err());
+        assert!(parse("Start: A; A = \"a\"; A = /a/;").is_err());
+    }
+
+    #[test]
+    fn reads_modes() {
+        let file = parse(
+            r#"Start: Quote Text EndQuote;
+               Quote = "'" => push Text;
+               <Text, Escaped> Text = /[^']+/;
+               <Text> EndQuote = "'" => pop;"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            file.tokens()[0].action,
+            Some(ModeAction::Push("Text".into()))
+        );
+        assert_eq!(file.tokens()[1].modes, ["Text", "Escaped"]);
+        assert_eq!(file.tokens()[2].action, Some(ModeAction::Pop));
+        assert!(parse("Start: A; <Text> A: B;").is_err());
+        assert!(parse("Start: A; A = \"a\" => enter Text;").is_err());
+    }
+
+    #[test]
+    fn reads_error_terminal() {
+        let file = parse("Start: S; S: error | A;").unwrap();
+        assert_eq!(
+            *file.grammar().rules()[1].symbols(),
+            [Symbol::Terminal(Name::new(ERROR))]
+        );
+        assert!(Name::new(ERROR).is_error());
+
+        assert!(parse("Start: S; S: A; Error: B;").is_err());
+        assert!(parse("Start: S; S: A; Error = /e/;").is_err());
+    }
+
+    #[test]
+    fn names_literals() {
+        let file = parse(
+            r#"Start: Statement;
+               Statement: "if" Expression "{" "}" | "let" Identifier ":=" Expression;
+               Expression: Expression "==" Identifier | Identifier;
+               Assign = ":=";"#,
+        )
+        .unwrap();
+        let names = |rule: usize| {
+            file.grammar().rules()[rule]
+                .symbols()
+                .iter()
+                .map(|s| format!("{:?}", s.terminal().map(|t| t.as_str())))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        assert_eq!(names(1), r#"Some("If") None Some("LBrace") Some("RBrace")"#);
+        assert_eq!(
+            names(2),
+            r#"Some("Let") Some("Identifier") Some("Assign") None"#
+        );
+        assert_eq!(file.tokens()[0].name, "Assign");
+        assert_eq!(
+            file.tokens()
+                .iter()
+                .map(|t| t.name.as_str())
+                .collect::<Vec<_>>(),
+            ["Assign", "If", "LBrace", "RBrace", "Let", "EqualsEquals"]
+        );
+
+        assert_eq!(literal_name("else_if"), Some("ElseIf".into()));
+        assert_eq!(literal_name("->"), Some("MinusGt".into()));
+        assert_eq!(literal_name("2"), None);
+        assert!(parse(r#"Start: If "if";"#).is_err());
+    }
+
+    #[test]
+    fn reports_position() {
+        let err = parse("Start: A;\nA: B\n").err().unwrap();
+        assert_eq!((err.line, err.column), (3, 1));
+
+        let err = parse("Start: A | B;").err().unwrap();
+        assert_eq!((err.line, err.column), (1, 1));
+    }
+}
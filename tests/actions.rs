@@ -0,0 +1,207 @@
+use yaw::runtime::{Parse, ParseState, Token};
+
+#[rustfmt::skip]
+#[path = "generated/expressions.rs"]
+mod parser;
+
+use parser::{Actions, Parser, TokenType};
+
+fn tokenize(source: &str) -> Vec<Token<TokenType>> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let kind = match c {
+            '+' => TokenType::Plus,
+            ',' => TokenType::Comma,
+            '(' => TokenType::LParen,
+            ')' => TokenType::RParen,
+            ' ' => continue,
+            '0'..='9' => {
+                while let Some((_, '0'..='9')) = chars.peek() {
+                    chars.next();
+                }
+                TokenType::Number
+            }
+            _ => {
+                while let Some((_, 'a'..='z')) = chars.peek() {
+                    chars.next();
+                }
+                TokenType::Identifier
+            }
+        };
+        let end = chars.peek().map_or(source.len(), |&(i, _)| i);
+        tokens.push(Token {
+            span: (start, end),
+            kind,
+        });
+    }
+
+    tokens.push(Token {
+        span: (source.len(), source.len()),
+        kind: TokenType::Eof,
+    });
+    tokens
+}
+
+struct Evaluator<'s> {
+    source: &'s str,
+    calls: usize,
+}
+
+impl<'s> Evaluator<'s> {
+    fn text(&self, token: Token<TokenType>) -> &'s str {
+        &self.source[token.span.0..token.span.1]
+    }
+}
+
+impl Actions for Evaluator<'_> {
+    type Start = i64;
+    type Expr = i64;
+    type Term = i64;
+    type Args = Vec<i64>;
+
+    fn reduce_start(&mut self, expr: i64) -> i64 {
+        expr
+    }
+
+    fn reduce_add(&mut self, lhs: i64, _plus: Token<TokenType>, rhs: i64) -> i64 {
+        lhs + rhs
+    }
+
+    fn reduce_expr_2(&mut self, term: i64) -> i64 {
+        term
+    }
+
+    fn reduce_number(&mut self, number: Token<TokenType>) -> i64 {
+        self.text(number).parse().unwrap()
+    }
+
+    fn reduce_call(
+        &mut self,
+        function: Token<TokenType>,
+        _l_paren: Token<TokenType>,
+        args: Vec<i64>,
+        _r_paren: Token<TokenType>,
+    ) -> i64 {
+        self.calls += 1;
+        match self.text(function) {
+            "max" => args.into_iter().max().unwrap(),
+            "neg" => -args[0],
+            other => panic!("unknown function {}", other),
+        }
+    }
+
+    fn reduce_group(
+        &mut self,
+        _l_paren: Token<TokenType>,
+        expr: i64,
+        _r_paren: Token<TokenType>,
+    ) -> i64 {
+        expr
+    }
+
+    fn reduce_more_arguments(
+        &mut self,
+        mut args: Vec<i64>,
+        _comma: Token<TokenType>,
+        expr: i64,
+    ) -> Vec<i64> {
+        args.push(expr);
+        args
+    }
+
+    fn reduce_first_argument(&mut self, expr: i64) -> Vec<i64> {
+        vec![expr]
+    }
+}
+
+/// Prints expressions with explicit grouping, sharing the parser with the evaluator.
+struct Printer<'s>(&'s str);
+
+impl Actions for Printer<'_> {
+    type Start = String;
+    type Expr = String;
+    type Term = String;
+    type Args = Vec<String>;
+
+    fn reduce_start(&mut self, expr: String) -> String {
+        expr
+    }
+
+    fn reduce_add(&mut self, lhs: String, _plus: Token<TokenType>, rhs: String) -> String {
+        format!("({} + {})", lhs, rhs)
+    }
+
+    fn reduce_expr_2(&mut self, term: String) -> String {
+        term
+    }
+
+    fn reduce_number(&mut self, number: Token<TokenType>) -> String {
+        self.0[number.span.0..number.span.1].to_owned()
+    }
+
+    fn reduce_call(
+        &mut self,
+        function: Token<TokenType>,
+        _l_paren: Token<TokenType>,
+        args: Vec<String>,
+        _r_paren: Token<TokenType>,
+    ) -> String {
+        format!(
+            "{}[{}]",
+            &self.0[function.span.0..function.span.1],
+            args.join(", ")
+        )
+    }
+
+    fn reduce_group(
+        &mut self,
+        _l_paren: Token<TokenType>,
+        expr: String,
+        _r_paren: Token<TokenType>,
+    ) -> String {
+        expr
+    }
+
+    fn reduce_more_arguments(
+        &mut self,
+        mut args: Vec<String>,
+        _comma: Token<TokenType>,
+        expr: String,
+    ) -> Vec<String> {
+        args.push(expr);
+        args
+    }
+
+    fn reduce_first_argument(&mut self, expr: String) -> Vec<String> {
+        vec![expr]
+    }
+}
+
+const SOURCE: &str = "1 + max(2, neg(3) + 10, 4) + (5)";
+
+#[test]
+fn evaluates_with_an_evaluator() {
+    let evaluator = Evaluator {
+        source: SOURCE,
+        calls: 0,
+    };
+    let mut parser = Parser(ParseState::new(tokenize(SOURCE).into_iter()), evaluator);
+
+    assert_eq!(parser.parse(), Ok(13));
+    assert_eq!(parser.1.calls, 2);
+}
+
+#[test]
+fn prints_with_a_printer() {
+    let mut parser = Parser(
+        ParseState::new(tokenize(SOURCE).into_iter()),
+        Printer(SOURCE),
+    );
+
+    assert_eq!(
+        parser.parse(),
+        Ok("((1 + max[2, (neg[3] + 10), 4]) + 5)".to_owned())
+    );
+}
@@ -0,0 +1,372 @@
+use futures_core::Stream;
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+use yaw::runtime::repair::{Repair, RepairOptions};
+use yaw::runtime::{stream, Feed, Parse, ParseError, ParseState, Token};
+
+#[rustfmt::skip]
+#[path = "generated/calculator.rs"]
+mod parser;
+
+/// The same grammar generated with the table backend.
+#[rustfmt::skip]
+#[path = "generated/calculator_table.rs"]
+mod table;
+
+use parser::{Parser, TokenType};
+
+thread_local! {
+    static SOURCE: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Reads the value of a `Number` token from the input being parsed.
+fn number<T>(token: &Token<T>) -> i64 {
+    SOURCE.with(|s| s.borrow()[token.span.0..token.span.1].parse().unwrap())
+}
+
+fn tokenize(source: &str) -> Vec<Token<TokenType>> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let kind = match c {
+            '+' => TokenType::Plus,
+            '-' => TokenType::Minus,
+            '*' => TokenType::Times,
+            '(' => TokenType::LParen,
+            ')' => TokenType::RParen,
+            ' ' => continue,
+            _ => {
+                while let Some((_, '0'..='9')) = chars.peek() {
+                    chars.next();
+                }
+                TokenType::Number
+            }
+        };
+        let end = chars.peek().map_or(source.len(), |&(i, _)| i);
+        tokens.push(Token {
+            span: (start, end),
+            kind,
+        });
+    }
+
+    tokens.push(Token {
+        span: (source.len(), source.len()),
+        kind: TokenType::Eof,
+    });
+    tokens
+}
+
+fn evaluate(source: &str) -> Result<i64, ParseError<TokenType>> {
+    SOURCE.with(|s| *s.borrow_mut() = source.to_owned());
+    Parser(ParseState::new(tokenize(source).into_iter())).parse()
+}
+
+fn evaluate_table(source: &str) -> Result<i64, ParseError<table::TokenType>> {
+    SOURCE.with(|s| *s.borrow_mut() = source.to_owned());
+    let tokens = tokenize(source).into_iter().map(|token| Token {
+        span: token.span,
+        kind: match token.kind {
+            TokenType::Eof => table::TokenType::Eof,
+            TokenType::LParen => table::TokenType::LParen,
+            TokenType::Minus => table::TokenType::Minus,
+            TokenType::Number => table::TokenType::Number,
+            TokenType::Plus => table::TokenType::Plus,
+            TokenType::RParen => table::TokenType::RParen,
+            TokenType::Times => table::TokenType::Times,
+        },
+    });
+    table::Parser(ParseState::new(tokens)).parse()
+}
+
+fn evaluate_repairing(source: &str) -> (Option<i64>, Vec<ParseError<TokenType>>) {
+    SOURCE.with(|s| *s.borrow_mut() = source.to_owned());
+    let options = RepairOptions {
+        // An inserted number has no text to read its value from.
+        insert_cost: |kind| (*kind != TokenType::Number).then_some(1),
+        ..RepairOptions::default()
+    };
+    Parser(ParseState::new(tokenize(source).into_iter())).parse_repairing(&options)
+}
+
+#[test]
+fn computes_values() {
+    assert_eq!(evaluate("42"), Ok(42));
+    assert_eq!(evaluate("1 + 2 * 3"), Ok(7));
+    assert_eq!(evaluate("(1 + 2) * 3"), Ok(9));
+    assert_eq!(evaluate("10 - 4 - 3"), Ok(3));
+}
+
+#[test]
+fn computes_values_with_the_table_backend() {
+    let inputs = [
+        "42",
+        "1 + 2 * 3",
+        "(1 + 2) * 3",
+        "10 - 4 - 3",
+        "1 + * 2",
+        "(1",
+        "1 2",
+        "",
+    ];
+
+    for input in inputs {
+        match (evaluate(input), evaluate_table(input)) {
+            (Ok(value), Ok(table_value)) => assert_eq!(value, table_value, "{}", input),
+            (Err(error), Err(table_error)) => {
+                // The token types differ, but have the same names.
+                assert_eq!(
+                    format!("{:?}", error.expected),
+                    format!("{:?}", table_error.expected),
+                    "{}",
+                    input
+                );
+                assert_eq!(error.state, table_error.state, "{}", input);
+                assert_eq!(
+                    error.found.map(|t| t.span),
+                    table_error.found.map(|t| t.span),
+                    "{}",
+                    input
+                );
+            }
+            (result, table_result) => panic!("{:?} != {:?}", result, table_result),
+        }
+    }
+    assert_eq!(evaluate_table("(1 + 2) * 3"), Ok(9));
+}
+
+#[test]
+fn reports_unexpected_tokens() {
+    let error = evaluate("1 + * 2").unwrap_err();
+
+    assert_eq!(
+        error.found,
+        Some(Token {
+            span: (4, 5),
+            kind: TokenType::Times
+        })
+    );
+    assert_eq!(error.expected, [TokenType::LParen, TokenType::Number]);
+    assert_eq!(
+        error.to_string(),
+        "expected one of `LParen`, `Number` but found `Times`"
+    );
+    assert_eq!(
+        evaluate("(1").unwrap_err().to_string(),
+        "expected one of `Minus`, `Plus`, `RParen`, `Times` but found end of input"
+    );
+}
+
+#[test]
+fn repairs_errors() {
+    let (value, errors) = evaluate_repairing("(1 + 2");
+    assert_eq!(value, Some(3));
+    assert_eq!(errors[0].repairs, [Repair::Insert(TokenType::RParen)]);
+
+    // An error close to the last one is repaired together with it.
+    let (value, errors) = evaluate_repairing("1 + * 2 (3");
+    assert_eq!(value, Some(3));
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].repairs,
+        [
+            Repair::Delete(Token {
+                span: (4, 5),
+                kind: TokenType::Times
+            }),
+            Repair::Shift(Token {
+                span: (6, 7),
+                kind: TokenType::Number
+            }),
+            Repair::Delete(Token {
+                span: (8, 9),
+                kind: TokenType::LParen
+            }),
+            Repair::Delete(Token {
+                span: (9, 10),
+                kind: TokenType::Number
+            }),
+        ]
+    );
+
+    let (value, errors) = evaluate_repairing("1 + * 2 - 4 - (5 - 3");
+    assert_eq!(value, Some(-3));
+    assert_eq!(errors.len(), 2);
+    assert_eq!(
+        errors[0].to_string(),
+        "expected one of `LParen`, `Number` but found `Times`, repaired with: delete `Times`"
+    );
+    assert_eq!(errors[1].repairs, [Repair::Insert(TokenType::RParen)]);
+}
+
+#[test]
+fn stops_at_errors_without_repair() {
+    let (value, errors) = evaluate_repairing("1 +");
+    assert_eq!(value, None);
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].repairs.is_empty());
+}
+
+#[test]
+fn parses_tokens_as_they_are_fed() {
+    let source = "(1 + 2) * 3 + 4 (";
+    SOURCE.with(|s| *s.borrow_mut() = source.to_owned());
+    let mut tokens = tokenize(source);
+    tokens.pop();
+    let mut parser = Parser(ParseState::new(std::iter::empty()));
+
+    let mut fed = Vec::new();
+    for &token in &tokens[..tokens.len() - 1] {
+        fed.push(parser.feed(token));
+    }
+    assert!(fed.iter().all(|feed| *feed == Feed::NeedMore));
+
+    // The rejected token is discarded, so the input can still be completed.
+    let error = match parser.feed(tokens[tokens.len() - 1]) {
+        Feed::Error(error) => error,
+        feed => panic!("unexpected {:?}", feed),
+    };
+    assert_eq!(error.found, Some(tokens[tokens.len() - 1]));
+    assert_eq!(parser.finish(), Feed::Accepted(13));
+
+    // After an input is accepted, the parser starts over.
+    SOURCE.with(|s| *s.borrow_mut() = "5 -".to_owned());
+    for token in tokenize("5 -").into_iter().take(2) {
+        assert_eq!(parser.feed(token), Feed::NeedMore);
+    }
+    match parser.finish() {
+        Feed::Error(error) => assert_eq!(error.found, None),
+        feed => panic!("unexpected {:?}", feed),
+    }
+}
+
+#[test]
+fn rolls_back_to_checkpoints() {
+    use TokenType::*;
+
+    let source = "1 + (2 3";
+    SOURCE.with(|s| *s.borrow_mut() = source.to_owned());
+    let tokens = tokenize(source);
+    let mut parser = Parser(ParseState::new(std::iter::empty()));
+    parser.feed(tokens[0]);
+    parser.feed(tokens[1]);
+
+    let checkpoint = parser.0.checkpoint();
+    assert_eq!(parser.feed(tokens[2]), Feed::NeedMore);
+    assert_eq!(parser.feed(tokens[3]), Feed::NeedMore);
+    assert!(matches!(parser.finish(), Feed::Error(_)));
+
+    // Nothing is replayed, the parser continues after `1 +` with the next token.
+    parser.0.restore(checkpoint);
+    assert_eq!(parser.feed(tokens[4]), Feed::NeedMore);
+    assert_eq!(parser.finish(), Feed::Accepted(4));
+
+    // Whether tokens would be accepted is answered without changing the parser.
+    parser.feed(tokens[0]);
+    parser.feed(tokens[1]);
+    let states = parser.0.states().to_vec();
+    assert!(parser.accepts(&[LParen, Number]));
+    assert!(parser.accepts(&[Number, Times, Number, Eof]));
+    assert!(!parser.accepts(&[Number, Eof, Number]));
+    assert!(!parser.accepts(&[LParen, Number, Eof]));
+    assert!(!parser.accepts(&[Times]));
+    assert_eq!(parser.0.states(), states);
+}
+
+#[test]
+fn replays_pulled_tokens_after_a_restore() {
+    let source = "2 * (3 + 4) - 1";
+    SOURCE.with(|s| *s.borrow_mut() = source.to_owned());
+    let mut parser = Parser(ParseState::new(tokenize(source).into_iter()));
+    while let Some(token) = parser
+        .current_token()
+        .filter(|t| t.kind != TokenType::LParen)
+    {
+        assert_eq!(parser.action(token), None);
+    }
+
+    // The tokens after the checkpoint are requested from the source only once.
+    let checkpoint = parser.0.checkpoint();
+    assert_eq!(parser.parse(), Ok(13));
+    assert!(parser.0.tokens().as_slice().is_empty());
+    for _ in 0..2 {
+        parser.0.restore(checkpoint.clone());
+        assert_eq!(parser.parse(), Ok(13));
+    }
+}
+
+/// A stream of tokens in memory, which is only ready every other time it is polled, like tokens
+/// arriving over a socket.
+struct Tokens {
+    tokens: std::vec::IntoIter<Token<TokenType>>,
+    ready: bool,
+}
+
+impl Stream for Tokens {
+    type Item = Token<TokenType>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.ready = !self.ready;
+        if self.ready {
+            Poll::Ready(self.tokens.next())
+        } else {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+struct Unpark(Thread);
+
+impl Wake for Unpark {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Runs a future to completion on the current thread.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = Box::pin(future);
+    let waker = Waker::from(Arc::new(Unpark(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => break output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+fn evaluate_stream(
+    source: &str,
+    tokens: Vec<Token<TokenType>>,
+) -> Result<i64, ParseError<TokenType>> {
+    SOURCE.with(|s| *s.borrow_mut() = source.to_owned());
+    let tokens = Tokens {
+        tokens: tokens.into_iter(),
+        ready: false,
+    };
+    block_on(stream::parse(
+        &mut Parser(ParseState::new(std::iter::empty())),
+        tokens,
+    ))
+}
+
+#[test]
+fn parses_tokens_from_a_stream() {
+    let source = "(1 + 2) * 3";
+    assert_eq!(evaluate_stream(source, tokenize(source)), Ok(9));
+
+    let source = "1 + * 2";
+    assert_eq!(evaluate_stream(source, tokenize(source)), evaluate(source));
+
+    // The stream ends before the end of input.
+    let source = "1 +";
+    let mut tokens = tokenize(source);
+    tokens.pop();
+    assert_eq!(evaluate_stream(source, tokens).unwrap_err().found, None);
+}
@@ -0,0 +1,115 @@
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+const GRAMMAR: &str = "Start: E; E: E Plus Num | Num;";
+const AMBIGUOUS: &str = "Start: E; E: E Plus E | Num;";
+
+/// Writes `grammar` to `<name>.yaw` in a temporary directory of this test run.
+fn grammar_file(name: &str, grammar: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("yaw-cli-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(format!("{}.yaw", name));
+    fs::write(&path, grammar).unwrap();
+    path
+}
+
+fn cargo_yaw(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_cargo-yaw"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn json(output: &Output) -> Value {
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn exits_with_failure_on_conflicts() {
+    let clean = grammar_file("clean", GRAMMAR);
+    let output = cargo_yaw(&["check", clean.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let ambiguous = grammar_file("ambiguous", AMBIGUOUS);
+    let output = cargo_yaw(&["check", ambiguous.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("conflict 0: state"), "{}", stdout);
+}
+
+#[test]
+fn exits_with_usage_errors() {
+    let grammar = grammar_file("usage", GRAMMAR);
+    let grammar = grammar.to_str().unwrap();
+    for args in [
+        &["frobnicate", grammar][..],
+        &["check"],
+        &["check", "--depth", "x", grammar],
+        &["check", "--unknown", grammar],
+        &["explain", "--state", "99", grammar],
+        &["dot", "--state", "99", grammar],
+        &[
+            "dot", "--state", "0", "--state", "99", "--depth", "1", grammar,
+        ],
+    ] {
+        assert_eq!(cargo_yaw(args).status.code(), Some(2), "{:?}", args);
+    }
+    let output = cargo_yaw(&["dot", "--state", "99", "--depth", "1", grammar]);
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("error: there is no state 99"));
+
+    let malformed = grammar_file("malformed", "Start: E; E: (");
+    let output = cargo_yaw(&["check", malformed.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .starts_with("error: "));
+
+    let missing = grammar_file("missing", GRAMMAR).with_file_name("absent.yaw");
+    let output = cargo_yaw(&["check", missing.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn prints_json() {
+    let ambiguous = grammar_file("json", AMBIGUOUS);
+    let ambiguous = ambiguous.to_str().unwrap();
+
+    let check = json(&cargo_yaw(&["check", "--json", ambiguous]));
+    assert_eq!(check["rules"], 3);
+    assert!(check["states"].as_u64().unwrap() > 0);
+    assert_eq!(check["overlaps"], Value::Array(Vec::new()));
+    let conflict = &check["conflicts"][0];
+    assert_eq!(conflict["symbol"], "Plus");
+    assert!(conflict["state"].is_u64());
+    assert_eq!(conflict["actions"].as_array().unwrap().len(), 2);
+
+    let states = json(&cargo_yaw(&["states", "--json", ambiguous]));
+    let states = states.as_array().unwrap();
+    assert_eq!(states.len() as u64, check["states"].as_u64().unwrap());
+    assert_eq!(states[0]["state"], 0);
+    assert_eq!(states[0]["kernel"][0], "Start -> . E, Eof");
+    assert!(states[0]["items"].as_array().unwrap().len() > 1);
+    let transition = &states[0]["transitions"][0];
+    assert!(transition["symbol"].is_string() && transition["state"].is_u64());
+}
+
+#[test]
+fn stops_quietly_when_the_output_is_closed() {
+    let grammar = grammar_file("closed", AMBIGUOUS);
+    let mut child = Command::new(env!("CARGO_BIN_EXE_cargo-yaw"))
+        .args(["states", grammar.to_str().unwrap()])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // Closing the only reading end fails every write, like a `head` that has seen enough.
+    drop(child.stdout.take());
+
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stderr.is_empty());
+}
@@ -0,0 +1,58 @@
+use yaw::runtime::lexer::LexError;
+use yaw::runtime::{Parse, ParseError, ParseState};
+
+#[rustfmt::skip]
+#[path = "generated/contextual.rs"]
+mod parser;
+
+use parser::{is_trivia, lex, scan, Parser, TokenType};
+
+fn parse_scanned(source: &str) -> Result<(), ParseError<TokenType>> {
+    Parser(ParseState::new(scan(source)).with_trivia(is_trivia)).parse()
+}
+
+fn parse_lexed(source: &str) -> Result<(), ParseError<TokenType>> {
+    Parser(ParseState::new(lex(source).unwrap().into_iter()).with_trivia(is_trivia)).parse()
+}
+
+#[test]
+fn splits_shifts_in_generic_arguments() {
+    let source = "type Map = HashMap<Key, Vec<Value>>;\nlet x = a >> 2;";
+
+    assert!(parse_lexed(source).is_err());
+    assert_eq!(parse_scanned(source), Ok(()));
+}
+
+#[test]
+fn lexes_keywords_as_identifiers_where_expected() {
+    let source = "let type = 1;\ntype T = type;";
+
+    assert!(parse_lexed(source).is_err());
+    assert_eq!(parse_scanned(source), Ok(()));
+}
+
+#[test]
+fn exposes_the_expected_terminals() {
+    let mut parser = Parser(ParseState::new(scan("let x")).with_trivia(is_trivia));
+
+    assert_eq!(parser.expected(), [TokenType::Let, TokenType::Type]);
+    assert_eq!(
+        parser.parse().unwrap_err().to_string(),
+        "expected `=` but found end of input"
+    );
+    assert_eq!(parser.expected(), [TokenType::Equals]);
+}
+
+#[test]
+fn stops_at_unknown_text() {
+    let mut parser = Parser(ParseState::new(scan("let x = 1 % 2;")).with_trivia(is_trivia));
+
+    assert_eq!(parser.parse().unwrap_err().found, None);
+    assert_eq!(
+        parser.0.tokens().error(),
+        Some(LexError {
+            offset: 10,
+            found: '%'
+        })
+    );
+}
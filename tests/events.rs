@@ -0,0 +1,125 @@
+use yaw::runtime::events::{Event, Recorder};
+use yaw::runtime::{Parse, ParseState, Token};
+
+#[rustfmt::skip]
+#[path = "generated/events.rs"]
+mod parser;
+
+use parser::{is_trivia, NodeKind, Parser, TokenType};
+
+fn tokenize(source: &str) -> Vec<Token<TokenType>> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let kind = match c {
+            ',' => TokenType::Comma,
+            '(' => TokenType::LParen,
+            ')' => TokenType::RParen,
+            ' ' => {
+                while chars.next_if(|&(_, c)| c == ' ').is_some() {}
+                TokenType::Whitespace
+            }
+            _ => {
+                while chars.next_if(|&(_, c)| c.is_ascii_lowercase()).is_some() {}
+                TokenType::Identifier
+            }
+        };
+        let end = chars.peek().map_or(source.len(), |&(i, _)| i);
+        tokens.push(Token {
+            span: (start, end),
+            kind,
+        });
+    }
+
+    tokens.push(Token {
+        span: (source.len(), source.len()),
+        kind: TokenType::Eof,
+    });
+    tokens
+}
+
+fn parse(source: &str) -> Vec<Event<TokenType, NodeKind>> {
+    let state = ParseState::new(tokenize(source).into_iter()).with_trivia(is_trivia);
+    Parser(state, Recorder::new(is_trivia)).parse().unwrap()
+}
+
+/// Renders the events as an indented outline with the text of every token.
+fn outline(source: &str, events: &[Event<TokenType, NodeKind>]) -> String {
+    let mut out = String::new();
+    let mut depth = 0;
+
+    for event in events {
+        match event {
+            Event::StartNode(kind) => {
+                out += &format!("{}{:?}\n", "  ".repeat(depth), kind);
+                depth += 1;
+            }
+            Event::Token(token) => {
+                let text = &source[token.span.0..token.span.1];
+                out += &format!("{}{:?} {:?}\n", "  ".repeat(depth), token.kind, text);
+            }
+            Event::FinishNode => depth -= 1,
+        }
+    }
+
+    assert_eq!(depth, 0);
+    out
+}
+
+#[test]
+fn nests_nodes_in_text_order() {
+    let source = "f(a, b)";
+
+    assert_eq!(
+        outline(source, &parse(source)),
+        "\
+Call
+  Identifier \"f\"
+  LParen \"(\"
+  Args
+    Args
+      Identifier \"a\"
+    Comma \",\"
+    Whitespace \" \"
+    Identifier \"b\"
+  RParen \")\"
+"
+    );
+}
+
+#[test]
+fn keeps_outer_trivia_outside_of_nodes() {
+    let source = " f( a ) ";
+    let events = parse(source);
+
+    assert_eq!(
+        events.first(),
+        Some(&Event::Token(Token {
+            span: (0, 1),
+            kind: TokenType::Whitespace
+        }))
+    );
+    assert_eq!(
+        events.last(),
+        Some(&Event::Token(Token {
+            span: (7, 8),
+            kind: TokenType::Whitespace
+        }))
+    );
+    assert_eq!(
+        outline(source, &events),
+        "\
+Whitespace \" \"
+Call
+  Identifier \"f\"
+  LParen \"(\"
+  Whitespace \" \"
+  Args
+    Identifier \"a\"
+  Whitespace \" \"
+  RParen \")\"
+Whitespace \" \"
+"
+    );
+}
@@ -0,0 +1,119 @@
+use std::path::Path;
+use yaw::build::generate_source;
+use yaw::generator::{Backend, Options};
+use yaw::messages::Messages;
+
+/// Compares a checked in parser with the output of the current generator.
+/// Set YAW_BLESS to update the parser after changing the generator.
+fn check_generated(name: &str, options: &Options) {
+    check_generated_from(name, name, options);
+}
+
+/// Like [`check_generated`], for a parser named differently from its grammar.
+fn check_generated_from(grammar: &str, name: &str, options: &Options) {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/generated");
+    let messages = Path::new(grammar).with_extension("messages");
+    let grammar = format!("{}.yaw", grammar);
+    let source = std::fs::read_to_string(dir.join(&grammar)).unwrap();
+    // The messages in the options are read from the file next to the grammar.
+    let messages_source = options
+        .messages
+        .as_ref()
+        .map(|_| std::fs::read_to_string(dir.join(&messages)).unwrap());
+    let messages = messages_source.as_deref().zip(Some(messages.as_path()));
+    let generated = generate_source(&source, Path::new(&grammar), messages, options).unwrap();
+    let path = dir.join(format!("{}.rs", name));
+
+    if std::env::var_os("YAW_BLESS").is_some() {
+        std::fs::write(&path, &generated).unwrap();
+    }
+
+    assert_eq!(std::fs::read_to_string(path).unwrap(), generated);
+}
+
+#[test]
+fn calculator_is_current() {
+    let options = Options {
+        checkpoints: true,
+        ..Options::default()
+    };
+    check_generated("calculator", &options);
+}
+
+#[test]
+fn calculator_table_is_current() {
+    let options = Options {
+        backend: Backend::Table,
+        ..Options::default()
+    };
+    check_generated_from("calculator", "calculator_table", &options);
+}
+
+#[test]
+fn expressions_is_current() {
+    let options = Options {
+        actions_trait: Some("Actions".into()),
+        ..Options::default()
+    };
+    check_generated("expressions", &options);
+}
+
+#[test]
+fn tree_is_current() {
+    let options = Options {
+        ast: true,
+        ..Options::default()
+    };
+    check_generated("tree", &options);
+}
+
+#[test]
+fn lossless_is_current() {
+    let options = Options {
+        cst: true,
+        trivia: vec!["Whitespace".into(), "Comment".into()],
+        ..Options::default()
+    };
+    check_generated("lossless", &options);
+}
+
+#[test]
+fn events_is_current() {
+    let options = Options {
+        events: true,
+        trivia: vec!["Whitespace".into()],
+        ..Options::default()
+    };
+    check_generated("events", &options);
+}
+
+#[test]
+fn lexed_is_current() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/generated");
+    let messages = std::fs::read_to_string(dir.join("lexed.messages")).unwrap();
+    let options = Options {
+        messages: Some(Messages::parse(&messages).unwrap()),
+        ..Options::default()
+    };
+    check_generated("lexed", &options);
+}
+
+#[test]
+fn contextual_is_current() {
+    check_generated("contextual", &Options::default());
+}
+
+#[test]
+fn modes_is_current() {
+    check_generated("modes", &Options::default());
+}
+
+#[test]
+fn indented_is_current() {
+    check_generated("indented", &Options::default());
+}
+
+#[test]
+fn recovery_is_current() {
+    check_generated("recovery", &Options::default());
+}
@@ -0,0 +1,291 @@
+// @generated by yaw 0.1.0, do not edit.
+// yaw-hash: 2247c7e5ee65401e
+pub const GRAMMAR_HASH: u64 = 0x2247c7e5ee65401e;
+const _: () = yaw::build::assert_fresh(GRAMMAR_HASH, include_str!("calculator.yaw"));
+
+use yaw::runtime::{Parse, ParseState, Step, Token, TokenSource};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TokenType {
+    Eof,
+    LParen,
+    Minus,
+    Number,
+    Plus,
+    RParen,
+    Times,
+}
+
+impl std::fmt::Display for TokenType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            TokenType::Eof => "Eof",
+            TokenType::LParen => "LParen",
+            TokenType::Minus => "Minus",
+            TokenType::Number => "Number",
+            TokenType::Plus => "Plus",
+            TokenType::RParen => "RParen",
+            TokenType::Times => "Times",
+        })
+    }
+}
+
+#[derive(Clone)]
+pub enum Value {
+    Terminal(Token<TokenType>),
+    Start(i64),
+    Expr(i64),
+    Term(i64),
+    Factor(i64),
+}
+
+pub struct Parser<I = std::vec::IntoIter<Token<TokenType>>>(pub ParseState<I, TokenType, Value>);
+
+const EXPECTED: [&[TokenType]; 26] = [
+    &[TokenType::LParen, TokenType::Number],
+    &[TokenType::Eof, TokenType::Minus, TokenType::Plus],
+    &[TokenType::Eof, TokenType::Minus, TokenType::Plus, TokenType::Times],
+    &[TokenType::Eof, TokenType::Minus, TokenType::Plus, TokenType::Times],
+    &[TokenType::Eof, TokenType::Minus, TokenType::Plus, TokenType::Times],
+    &[TokenType::LParen, TokenType::Number],
+    &[TokenType::LParen, TokenType::Number],
+    &[TokenType::LParen, TokenType::Number],
+    &[TokenType::LParen, TokenType::Number],
+    &[TokenType::Minus, TokenType::Plus, TokenType::RParen],
+    &[TokenType::Minus, TokenType::Plus, TokenType::RParen, TokenType::Times],
+    &[TokenType::Minus, TokenType::Plus, TokenType::RParen, TokenType::Times],
+    &[TokenType::Minus, TokenType::Plus, TokenType::RParen, TokenType::Times],
+    &[TokenType::LParen, TokenType::Number],
+    &[TokenType::Eof, TokenType::Minus, TokenType::Plus, TokenType::Times],
+    &[TokenType::Eof, TokenType::Minus, TokenType::Plus, TokenType::Times],
+    &[TokenType::Eof, TokenType::Minus, TokenType::Plus, TokenType::Times],
+    &[TokenType::LParen, TokenType::Number],
+    &[TokenType::LParen, TokenType::Number],
+    &[TokenType::Eof, TokenType::Minus, TokenType::Plus, TokenType::Times],
+    &[TokenType::LParen, TokenType::Number],
+    &[TokenType::Minus, TokenType::Plus, TokenType::RParen],
+    &[TokenType::Minus, TokenType::Plus, TokenType::RParen, TokenType::Times],
+    &[TokenType::Minus, TokenType::Plus, TokenType::RParen, TokenType::Times],
+    &[TokenType::Minus, TokenType::Plus, TokenType::RParen, TokenType::Times],
+    &[TokenType::Minus, TokenType::Plus, TokenType::RParen, TokenType::Times],
+];
+
+impl<I: TokenSource<TokenType>> Parse<I, TokenType> for Parser<I> {
+    type Value = Value;
+    type Output = i64;
+
+    fn parse_state_mut(&mut self) -> &mut ParseState<I, TokenType, Value> {
+        &mut self.0
+    }
+
+    fn parse_state(&self) -> &ParseState<I, TokenType, Value> {
+        &self.0
+    }
+
+    fn expected_terminals(&self, state: usize) -> &'static [TokenType] {
+        EXPECTED[state]
+    }
+
+    fn eof(&self) -> TokenType {
+        TokenType::Eof
+    }
+
+    fn error_terminal(&self) -> Option<TokenType> {
+        None
+    }
+
+    fn step(&self, state: usize, kind: TokenType) -> Option<Step> {
+        match (state, kind) {
+        (0, TokenType::LParen) => Some(Step::Shift(5)),
+        (0, TokenType::Number) => Some(Step::Shift(4)),
+        (1, TokenType::Eof) => Some(Step::Accept(0, 1)),
+        (1, TokenType::Minus) => Some(Step::Shift(7)),
+        (1, TokenType::Plus) => Some(Step::Shift(6)),
+        (2, TokenType::Eof) => Some(Step::Reduce(3, 1)),
+        (2, TokenType::Minus) => Some(Step::Reduce(3, 1)),
+        (2, TokenType::Plus) => Some(Step::Reduce(3, 1)),
+        (2, TokenType::Times) => Some(Step::Shift(8)),
+        (3, TokenType::Eof) => Some(Step::Reduce(5, 1)),
+        (3, TokenType::Minus) => Some(Step::Reduce(5, 1)),
+        (3, TokenType::Plus) => Some(Step::Reduce(5, 1)),
+        (3, TokenType::Times) => Some(Step::Reduce(5, 1)),
+        (4, TokenType::Eof) => Some(Step::Reduce(6, 1)),
+        (4, TokenType::Minus) => Some(Step::Reduce(6, 1)),
+        (4, TokenType::Plus) => Some(Step::Reduce(6, 1)),
+        (4, TokenType::Times) => Some(Step::Reduce(6, 1)),
+        (5, TokenType::LParen) => Some(Step::Shift(13)),
+        (5, TokenType::Number) => Some(Step::Shift(12)),
+        (6, TokenType::LParen) => Some(Step::Shift(5)),
+        (6, TokenType::Number) => Some(Step::Shift(4)),
+        (7, TokenType::LParen) => Some(Step::Shift(5)),
+        (7, TokenType::Number) => Some(Step::Shift(4)),
+        (8, TokenType::LParen) => Some(Step::Shift(5)),
+        (8, TokenType::Number) => Some(Step::Shift(4)),
+        (9, TokenType::Minus) => Some(Step::Shift(18)),
+        (9, TokenType::Plus) => Some(Step::Shift(17)),
+        (9, TokenType::RParen) => Some(Step::Shift(19)),
+        (10, TokenType::Minus) => Some(Step::Reduce(3, 1)),
+        (10, TokenType::Plus) => Some(Step::Reduce(3, 1)),
+        (10, TokenType::RParen) => Some(Step::Reduce(3, 1)),
+        (10, TokenType::Times) => Some(Step::Shift(20)),
+        (11, TokenType::Minus) => Some(Step::Reduce(5, 1)),
+        (11, TokenType::Plus) => Some(Step::Reduce(5, 1)),
+        (11, TokenType::RParen) => Some(Step::Reduce(5, 1)),
+        (11, TokenType::Times) => Some(Step::Reduce(5, 1)),
+        (12, TokenType::Minus) => Some(Step::Reduce(6, 1)),
+        (12, TokenType::Plus) => Some(Step::Reduce(6, 1)),
+        (12, TokenType::RParen) => Some(Step::Reduce(6, 1)),
+        (12, TokenType::Times) => Some(Step::Reduce(6, 1)),
+        (13, TokenType::LParen) => Some(Step::Shift(13)),
+        (13, TokenType::Number) => Some(Step::Shift(12)),
+        (14, TokenType::Eof) => Some(Step::Reduce(1, 3)),
+        (14, TokenType::Minus) => Some(Step::Reduce(1, 3)),
+        (14, TokenType::Plus) => Some(Step::Reduce(1, 3)),
+        (14, TokenType::Times) => Some(Step::Shift(8)),
+        (15, TokenType::Eof) => Some(Step::Reduce(2, 3)),
+        (15, TokenType::Minus) => Some(Step::Reduce(2, 3)),
+        (15, TokenType::Plus) => Some(Step::Reduce(2, 3)),
+        (15, TokenType::Times) => Some(Step::Shift(8)),
+        (16, TokenType::Eof) => Some(Step::Reduce(4, 3)),
+        (16, TokenType::Minus) => Some(Step::Reduce(4, 3)),
+        (16, TokenType::Plus) => Some(Step::Reduce(4, 3)),
+        (16, TokenType::Times) => Some(Step::Reduce(4, 3)),
+        (17, TokenType::LParen) => Some(Step::Shift(13)),
+        (17, TokenType::Number) => Some(Step::Shift(12)),
+        (18, TokenType::LParen) => Some(Step::Shift(13)),
+        (18, TokenType::Number) => Some(Step::Shift(12)),
+        (19, TokenType::Eof) => Some(Step::Reduce(7, 3)),
+        (19, TokenType::Minus) => Some(Step::Reduce(7, 3)),
+        (19, TokenType::Plus) => Some(Step::Reduce(7, 3)),
+        (19, TokenType::Times) => Some(Step::Reduce(7, 3)),
+        (20, TokenType::LParen) => Some(Step::Shift(13)),
+        (20, TokenType::Number) => Some(Step::Shift(12)),
+        (21, TokenType::Minus) => Some(Step::Shift(18)),
+        (21, TokenType::Plus) => Some(Step::Shift(17)),
+        (21, TokenType::RParen) => Some(Step::Shift(25)),
+        (22, TokenType::Minus) => Some(Step::Reduce(1, 3)),
+        (22, TokenType::Plus) => Some(Step::Reduce(1, 3)),
+        (22, TokenType::RParen) => Some(Step::Reduce(1, 3)),
+        (22, TokenType::Times) => Some(Step::Shift(20)),
+        (23, TokenType::Minus) => Some(Step::Reduce(2, 3)),
+        (23, TokenType::Plus) => Some(Step::Reduce(2, 3)),
+        (23, TokenType::RParen) => Some(Step::Reduce(2, 3)),
+        (23, TokenType::Times) => Some(Step::Shift(20)),
+        (24, TokenType::Minus) => Some(Step::Reduce(4, 3)),
+        (24, TokenType::Plus) => Some(Step::Reduce(4, 3)),
+        (24, TokenType::RParen) => Some(Step::Reduce(4, 3)),
+        (24, TokenType::Times) => Some(Step::Reduce(4, 3)),
+        (25, TokenType::Minus) => Some(Step::Reduce(7, 3)),
+        (25, TokenType::Plus) => Some(Step::Reduce(7, 3)),
+        (25, TokenType::RParen) => Some(Step::Reduce(7, 3)),
+        (25, TokenType::Times) => Some(Step::Reduce(7, 3)),
+        _ => None,
+        }
+    }
+
+    fn goto_state(&self, state: usize, rule: usize) -> usize {
+        match (rule, state) {
+        (1, 0) => 1,
+        (1, 5) => 9,
+        (1, 13) => 21,
+        (2, 0) => 1,
+        (2, 5) => 9,
+        (2, 13) => 21,
+        (3, 0) => 1,
+        (3, 5) => 9,
+        (3, 13) => 21,
+        (4, 0) => 2,
+        (4, 5) => 10,
+        (4, 6) => 14,
+        (4, 7) => 15,
+        (4, 13) => 10,
+        (4, 17) => 22,
+        (4, 18) => 23,
+        (5, 0) => 2,
+        (5, 5) => 10,
+        (5, 6) => 14,
+        (5, 7) => 15,
+        (5, 13) => 10,
+        (5, 17) => 22,
+        (5, 18) => 23,
+        (6, 0) => 3,
+        (6, 5) => 11,
+        (6, 6) => 3,
+        (6, 7) => 3,
+        (6, 8) => 16,
+        (6, 13) => 11,
+        (6, 17) => 11,
+        (6, 18) => 11,
+        (6, 20) => 24,
+        (7, 0) => 3,
+        (7, 5) => 11,
+        (7, 6) => 3,
+        (7, 7) => 3,
+        (7, 8) => 16,
+        (7, 13) => 11,
+        (7, 17) => 11,
+        (7, 18) => 11,
+        (7, 20) => 24,
+        _ => panic!("unkown goto"),
+        }
+    }
+
+    fn token_value(&mut self, token: Token<TokenType>) -> Value {
+        Value::Terminal(token)
+    }
+
+    #[allow(unused_variables, unused_braces, clippy::just_underscores_and_digits, clippy::let_unit_value)]
+    fn reduce_values(&mut self, rule: usize, values: Vec<Value>) -> Value {
+        let mut values = values.into_iter();
+        match rule {
+        0 => {
+        let _1 = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        Value::Start(_1)
+        }
+        1 => {
+        let lhs = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let rhs = match values.next() { Some(Value::Term(v)) => v, _ => unreachable!() };
+        Value::Expr({ lhs + rhs })
+        }
+        2 => {
+        let lhs = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let rhs = match values.next() { Some(Value::Term(v)) => v, _ => unreachable!() };
+        Value::Expr({ lhs - rhs })
+        }
+        3 => {
+        let _1 = match values.next() { Some(Value::Term(v)) => v, _ => unreachable!() };
+        Value::Expr(_1)
+        }
+        4 => {
+        let lhs = match values.next() { Some(Value::Term(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let rhs = match values.next() { Some(Value::Factor(v)) => v, _ => unreachable!() };
+        Value::Term({ lhs * rhs })
+        }
+        5 => {
+        let _1 = match values.next() { Some(Value::Factor(v)) => v, _ => unreachable!() };
+        Value::Term(_1)
+        }
+        6 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Factor({ super::number(&_1) })
+        }
+        7 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Factor({ _2 })
+        }
+        _ => unreachable!(),
+        }
+    }
+
+    fn output(&mut self, value: Value) -> i64 {
+        match value {
+        Value::Start(v) => v,
+        _ => unreachable!(),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,184 @@
+// @generated by yaw 0.1.0, do not edit.
+// yaw-hash: 2247c7e5ee65401e
+pub const GRAMMAR_HASH: u64 = 0x2247c7e5ee65401e;
+const _: () = yaw::build::assert_fresh(GRAMMAR_HASH, include_str!("calculator.yaw"));
+
+use yaw::runtime::{Parse, ParseState, Step, Token, TokenSource};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TokenType {
+    Eof,
+    LParen,
+    Minus,
+    Number,
+    Plus,
+    RParen,
+    Times,
+}
+
+impl std::fmt::Display for TokenType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            TokenType::Eof => "Eof",
+            TokenType::LParen => "LParen",
+            TokenType::Minus => "Minus",
+            TokenType::Number => "Number",
+            TokenType::Plus => "Plus",
+            TokenType::RParen => "RParen",
+            TokenType::Times => "Times",
+        })
+    }
+}
+
+pub enum Value {
+    Terminal(Token<TokenType>),
+    Start(i64),
+    Expr(i64),
+    Term(i64),
+    Factor(i64),
+}
+
+pub struct Parser<I = std::vec::IntoIter<Token<TokenType>>>(pub ParseState<I, TokenType, Value>);
+
+const EXPECTED: [&[TokenType]; 26] = [
+    &[TokenType::LParen, TokenType::Number],
+    &[TokenType::Eof, TokenType::Minus, TokenType::Plus],
+    &[TokenType::Eof, TokenType::Minus, TokenType::Plus, TokenType::Times],
+    &[TokenType::Eof, TokenType::Minus, TokenType::Plus, TokenType::Times],
+    &[TokenType::Eof, TokenType::Minus, TokenType::Plus, TokenType::Times],
+    &[TokenType::LParen, TokenType::Number],
+    &[TokenType::LParen, TokenType::Number],
+    &[TokenType::LParen, TokenType::Number],
+    &[TokenType::LParen, TokenType::Number],
+    &[TokenType::Minus, TokenType::Plus, TokenType::RParen],
+    &[TokenType::Minus, TokenType::Plus, TokenType::RParen, TokenType::Times],
+    &[TokenType::Minus, TokenType::Plus, TokenType::RParen, TokenType::Times],
+    &[TokenType::Minus, TokenType::Plus, TokenType::RParen, TokenType::Times],
+    &[TokenType::LParen, TokenType::Number],
+    &[TokenType::Eof, TokenType::Minus, TokenType::Plus, TokenType::Times],
+    &[TokenType::Eof, TokenType::Minus, TokenType::Plus, TokenType::Times],
+    &[TokenType::Eof, TokenType::Minus, TokenType::Plus, TokenType::Times],
+    &[TokenType::LParen, TokenType::Number],
+    &[TokenType::LParen, TokenType::Number],
+    &[TokenType::Eof, TokenType::Minus, TokenType::Plus, TokenType::Times],
+    &[TokenType::LParen, TokenType::Number],
+    &[TokenType::Minus, TokenType::Plus, TokenType::RParen],
+    &[TokenType::Minus, TokenType::Plus, TokenType::RParen, TokenType::Times],
+    &[TokenType::Minus, TokenType::Plus, TokenType::RParen, TokenType::Times],
+    &[TokenType::Minus, TokenType::Plus, TokenType::RParen, TokenType::Times],
+    &[TokenType::Minus, TokenType::Plus, TokenType::RParen, TokenType::Times],
+];
+
+const ACTIONS: [[(u8, usize); 7]; 26] = [[(0, 0), (1, 5), (0, 0), (1, 4), (0, 0), (0, 0), (0, 0)], [(3, 0), (0, 0), (1, 7), (0, 0), (1, 6), (0, 0), (0, 0)], [(2, 3), (0, 0), (2, 3), (0, 0), (2, 3), (0, 0), (1, 8)], [(2, 5), (0, 0), (2, 5), (0, 0), (2, 5), (0, 0), (2, 5)], [(2, 6), (0, 0), (2, 6), (0, 0), (2, 6), (0, 0), (2, 6)], [(0, 0), (1, 13), (0, 0), (1, 12), (0, 0), (0, 0), (0, 0)], [(0, 0), (1, 5), (0, 0), (1, 4), (0, 0), (0, 0), (0, 0)], [(0, 0), (1, 5), (0, 0), (1, 4), (0, 0), (0, 0), (0, 0)], [(0, 0), (1, 5), (0, 0), (1, 4), (0, 0), (0, 0), (0, 0)], [(0, 0), (0, 0), (1, 18), (0, 0), (1, 17), (1, 19), (0, 0)], [(0, 0), (0, 0), (2, 3), (0, 0), (2, 3), (2, 3), (1, 20)], [(0, 0), (0, 0), (2, 5), (0, 0), (2, 5), (2, 5), (2, 5)], [(0, 0), (0, 0), (2, 6), (0, 0), (2, 6), (2, 6), (2, 6)], [(0, 0), (1, 13), (0, 0), (1, 12), (0, 0), (0, 0), (0, 0)], [(2, 1), (0, 0), (2, 1), (0, 0), (2, 1), (0, 0), (1, 8)], [(2, 2), (0, 0), (2, 2), (0, 0), (2, 2), (0, 0), (1, 8)], [(2, 4), (0, 0), (2, 4), (0, 0), (2, 4), (0, 0), (2, 4)], [(0, 0), (1, 13), (0, 0), (1, 12), (0, 0), (0, 0), (0, 0)], [(0, 0), (1, 13), (0, 0), (1, 12), (0, 0), (0, 0), (0, 0)], [(2, 7), (0, 0), (2, 7), (0, 0), (2, 7), (0, 0), (2, 7)], [(0, 0), (1, 13), (0, 0), (1, 12), (0, 0), (0, 0), (0, 0)], [(0, 0), (0, 0), (1, 18), (0, 0), (1, 17), (1, 25), (0, 0)], [(0, 0), (0, 0), (2, 1), (0, 0), (2, 1), (2, 1), (1, 20)], [(0, 0), (0, 0), (2, 2), (0, 0), (2, 2), (2, 2), (1, 20)], [(0, 0), (0, 0), (2, 4), (0, 0), (2, 4), (2, 4), (2, 4)], [(0, 0), (0, 0), (2, 7), (0, 0), (2, 7), (2, 7), (2, 7)]];
+
+const GOTOS: [[usize; 8]; 26] = [[0, 1, 1, 1, 2, 2, 3, 3], [0, 0, 0, 0, 0, 0, 0, 0], [0, 0, 0, 0, 0, 0, 0, 0], [0, 0, 0, 0, 0, 0, 0, 0], [0, 0, 0, 0, 0, 0, 0, 0], [0, 9, 9, 9, 10, 10, 11, 11], [0, 0, 0, 0, 14, 14, 3, 3], [0, 0, 0, 0, 15, 15, 3, 3], [0, 0, 0, 0, 0, 0, 16, 16], [0, 0, 0, 0, 0, 0, 0, 0], [0, 0, 0, 0, 0, 0, 0, 0], [0, 0, 0, 0, 0, 0, 0, 0], [0, 0, 0, 0, 0, 0, 0, 0], [0, 21, 21, 21, 10, 10, 11, 11], [0, 0, 0, 0, 0, 0, 0, 0], [0, 0, 0, 0, 0, 0, 0, 0], [0, 0, 0, 0, 0, 0, 0, 0], [0, 0, 0, 0, 22, 22, 11, 11], [0, 0, 0, 0, 23, 23, 11, 11], [0, 0, 0, 0, 0, 0, 0, 0], [0, 0, 0, 0, 0, 0, 24, 24], [0, 0, 0, 0, 0, 0, 0, 0], [0, 0, 0, 0, 0, 0, 0, 0], [0, 0, 0, 0, 0, 0, 0, 0], [0, 0, 0, 0, 0, 0, 0, 0], [0, 0, 0, 0, 0, 0, 0, 0]];
+
+const RULE_LENGTHS: [usize; 8] = [1, 3, 3, 1, 3, 1, 1, 3];
+
+impl<I: TokenSource<TokenType>> Parse<I, TokenType> for Parser<I> {
+    type Value = Value;
+    type Output = i64;
+
+    fn parse_state_mut(&mut self) -> &mut ParseState<I, TokenType, Value> {
+        &mut self.0
+    }
+
+    fn parse_state(&self) -> &ParseState<I, TokenType, Value> {
+        &self.0
+    }
+
+    fn expected_terminals(&self, state: usize) -> &'static [TokenType] {
+        EXPECTED[state]
+    }
+
+    fn eof(&self) -> TokenType {
+        TokenType::Eof
+    }
+
+    fn error_terminal(&self) -> Option<TokenType> {
+        None
+    }
+
+    fn step(&self, state: usize, kind: TokenType) -> Option<Step> {
+        let column = match kind {
+        TokenType::Eof => 0,
+        TokenType::LParen => 1,
+        TokenType::Minus => 2,
+        TokenType::Number => 3,
+        TokenType::Plus => 4,
+        TokenType::RParen => 5,
+        TokenType::Times => 6,
+        #[allow(unreachable_patterns)]
+        _ => return None,
+        };
+        match ACTIONS[state][column] {
+        (1, state) => Some(Step::Shift(state)),
+        (2, rule) => Some(Step::Reduce(rule, RULE_LENGTHS[rule])),
+        (3, rule) => Some(Step::Accept(rule, RULE_LENGTHS[rule])),
+        _ => None,
+        }
+    }
+
+    fn goto_state(&self, state: usize, rule: usize) -> usize {
+        GOTOS[state][rule]
+    }
+
+    fn token_value(&mut self, token: Token<TokenType>) -> Value {
+        Value::Terminal(token)
+    }
+
+    #[allow(unused_variables, unused_braces, clippy::just_underscores_and_digits, clippy::let_unit_value)]
+    fn reduce_values(&mut self, rule: usize, values: Vec<Value>) -> Value {
+        let mut values = values.into_iter();
+        match rule {
+        0 => {
+        let _1 = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        Value::Start(_1)
+        }
+        1 => {
+        let lhs = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let rhs = match values.next() { Some(Value::Term(v)) => v, _ => unreachable!() };
+        Value::Expr({ lhs + rhs })
+        }
+        2 => {
+        let lhs = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let rhs = match values.next() { Some(Value::Term(v)) => v, _ => unreachable!() };
+        Value::Expr({ lhs - rhs })
+        }
+        3 => {
+        let _1 = match values.next() { Some(Value::Term(v)) => v, _ => unreachable!() };
+        Value::Expr(_1)
+        }
+        4 => {
+        let lhs = match values.next() { Some(Value::Term(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let rhs = match values.next() { Some(Value::Factor(v)) => v, _ => unreachable!() };
+        Value::Term({ lhs * rhs })
+        }
+        5 => {
+        let _1 = match values.next() { Some(Value::Factor(v)) => v, _ => unreachable!() };
+        Value::Term(_1)
+        }
+        6 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Factor({ super::number(&_1) })
+        }
+        7 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Factor({ _2 })
+        }
+        _ => unreachable!(),
+        }
+    }
+
+    fn output(&mut self, value: Value) -> i64 {
+        match value {
+        Value::Start(v) => v,
+        _ => unreachable!(),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,373 @@
+// @generated by yaw 0.1.0, do not edit.
+// yaw-hash: 666fc31029532743
+pub const GRAMMAR_HASH: u64 = 0x666fc31029532743;
+const _: () = yaw::build::assert_fresh(GRAMMAR_HASH, include_str!("contextual.yaw"));
+
+use yaw::runtime::{Parse, ParseState, Step, Token, TokenSource};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TokenType {
+    Comma,
+    Eof,
+    Equals,
+    Gt,
+    GtGt,
+    Identifier,
+    Let,
+    Lt,
+    Number,
+    Semicolon,
+    Type,
+    Whitespace,
+}
+
+impl std::fmt::Display for TokenType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            TokenType::Comma => ",",
+            TokenType::Eof => "Eof",
+            TokenType::Equals => "=",
+            TokenType::Gt => ">",
+            TokenType::GtGt => ">>",
+            TokenType::Identifier => "Identifier",
+            TokenType::Let => "let",
+            TokenType::Lt => "<",
+            TokenType::Number => "Number",
+            TokenType::Semicolon => ";",
+            TokenType::Type => "type",
+            TokenType::Whitespace => "Whitespace",
+        })
+    }
+}
+
+pub fn is_trivia(kind: &TokenType) -> bool {
+    matches!(kind, TokenType::Whitespace)
+}
+
+pub enum Value {
+    Terminal(Token<TokenType>),
+    Program(()),
+    Items(()),
+    Item(()),
+    TypeExpr(()),
+    TypeExprs(()),
+    Expr(()),
+    Atom(()),
+}
+
+pub struct Parser<I = std::vec::IntoIter<Token<TokenType>>>(pub ParseState<I, TokenType, Value>);
+
+const EXPECTED: [&[TokenType]; 32] = [
+    &[TokenType::Let, TokenType::Type],
+    &[TokenType::Eof, TokenType::Let, TokenType::Type],
+    &[TokenType::Eof, TokenType::Let, TokenType::Type],
+    &[TokenType::Identifier],
+    &[TokenType::Identifier],
+    &[TokenType::Eof, TokenType::Let, TokenType::Type],
+    &[TokenType::Equals],
+    &[TokenType::Equals],
+    &[TokenType::Identifier],
+    &[TokenType::Identifier, TokenType::Number],
+    &[TokenType::Semicolon],
+    &[TokenType::Lt, TokenType::Semicolon],
+    &[TokenType::Gt, TokenType::GtGt, TokenType::Semicolon],
+    &[TokenType::Gt, TokenType::GtGt, TokenType::Semicolon],
+    &[TokenType::Gt, TokenType::GtGt, TokenType::Semicolon],
+    &[TokenType::Gt, TokenType::GtGt, TokenType::Semicolon],
+    &[TokenType::Eof, TokenType::Let, TokenType::Type],
+    &[TokenType::Identifier],
+    &[TokenType::Eof, TokenType::Let, TokenType::Type],
+    &[TokenType::Identifier, TokenType::Number],
+    &[TokenType::Identifier, TokenType::Number],
+    &[TokenType::Comma, TokenType::Gt, TokenType::Lt],
+    &[TokenType::Comma, TokenType::Gt],
+    &[TokenType::Comma, TokenType::Gt],
+    &[TokenType::Gt, TokenType::GtGt, TokenType::Semicolon],
+    &[TokenType::Gt, TokenType::GtGt, TokenType::Semicolon],
+    &[TokenType::Identifier],
+    &[TokenType::Semicolon],
+    &[TokenType::Identifier],
+    &[TokenType::Comma, TokenType::Gt],
+    &[TokenType::Comma, TokenType::Gt],
+    &[TokenType::Comma, TokenType::Gt],
+];
+
+impl<I: TokenSource<TokenType>> Parse<I, TokenType> for Parser<I> {
+    type Value = Value;
+    type Output = ();
+
+    fn parse_state_mut(&mut self) -> &mut ParseState<I, TokenType, Value> {
+        &mut self.0
+    }
+
+    fn parse_state(&self) -> &ParseState<I, TokenType, Value> {
+        &self.0
+    }
+
+    fn expected_terminals(&self, state: usize) -> &'static [TokenType] {
+        EXPECTED[state]
+    }
+
+    fn eof(&self) -> TokenType {
+        TokenType::Eof
+    }
+
+    fn error_terminal(&self) -> Option<TokenType> {
+        None
+    }
+
+    fn step(&self, state: usize, kind: TokenType) -> Option<Step> {
+        match (state, kind) {
+        (0, TokenType::Let) => Some(Step::Shift(4)),
+        (0, TokenType::Type) => Some(Step::Shift(3)),
+        (1, TokenType::Eof) => Some(Step::Accept(0, 1)),
+        (1, TokenType::Let) => Some(Step::Shift(4)),
+        (1, TokenType::Type) => Some(Step::Shift(3)),
+        (2, TokenType::Eof) => Some(Step::Reduce(2, 1)),
+        (2, TokenType::Let) => Some(Step::Reduce(2, 1)),
+        (2, TokenType::Type) => Some(Step::Reduce(2, 1)),
+        (3, TokenType::Identifier) => Some(Step::Shift(6)),
+        (4, TokenType::Identifier) => Some(Step::Shift(7)),
+        (5, TokenType::Eof) => Some(Step::Reduce(1, 2)),
+        (5, TokenType::Let) => Some(Step::Reduce(1, 2)),
+        (5, TokenType::Type) => Some(Step::Reduce(1, 2)),
+        (6, TokenType::Equals) => Some(Step::Shift(8)),
+        (7, TokenType::Equals) => Some(Step::Shift(9)),
+        (8, TokenType::Identifier) => Some(Step::Shift(11)),
+        (9, TokenType::Identifier) => Some(Step::Shift(14)),
+        (9, TokenType::Number) => Some(Step::Shift(15)),
+        (10, TokenType::Semicolon) => Some(Step::Shift(16)),
+        (11, TokenType::Lt) => Some(Step::Shift(17)),
+        (11, TokenType::Semicolon) => Some(Step::Reduce(5, 1)),
+        (12, TokenType::Gt) => Some(Step::Shift(20)),
+        (12, TokenType::GtGt) => Some(Step::Shift(19)),
+        (12, TokenType::Semicolon) => Some(Step::Shift(18)),
+        (13, TokenType::Gt) => Some(Step::Reduce(11, 1)),
+        (13, TokenType::GtGt) => Some(Step::Reduce(11, 1)),
+        (13, TokenType::Semicolon) => Some(Step::Reduce(11, 1)),
+        (14, TokenType::Gt) => Some(Step::Reduce(12, 1)),
+        (14, TokenType::GtGt) => Some(Step::Reduce(12, 1)),
+        (14, TokenType::Semicolon) => Some(Step::Reduce(12, 1)),
+        (15, TokenType::Gt) => Some(Step::Reduce(13, 1)),
+        (15, TokenType::GtGt) => Some(Step::Reduce(13, 1)),
+        (15, TokenType::Semicolon) => Some(Step::Reduce(13, 1)),
+        (16, TokenType::Eof) => Some(Step::Reduce(3, 5)),
+        (16, TokenType::Let) => Some(Step::Reduce(3, 5)),
+        (16, TokenType::Type) => Some(Step::Reduce(3, 5)),
+        (17, TokenType::Identifier) => Some(Step::Shift(21)),
+        (18, TokenType::Eof) => Some(Step::Reduce(4, 5)),
+        (18, TokenType::Let) => Some(Step::Reduce(4, 5)),
+        (18, TokenType::Type) => Some(Step::Reduce(4, 5)),
+        (19, TokenType::Identifier) => Some(Step::Shift(14)),
+        (19, TokenType::Number) => Some(Step::Shift(15)),
+        (20, TokenType::Identifier) => Some(Step::Shift(14)),
+        (20, TokenType::Number) => Some(Step::Shift(15)),
+        (21, TokenType::Comma) => Some(Step::Reduce(5, 1)),
+        (21, TokenType::Gt) => Some(Step::Reduce(5, 1)),
+        (21, TokenType::Lt) => Some(Step::Shift(26)),
+        (22, TokenType::Comma) => Some(Step::Shift(28)),
+        (22, TokenType::Gt) => Some(Step::Shift(27)),
+        (23, TokenType::Comma) => Some(Step::Reduce(8, 1)),
+        (23, TokenType::Gt) => Some(Step::Reduce(8, 1)),
+        (24, TokenType::Gt) => Some(Step::Reduce(9, 3)),
+        (24, TokenType::GtGt) => Some(Step::Reduce(9, 3)),
+        (24, TokenType::Semicolon) => Some(Step::Reduce(9, 3)),
+        (25, TokenType::Gt) => Some(Step::Reduce(10, 3)),
+        (25, TokenType::GtGt) => Some(Step::Reduce(10, 3)),
+        (25, TokenType::Semicolon) => Some(Step::Reduce(10, 3)),
+        (26, TokenType::Identifier) => Some(Step::Shift(21)),
+        (27, TokenType::Semicolon) => Some(Step::Reduce(6, 4)),
+        (28, TokenType::Identifier) => Some(Step::Shift(21)),
+        (29, TokenType::Comma) => Some(Step::Shift(28)),
+        (29, TokenType::Gt) => Some(Step::Shift(31)),
+        (30, TokenType::Comma) => Some(Step::Reduce(7, 3)),
+        (30, TokenType::Gt) => Some(Step::Reduce(7, 3)),
+        (31, TokenType::Comma) => Some(Step::Reduce(6, 4)),
+        (31, TokenType::Gt) => Some(Step::Reduce(6, 4)),
+        _ => None,
+        }
+    }
+
+    fn goto_state(&self, state: usize, rule: usize) -> usize {
+        match (rule, state) {
+        (1, 0) => 1,
+        (2, 0) => 1,
+        (3, 0) => 2,
+        (3, 1) => 5,
+        (4, 0) => 2,
+        (4, 1) => 5,
+        (5, 8) => 10,
+        (5, 17) => 23,
+        (5, 26) => 23,
+        (5, 28) => 30,
+        (6, 8) => 10,
+        (6, 17) => 23,
+        (6, 26) => 23,
+        (6, 28) => 30,
+        (7, 17) => 22,
+        (7, 26) => 29,
+        (8, 17) => 22,
+        (8, 26) => 29,
+        (9, 9) => 12,
+        (10, 9) => 12,
+        (11, 9) => 12,
+        (12, 9) => 13,
+        (12, 19) => 24,
+        (12, 20) => 25,
+        (13, 9) => 13,
+        (13, 19) => 24,
+        (13, 20) => 25,
+        _ => panic!("unkown goto"),
+        }
+    }
+
+    fn token_value(&mut self, token: Token<TokenType>) -> Value {
+        Value::Terminal(token)
+    }
+
+    #[allow(unused_variables, unused_braces, clippy::just_underscores_and_digits, clippy::let_unit_value)]
+    fn reduce_values(&mut self, rule: usize, values: Vec<Value>) -> Value {
+        let mut values = values.into_iter();
+        match rule {
+        0 => {
+        let _1 = match values.next() { Some(Value::Items(v)) => v, _ => unreachable!() };
+        Value::Program(_1)
+        }
+        1 => {
+        let _1 = match values.next() { Some(Value::Items(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Item(v)) => v, _ => unreachable!() };
+        Value::Items(())
+        }
+        2 => {
+        let _1 = match values.next() { Some(Value::Item(v)) => v, _ => unreachable!() };
+        Value::Items(_1)
+        }
+        3 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _4 = match values.next() { Some(Value::TypeExpr(v)) => v, _ => unreachable!() };
+        let _5 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Item(())
+        }
+        4 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _4 = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        let _5 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Item(())
+        }
+        5 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::TypeExpr(())
+        }
+        6 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::TypeExprs(v)) => v, _ => unreachable!() };
+        let _4 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::TypeExpr(())
+        }
+        7 => {
+        let _1 = match values.next() { Some(Value::TypeExprs(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::TypeExpr(v)) => v, _ => unreachable!() };
+        Value::TypeExprs(())
+        }
+        8 => {
+        let _1 = match values.next() { Some(Value::TypeExpr(v)) => v, _ => unreachable!() };
+        Value::TypeExprs(_1)
+        }
+        9 => {
+        let _1 = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Atom(v)) => v, _ => unreachable!() };
+        Value::Expr(())
+        }
+        10 => {
+        let _1 = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Atom(v)) => v, _ => unreachable!() };
+        Value::Expr(())
+        }
+        11 => {
+        let _1 = match values.next() { Some(Value::Atom(v)) => v, _ => unreachable!() };
+        Value::Expr(_1)
+        }
+        12 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Atom(())
+        }
+        13 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Atom(())
+        }
+        _ => unreachable!(),
+        }
+    }
+
+    fn output(&mut self, value: Value) {
+        match value {
+        Value::Program(v) => v,
+        _ => unreachable!(),
+        }
+    }
+}
+/// Recognises the tokens of the grammar, taking the longest match and otherwise the token with priority.
+pub static LEXER: yaw::runtime::lexer::Lexer<TokenType> = yaw::runtime::lexer::Lexer {
+    modes: &[
+        yaw::runtime::lexer::Mode {
+            transitions: &[
+                &[('\n', '\n', 1), (' ', ' ', 1), (',', ',', 2), ('0', '9', 3), (';', ';', 4), ('<', '<', 5), ('=', '=', 6), ('>', '>', 7), ('A', 'Z', 8), ('_', '_', 8), ('a', 'k', 8), ('l', 'l', 9), ('m', 's', 8), ('t', 't', 10), ('u', 'z', 8)],
+                &[('\n', '\n', 1), (' ', ' ', 1)],
+                &[],
+                &[('0', '9', 3)],
+                &[],
+                &[],
+                &[],
+                &[('>', '>', 11)],
+                &[('0', '9', 8), ('A', 'Z', 8), ('_', '_', 8), ('a', 'z', 8)],
+                &[('0', '9', 8), ('A', 'Z', 8), ('_', '_', 8), ('a', 'd', 8), ('e', 'e', 12), ('f', 'z', 8)],
+                &[('0', '9', 8), ('A', 'Z', 8), ('_', '_', 8), ('a', 'x', 8), ('y', 'y', 13), ('z', 'z', 8)],
+                &[],
+                &[('0', '9', 8), ('A', 'Z', 8), ('_', '_', 8), ('a', 's', 8), ('t', 't', 14), ('u', 'z', 8)],
+                &[('0', '9', 8), ('A', 'Z', 8), ('_', '_', 8), ('a', 'o', 8), ('p', 'p', 15), ('q', 'z', 8)],
+                &[('0', '9', 8), ('A', 'Z', 8), ('_', '_', 8), ('a', 'z', 8)],
+                &[('0', '9', 8), ('A', 'Z', 8), ('_', '_', 8), ('a', 'd', 8), ('e', 'e', 16), ('f', 'z', 8)],
+                &[('0', '9', 8), ('A', 'Z', 8), ('_', '_', 8), ('a', 'z', 8)],
+            ],
+            accept: &[
+                &[],
+                &[TokenType::Whitespace],
+                &[TokenType::Comma],
+                &[TokenType::Number],
+                &[TokenType::Semicolon],
+                &[TokenType::Lt],
+                &[TokenType::Equals],
+                &[TokenType::Gt],
+                &[TokenType::Identifier],
+                &[TokenType::Identifier],
+                &[TokenType::Identifier],
+                &[TokenType::GtGt],
+                &[TokenType::Identifier],
+                &[TokenType::Identifier],
+                &[TokenType::Let, TokenType::Identifier],
+                &[TokenType::Identifier],
+                &[TokenType::Type, TokenType::Identifier],
+            ],
+        },
+    ],
+    actions: &[],
+    trivia: &[TokenType::Whitespace],
+    eof: TokenType::Eof,
+};
+
+pub fn lex(source: &str) -> Result<Vec<Token<TokenType>>, yaw::runtime::lexer::LexError> {
+    LEXER.tokenize(source)
+}
+
+/// Lexes `source` while parsing, preferring the tokens the parser expects.
+pub fn scan(source: &str) -> yaw::runtime::lexer::Scanner<'_, TokenType> {
+    LEXER.scan(source)
+}
@@ -0,0 +1,122 @@
+// @generated by yaw 0.1.0, do not edit.
+// yaw-hash: 3ebcf7a245c30575
+pub const GRAMMAR_HASH: u64 = 0x3ebcf7a245c30575;
+const _: () = yaw::build::assert_fresh(GRAMMAR_HASH, include_str!("events.yaw"));
+
+use yaw::runtime::{Parse, ParseState, Step, Token, TokenSource, events};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TokenType {
+    Comma,
+    Eof,
+    Identifier,
+    LParen,
+    RParen,
+    Whitespace,
+}
+
+impl std::fmt::Display for TokenType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            TokenType::Comma => "Comma",
+            TokenType::Eof => "Eof",
+            TokenType::Identifier => "Identifier",
+            TokenType::LParen => "LParen",
+            TokenType::RParen => "RParen",
+            TokenType::Whitespace => "Whitespace",
+        })
+    }
+}
+
+pub fn is_trivia(kind: &TokenType) -> bool {
+    matches!(kind, TokenType::Whitespace)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    Call,
+    Args,
+}
+
+pub struct Parser<I = std::vec::IntoIter<Token<TokenType>>>(pub ParseState<I, TokenType, usize>, pub events::Recorder<TokenType, NodeKind>);
+
+const EXPECTED: [&[TokenType]; 8] = [
+    &[TokenType::Identifier],
+    &[TokenType::LParen],
+    &[TokenType::Identifier],
+    &[TokenType::Comma, TokenType::RParen],
+    &[TokenType::Comma, TokenType::RParen],
+    &[TokenType::Eof],
+    &[TokenType::Identifier],
+    &[TokenType::Comma, TokenType::RParen],
+];
+
+impl<I: TokenSource<TokenType>> Parse<I, TokenType> for Parser<I> {
+    type Value = usize;
+    type Output = Vec<events::Event<TokenType, NodeKind>>;
+
+    fn parse_state_mut(&mut self) -> &mut ParseState<I, TokenType, usize> {
+        &mut self.0
+    }
+
+    fn parse_state(&self) -> &ParseState<I, TokenType, usize> {
+        &self.0
+    }
+
+    fn expected_terminals(&self, state: usize) -> &'static [TokenType] {
+        EXPECTED[state]
+    }
+
+    fn eof(&self) -> TokenType {
+        TokenType::Eof
+    }
+
+    fn error_terminal(&self) -> Option<TokenType> {
+        None
+    }
+
+    fn step(&self, state: usize, kind: TokenType) -> Option<Step> {
+        match (state, kind) {
+        (0, TokenType::Identifier) => Some(Step::Shift(1)),
+        (1, TokenType::LParen) => Some(Step::Shift(2)),
+        (2, TokenType::Identifier) => Some(Step::Shift(4)),
+        (3, TokenType::Comma) => Some(Step::Shift(6)),
+        (3, TokenType::RParen) => Some(Step::Shift(5)),
+        (4, TokenType::Comma) => Some(Step::Reduce(2, 1)),
+        (4, TokenType::RParen) => Some(Step::Reduce(2, 1)),
+        (5, TokenType::Eof) => Some(Step::Accept(0, 4)),
+        (6, TokenType::Identifier) => Some(Step::Shift(7)),
+        (7, TokenType::Comma) => Some(Step::Reduce(1, 3)),
+        (7, TokenType::RParen) => Some(Step::Reduce(1, 3)),
+        _ => None,
+        }
+    }
+
+    fn goto_state(&self, state: usize, rule: usize) -> usize {
+        match (rule, state) {
+        (1, 2) => 3,
+        (2, 2) => 3,
+        _ => panic!("unkown goto"),
+        }
+    }
+
+    fn token_value(&mut self, token: Token<TokenType>) -> usize {
+        let trivia = self.0.take_trivia();
+        self.1.token(trivia, token)
+    }
+
+    fn reduce_values(&mut self, rule: usize, values: Vec<usize>) -> usize {
+        let kind = match rule {
+        0 => NodeKind::Call,
+        1 => NodeKind::Args,
+        2 => NodeKind::Args,
+        _ => unreachable!(),
+        };
+        self.1.node(kind, values)
+    }
+
+    fn output(&mut self, _value: usize) -> Vec<events::Event<TokenType, NodeKind>> {
+        let trivia = self.0.take_trivia();
+        self.1.finish(trivia)
+    }
+}
\ No newline at end of file
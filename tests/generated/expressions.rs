@@ -0,0 +1,358 @@
+// @generated by yaw 0.1.0, do not edit.
+// yaw-hash: 4a84742279f8efe9
+pub const GRAMMAR_HASH: u64 = 0x4a84742279f8efe9;
+const _: () = yaw::build::assert_fresh(GRAMMAR_HASH, include_str!("expressions.yaw"));
+
+use yaw::runtime::{Parse, ParseState, Step, Token, TokenSource};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TokenType {
+    Comma,
+    Eof,
+    Identifier,
+    LParen,
+    Number,
+    Plus,
+    RParen,
+}
+
+impl std::fmt::Display for TokenType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            TokenType::Comma => "Comma",
+            TokenType::Eof => "Eof",
+            TokenType::Identifier => "Identifier",
+            TokenType::LParen => "LParen",
+            TokenType::Number => "Number",
+            TokenType::Plus => "Plus",
+            TokenType::RParen => "RParen",
+        })
+    }
+}
+
+pub trait Actions {
+    type Start;
+    type Expr;
+    type Term;
+    type Args;
+
+    /// Start -> Expr
+    fn reduce_start(&mut self, expr: Self::Expr) -> Self::Start;
+
+    /// Expr -> Expr Plus Term
+    fn reduce_add(&mut self, lhs: Self::Expr, plus: Token<TokenType>, rhs: Self::Term) -> Self::Expr;
+
+    /// Expr -> Term
+    fn reduce_expr_2(&mut self, term: Self::Term) -> Self::Expr;
+
+    /// Term -> Number
+    fn reduce_number(&mut self, number: Token<TokenType>) -> Self::Term;
+
+    /// Term -> Identifier LParen Args RParen
+    fn reduce_call(&mut self, function: Token<TokenType>, l_paren: Token<TokenType>, args: Self::Args, r_paren: Token<TokenType>) -> Self::Term;
+
+    /// Term -> LParen Expr RParen
+    fn reduce_group(&mut self, l_paren: Token<TokenType>, expr: Self::Expr, r_paren: Token<TokenType>) -> Self::Term;
+
+    /// Args -> Args Comma Expr
+    fn reduce_more_arguments(&mut self, args: Self::Args, comma: Token<TokenType>, expr: Self::Expr) -> Self::Args;
+
+    /// Args -> Expr
+    fn reduce_first_argument(&mut self, expr: Self::Expr) -> Self::Args;
+}
+
+pub enum Value<A>
+where A: Actions,
+{
+    Terminal(Token<TokenType>),
+    Start(A::Start),
+    Expr(A::Expr),
+    Term(A::Term),
+    Args(A::Args),
+}
+
+pub struct Parser<A: Actions, I = std::vec::IntoIter<Token<TokenType>>>(pub ParseState<I, TokenType, Value<A>>, pub A);
+
+const EXPECTED: [&[TokenType]; 38] = [
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Eof, TokenType::Plus],
+    &[TokenType::Eof, TokenType::Plus],
+    &[TokenType::Eof, TokenType::Plus],
+    &[TokenType::LParen],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Plus, TokenType::RParen],
+    &[TokenType::Plus, TokenType::RParen],
+    &[TokenType::Plus, TokenType::RParen],
+    &[TokenType::LParen],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Eof, TokenType::Plus],
+    &[TokenType::Comma, TokenType::Plus, TokenType::RParen],
+    &[TokenType::Comma, TokenType::Plus, TokenType::RParen],
+    &[TokenType::Comma, TokenType::Plus, TokenType::RParen],
+    &[TokenType::LParen],
+    &[TokenType::Comma, TokenType::RParen],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Eof, TokenType::Plus],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Plus, TokenType::RParen],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Eof, TokenType::Plus],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Plus, TokenType::RParen],
+    &[TokenType::Plus, TokenType::RParen],
+    &[TokenType::Comma, TokenType::RParen],
+    &[TokenType::Plus, TokenType::RParen],
+    &[TokenType::Comma, TokenType::Plus, TokenType::RParen],
+    &[TokenType::Comma, TokenType::RParen],
+    &[TokenType::Comma, TokenType::Plus, TokenType::RParen],
+    &[TokenType::Comma, TokenType::Plus, TokenType::RParen],
+    &[TokenType::Plus, TokenType::RParen],
+    &[TokenType::Comma, TokenType::Plus, TokenType::RParen],
+];
+
+impl<A: Actions, I: TokenSource<TokenType>> Parse<I, TokenType> for Parser<A, I> {
+    type Value = Value<A>;
+    type Output = A::Start;
+
+    fn parse_state_mut(&mut self) -> &mut ParseState<I, TokenType, Value<A>> {
+        &mut self.0
+    }
+
+    fn parse_state(&self) -> &ParseState<I, TokenType, Value<A>> {
+        &self.0
+    }
+
+    fn expected_terminals(&self, state: usize) -> &'static [TokenType] {
+        EXPECTED[state]
+    }
+
+    fn eof(&self) -> TokenType {
+        TokenType::Eof
+    }
+
+    fn error_terminal(&self) -> Option<TokenType> {
+        None
+    }
+
+    fn step(&self, state: usize, kind: TokenType) -> Option<Step> {
+        match (state, kind) {
+        (0, TokenType::Identifier) => Some(Step::Shift(4)),
+        (0, TokenType::LParen) => Some(Step::Shift(5)),
+        (0, TokenType::Number) => Some(Step::Shift(3)),
+        (1, TokenType::Eof) => Some(Step::Accept(0, 1)),
+        (1, TokenType::Plus) => Some(Step::Shift(6)),
+        (2, TokenType::Eof) => Some(Step::Reduce(2, 1)),
+        (2, TokenType::Plus) => Some(Step::Reduce(2, 1)),
+        (3, TokenType::Eof) => Some(Step::Reduce(3, 1)),
+        (3, TokenType::Plus) => Some(Step::Reduce(3, 1)),
+        (4, TokenType::LParen) => Some(Step::Shift(7)),
+        (5, TokenType::Identifier) => Some(Step::Shift(11)),
+        (5, TokenType::LParen) => Some(Step::Shift(12)),
+        (5, TokenType::Number) => Some(Step::Shift(10)),
+        (6, TokenType::Identifier) => Some(Step::Shift(4)),
+        (6, TokenType::LParen) => Some(Step::Shift(5)),
+        (6, TokenType::Number) => Some(Step::Shift(3)),
+        (7, TokenType::Identifier) => Some(Step::Shift(17)),
+        (7, TokenType::LParen) => Some(Step::Shift(19)),
+        (7, TokenType::Number) => Some(Step::Shift(16)),
+        (8, TokenType::Plus) => Some(Step::Shift(20)),
+        (8, TokenType::RParen) => Some(Step::Shift(21)),
+        (9, TokenType::Plus) => Some(Step::Reduce(2, 1)),
+        (9, TokenType::RParen) => Some(Step::Reduce(2, 1)),
+        (10, TokenType::Plus) => Some(Step::Reduce(3, 1)),
+        (10, TokenType::RParen) => Some(Step::Reduce(3, 1)),
+        (11, TokenType::LParen) => Some(Step::Shift(22)),
+        (12, TokenType::Identifier) => Some(Step::Shift(11)),
+        (12, TokenType::LParen) => Some(Step::Shift(12)),
+        (12, TokenType::Number) => Some(Step::Shift(10)),
+        (13, TokenType::Eof) => Some(Step::Reduce(1, 3)),
+        (13, TokenType::Plus) => Some(Step::Reduce(1, 3)),
+        (14, TokenType::Comma) => Some(Step::Reduce(7, 1)),
+        (14, TokenType::Plus) => Some(Step::Shift(24)),
+        (14, TokenType::RParen) => Some(Step::Reduce(7, 1)),
+        (15, TokenType::Comma) => Some(Step::Reduce(2, 1)),
+        (15, TokenType::Plus) => Some(Step::Reduce(2, 1)),
+        (15, TokenType::RParen) => Some(Step::Reduce(2, 1)),
+        (16, TokenType::Comma) => Some(Step::Reduce(3, 1)),
+        (16, TokenType::Plus) => Some(Step::Reduce(3, 1)),
+        (16, TokenType::RParen) => Some(Step::Reduce(3, 1)),
+        (17, TokenType::LParen) => Some(Step::Shift(25)),
+        (18, TokenType::Comma) => Some(Step::Shift(27)),
+        (18, TokenType::RParen) => Some(Step::Shift(26)),
+        (19, TokenType::Identifier) => Some(Step::Shift(11)),
+        (19, TokenType::LParen) => Some(Step::Shift(12)),
+        (19, TokenType::Number) => Some(Step::Shift(10)),
+        (20, TokenType::Identifier) => Some(Step::Shift(11)),
+        (20, TokenType::LParen) => Some(Step::Shift(12)),
+        (20, TokenType::Number) => Some(Step::Shift(10)),
+        (21, TokenType::Eof) => Some(Step::Reduce(5, 3)),
+        (21, TokenType::Plus) => Some(Step::Reduce(5, 3)),
+        (22, TokenType::Identifier) => Some(Step::Shift(17)),
+        (22, TokenType::LParen) => Some(Step::Shift(19)),
+        (22, TokenType::Number) => Some(Step::Shift(16)),
+        (23, TokenType::Plus) => Some(Step::Shift(20)),
+        (23, TokenType::RParen) => Some(Step::Shift(31)),
+        (24, TokenType::Identifier) => Some(Step::Shift(17)),
+        (24, TokenType::LParen) => Some(Step::Shift(19)),
+        (24, TokenType::Number) => Some(Step::Shift(16)),
+        (25, TokenType::Identifier) => Some(Step::Shift(17)),
+        (25, TokenType::LParen) => Some(Step::Shift(19)),
+        (25, TokenType::Number) => Some(Step::Shift(16)),
+        (26, TokenType::Eof) => Some(Step::Reduce(4, 4)),
+        (26, TokenType::Plus) => Some(Step::Reduce(4, 4)),
+        (27, TokenType::Identifier) => Some(Step::Shift(17)),
+        (27, TokenType::LParen) => Some(Step::Shift(19)),
+        (27, TokenType::Number) => Some(Step::Shift(16)),
+        (28, TokenType::Plus) => Some(Step::Shift(20)),
+        (28, TokenType::RParen) => Some(Step::Shift(35)),
+        (29, TokenType::Plus) => Some(Step::Reduce(1, 3)),
+        (29, TokenType::RParen) => Some(Step::Reduce(1, 3)),
+        (30, TokenType::Comma) => Some(Step::Shift(27)),
+        (30, TokenType::RParen) => Some(Step::Shift(36)),
+        (31, TokenType::Plus) => Some(Step::Reduce(5, 3)),
+        (31, TokenType::RParen) => Some(Step::Reduce(5, 3)),
+        (32, TokenType::Comma) => Some(Step::Reduce(1, 3)),
+        (32, TokenType::Plus) => Some(Step::Reduce(1, 3)),
+        (32, TokenType::RParen) => Some(Step::Reduce(1, 3)),
+        (33, TokenType::Comma) => Some(Step::Shift(27)),
+        (33, TokenType::RParen) => Some(Step::Shift(37)),
+        (34, TokenType::Comma) => Some(Step::Reduce(6, 3)),
+        (34, TokenType::Plus) => Some(Step::Shift(24)),
+        (34, TokenType::RParen) => Some(Step::Reduce(6, 3)),
+        (35, TokenType::Comma) => Some(Step::Reduce(5, 3)),
+        (35, TokenType::Plus) => Some(Step::Reduce(5, 3)),
+        (35, TokenType::RParen) => Some(Step::Reduce(5, 3)),
+        (36, TokenType::Plus) => Some(Step::Reduce(4, 4)),
+        (36, TokenType::RParen) => Some(Step::Reduce(4, 4)),
+        (37, TokenType::Comma) => Some(Step::Reduce(4, 4)),
+        (37, TokenType::Plus) => Some(Step::Reduce(4, 4)),
+        (37, TokenType::RParen) => Some(Step::Reduce(4, 4)),
+        _ => None,
+        }
+    }
+
+    fn goto_state(&self, state: usize, rule: usize) -> usize {
+        match (rule, state) {
+        (1, 0) => 1,
+        (1, 5) => 8,
+        (1, 7) => 14,
+        (1, 12) => 23,
+        (1, 19) => 28,
+        (1, 22) => 14,
+        (1, 25) => 14,
+        (1, 27) => 34,
+        (2, 0) => 1,
+        (2, 5) => 8,
+        (2, 7) => 14,
+        (2, 12) => 23,
+        (2, 19) => 28,
+        (2, 22) => 14,
+        (2, 25) => 14,
+        (2, 27) => 34,
+        (3, 0) => 2,
+        (3, 5) => 9,
+        (3, 6) => 13,
+        (3, 7) => 15,
+        (3, 12) => 9,
+        (3, 19) => 9,
+        (3, 20) => 29,
+        (3, 22) => 15,
+        (3, 24) => 32,
+        (3, 25) => 15,
+        (3, 27) => 15,
+        (4, 0) => 2,
+        (4, 5) => 9,
+        (4, 6) => 13,
+        (4, 7) => 15,
+        (4, 12) => 9,
+        (4, 19) => 9,
+        (4, 20) => 29,
+        (4, 22) => 15,
+        (4, 24) => 32,
+        (4, 25) => 15,
+        (4, 27) => 15,
+        (5, 0) => 2,
+        (5, 5) => 9,
+        (5, 6) => 13,
+        (5, 7) => 15,
+        (5, 12) => 9,
+        (5, 19) => 9,
+        (5, 20) => 29,
+        (5, 22) => 15,
+        (5, 24) => 32,
+        (5, 25) => 15,
+        (5, 27) => 15,
+        (6, 7) => 18,
+        (6, 22) => 30,
+        (6, 25) => 33,
+        (7, 7) => 18,
+        (7, 22) => 30,
+        (7, 25) => 33,
+        _ => panic!("unkown goto"),
+        }
+    }
+
+    fn token_value(&mut self, token: Token<TokenType>) -> Value<A> {
+        Value::Terminal(token)
+    }
+
+    #[allow(unused_variables, unused_braces, clippy::just_underscores_and_digits, clippy::let_unit_value)]
+    fn reduce_values(&mut self, rule: usize, values: Vec<Value<A>>) -> Value<A> {
+        let mut values = values.into_iter();
+        match rule {
+        0 => {
+        let _1 = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        Value::Start(self.1.reduce_start(_1))
+        }
+        1 => {
+        let lhs = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let rhs = match values.next() { Some(Value::Term(v)) => v, _ => unreachable!() };
+        Value::Expr(self.1.reduce_add(lhs, _2, rhs))
+        }
+        2 => {
+        let _1 = match values.next() { Some(Value::Term(v)) => v, _ => unreachable!() };
+        Value::Expr(self.1.reduce_expr_2(_1))
+        }
+        3 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Term(self.1.reduce_number(_1))
+        }
+        4 => {
+        let function = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Args(v)) => v, _ => unreachable!() };
+        let _4 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Term(self.1.reduce_call(function, _2, _3, _4))
+        }
+        5 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Term(self.1.reduce_group(_1, _2, _3))
+        }
+        6 => {
+        let _1 = match values.next() { Some(Value::Args(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        Value::Args(self.1.reduce_more_arguments(_1, _2, _3))
+        }
+        7 => {
+        let _1 = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        Value::Args(self.1.reduce_first_argument(_1))
+        }
+        _ => unreachable!(),
+        }
+    }
+
+    fn output(&mut self, value: Value<A>) -> A::Start {
+        match value {
+        Value::Start(v) => v,
+        _ => unreachable!(),
+        }
+    }
+}
\ No newline at end of file
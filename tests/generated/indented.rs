@@ -0,0 +1,354 @@
+// @generated by yaw 0.1.0, do not edit.
+// yaw-hash: 431c4d56c3946cd2
+pub const GRAMMAR_HASH: u64 = 0x431c4d56c3946cd2;
+const _: () = yaw::build::assert_fresh(GRAMMAR_HASH, include_str!("indented.yaw"));
+
+use yaw::runtime::{Parse, ParseState, Step, Token, TokenSource};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TokenType {
+    Colon,
+    Comma,
+    Comment,
+    Dedent,
+    Eof,
+    Equals,
+    Identifier,
+    Indent,
+    LBracket,
+    Newline,
+    Number,
+    RBracket,
+    Whitespace,
+}
+
+impl std::fmt::Display for TokenType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            TokenType::Colon => ":",
+            TokenType::Comma => ",",
+            TokenType::Comment => "Comment",
+            TokenType::Dedent => "Dedent",
+            TokenType::Eof => "Eof",
+            TokenType::Equals => "=",
+            TokenType::Identifier => "Identifier",
+            TokenType::Indent => "Indent",
+            TokenType::LBracket => "[",
+            TokenType::Newline => "Newline",
+            TokenType::Number => "Number",
+            TokenType::RBracket => "]",
+            TokenType::Whitespace => "Whitespace",
+        })
+    }
+}
+
+pub fn is_trivia(kind: &TokenType) -> bool {
+    matches!(kind, TokenType::Whitespace | TokenType::Comment)
+}
+
+pub enum Value {
+    Terminal(Token<TokenType>),
+    File(()),
+    Entries(()),
+    Entry(()),
+    Item(()),
+    Items(()),
+}
+
+pub struct Parser<I = std::vec::IntoIter<Token<TokenType>>>(pub ParseState<I, TokenType, Value>);
+
+const EXPECTED: [&[TokenType]; 37] = [
+    &[TokenType::Identifier],
+    &[TokenType::Eof, TokenType::Identifier],
+    &[TokenType::Eof, TokenType::Identifier],
+    &[TokenType::Colon, TokenType::Equals],
+    &[TokenType::Eof, TokenType::Identifier],
+    &[TokenType::Identifier, TokenType::LBracket, TokenType::Number],
+    &[TokenType::Newline],
+    &[TokenType::Newline],
+    &[TokenType::Newline],
+    &[TokenType::Newline],
+    &[TokenType::Identifier, TokenType::LBracket, TokenType::Number],
+    &[TokenType::Indent],
+    &[TokenType::Eof, TokenType::Identifier],
+    &[TokenType::Comma, TokenType::RBracket],
+    &[TokenType::Comma, TokenType::RBracket],
+    &[TokenType::Identifier, TokenType::LBracket, TokenType::Number],
+    &[TokenType::Comma, TokenType::RBracket],
+    &[TokenType::Comma, TokenType::RBracket],
+    &[TokenType::Identifier],
+    &[TokenType::Comma, TokenType::RBracket],
+    &[TokenType::Newline],
+    &[TokenType::Identifier, TokenType::LBracket, TokenType::Number],
+    &[TokenType::Dedent, TokenType::Identifier],
+    &[TokenType::Dedent, TokenType::Identifier],
+    &[TokenType::Colon, TokenType::Equals],
+    &[TokenType::Comma, TokenType::RBracket],
+    &[TokenType::Comma, TokenType::RBracket],
+    &[TokenType::Dedent, TokenType::Identifier],
+    &[TokenType::Eof, TokenType::Identifier],
+    &[TokenType::Identifier, TokenType::LBracket, TokenType::Number],
+    &[TokenType::Newline],
+    &[TokenType::Newline],
+    &[TokenType::Indent],
+    &[TokenType::Dedent, TokenType::Identifier],
+    &[TokenType::Identifier],
+    &[TokenType::Dedent, TokenType::Identifier],
+    &[TokenType::Dedent, TokenType::Identifier],
+];
+
+impl<I: TokenSource<TokenType>> Parse<I, TokenType> for Parser<I> {
+    type Value = Value;
+    type Output = ();
+
+    fn parse_state_mut(&mut self) -> &mut ParseState<I, TokenType, Value> {
+        &mut self.0
+    }
+
+    fn parse_state(&self) -> &ParseState<I, TokenType, Value> {
+        &self.0
+    }
+
+    fn expected_terminals(&self, state: usize) -> &'static [TokenType] {
+        EXPECTED[state]
+    }
+
+    fn eof(&self) -> TokenType {
+        TokenType::Eof
+    }
+
+    fn error_terminal(&self) -> Option<TokenType> {
+        None
+    }
+
+    fn step(&self, state: usize, kind: TokenType) -> Option<Step> {
+        match (state, kind) {
+        (0, TokenType::Identifier) => Some(Step::Shift(3)),
+        (1, TokenType::Eof) => Some(Step::Accept(0, 1)),
+        (1, TokenType::Identifier) => Some(Step::Shift(3)),
+        (2, TokenType::Eof) => Some(Step::Reduce(2, 1)),
+        (2, TokenType::Identifier) => Some(Step::Reduce(2, 1)),
+        (3, TokenType::Colon) => Some(Step::Shift(6)),
+        (3, TokenType::Equals) => Some(Step::Shift(5)),
+        (4, TokenType::Eof) => Some(Step::Reduce(1, 2)),
+        (4, TokenType::Identifier) => Some(Step::Reduce(1, 2)),
+        (5, TokenType::Identifier) => Some(Step::Shift(8)),
+        (5, TokenType::LBracket) => Some(Step::Shift(10)),
+        (5, TokenType::Number) => Some(Step::Shift(9)),
+        (6, TokenType::Newline) => Some(Step::Shift(11)),
+        (7, TokenType::Newline) => Some(Step::Shift(12)),
+        (8, TokenType::Newline) => Some(Step::Reduce(5, 1)),
+        (9, TokenType::Newline) => Some(Step::Reduce(6, 1)),
+        (10, TokenType::Identifier) => Some(Step::Shift(13)),
+        (10, TokenType::LBracket) => Some(Step::Shift(15)),
+        (10, TokenType::Number) => Some(Step::Shift(14)),
+        (11, TokenType::Indent) => Some(Step::Shift(18)),
+        (12, TokenType::Eof) => Some(Step::Reduce(3, 4)),
+        (12, TokenType::Identifier) => Some(Step::Reduce(3, 4)),
+        (13, TokenType::Comma) => Some(Step::Reduce(5, 1)),
+        (13, TokenType::RBracket) => Some(Step::Reduce(5, 1)),
+        (14, TokenType::Comma) => Some(Step::Reduce(6, 1)),
+        (14, TokenType::RBracket) => Some(Step::Reduce(6, 1)),
+        (15, TokenType::Identifier) => Some(Step::Shift(13)),
+        (15, TokenType::LBracket) => Some(Step::Shift(15)),
+        (15, TokenType::Number) => Some(Step::Shift(14)),
+        (16, TokenType::Comma) => Some(Step::Shift(21)),
+        (16, TokenType::RBracket) => Some(Step::Shift(20)),
+        (17, TokenType::Comma) => Some(Step::Reduce(9, 1)),
+        (17, TokenType::RBracket) => Some(Step::Reduce(9, 1)),
+        (18, TokenType::Identifier) => Some(Step::Shift(24)),
+        (19, TokenType::Comma) => Some(Step::Shift(21)),
+        (19, TokenType::RBracket) => Some(Step::Shift(25)),
+        (20, TokenType::Newline) => Some(Step::Reduce(7, 3)),
+        (21, TokenType::Identifier) => Some(Step::Shift(13)),
+        (21, TokenType::LBracket) => Some(Step::Shift(15)),
+        (21, TokenType::Number) => Some(Step::Shift(14)),
+        (22, TokenType::Dedent) => Some(Step::Shift(28)),
+        (22, TokenType::Identifier) => Some(Step::Shift(24)),
+        (23, TokenType::Dedent) => Some(Step::Reduce(2, 1)),
+        (23, TokenType::Identifier) => Some(Step::Reduce(2, 1)),
+        (24, TokenType::Colon) => Some(Step::Shift(30)),
+        (24, TokenType::Equals) => Some(Step::Shift(29)),
+        (25, TokenType::Comma) => Some(Step::Reduce(7, 3)),
+        (25, TokenType::RBracket) => Some(Step::Reduce(7, 3)),
+        (26, TokenType::Comma) => Some(Step::Reduce(8, 3)),
+        (26, TokenType::RBracket) => Some(Step::Reduce(8, 3)),
+        (27, TokenType::Dedent) => Some(Step::Reduce(1, 2)),
+        (27, TokenType::Identifier) => Some(Step::Reduce(1, 2)),
+        (28, TokenType::Eof) => Some(Step::Reduce(4, 6)),
+        (28, TokenType::Identifier) => Some(Step::Reduce(4, 6)),
+        (29, TokenType::Identifier) => Some(Step::Shift(8)),
+        (29, TokenType::LBracket) => Some(Step::Shift(10)),
+        (29, TokenType::Number) => Some(Step::Shift(9)),
+        (30, TokenType::Newline) => Some(Step::Shift(32)),
+        (31, TokenType::Newline) => Some(Step::Shift(33)),
+        (32, TokenType::Indent) => Some(Step::Shift(34)),
+        (33, TokenType::Dedent) => Some(Step::Reduce(3, 4)),
+        (33, TokenType::Identifier) => Some(Step::Reduce(3, 4)),
+        (34, TokenType::Identifier) => Some(Step::Shift(24)),
+        (35, TokenType::Dedent) => Some(Step::Shift(36)),
+        (35, TokenType::Identifier) => Some(Step::Shift(24)),
+        (36, TokenType::Dedent) => Some(Step::Reduce(4, 6)),
+        (36, TokenType::Identifier) => Some(Step::Reduce(4, 6)),
+        _ => None,
+        }
+    }
+
+    fn goto_state(&self, state: usize, rule: usize) -> usize {
+        match (rule, state) {
+        (1, 0) => 1,
+        (1, 18) => 22,
+        (1, 34) => 35,
+        (2, 0) => 1,
+        (2, 18) => 22,
+        (2, 34) => 35,
+        (3, 0) => 2,
+        (3, 1) => 4,
+        (3, 18) => 23,
+        (3, 22) => 27,
+        (3, 34) => 23,
+        (3, 35) => 27,
+        (4, 0) => 2,
+        (4, 1) => 4,
+        (4, 18) => 23,
+        (4, 22) => 27,
+        (4, 34) => 23,
+        (4, 35) => 27,
+        (5, 5) => 7,
+        (5, 10) => 17,
+        (5, 15) => 17,
+        (5, 21) => 26,
+        (5, 29) => 31,
+        (6, 5) => 7,
+        (6, 10) => 17,
+        (6, 15) => 17,
+        (6, 21) => 26,
+        (6, 29) => 31,
+        (7, 5) => 7,
+        (7, 10) => 17,
+        (7, 15) => 17,
+        (7, 21) => 26,
+        (7, 29) => 31,
+        (8, 10) => 16,
+        (8, 15) => 19,
+        (9, 10) => 16,
+        (9, 15) => 19,
+        _ => panic!("unkown goto"),
+        }
+    }
+
+    fn token_value(&mut self, token: Token<TokenType>) -> Value {
+        Value::Terminal(token)
+    }
+
+    #[allow(unused_variables, unused_braces, clippy::just_underscores_and_digits, clippy::let_unit_value)]
+    fn reduce_values(&mut self, rule: usize, values: Vec<Value>) -> Value {
+        let mut values = values.into_iter();
+        match rule {
+        0 => {
+        let _1 = match values.next() { Some(Value::Entries(v)) => v, _ => unreachable!() };
+        Value::File(_1)
+        }
+        1 => {
+        let _1 = match values.next() { Some(Value::Entries(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Entry(v)) => v, _ => unreachable!() };
+        Value::Entries(())
+        }
+        2 => {
+        let _1 = match values.next() { Some(Value::Entry(v)) => v, _ => unreachable!() };
+        Value::Entries(_1)
+        }
+        3 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Item(v)) => v, _ => unreachable!() };
+        let _4 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Entry(())
+        }
+        4 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _4 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _5 = match values.next() { Some(Value::Entries(v)) => v, _ => unreachable!() };
+        let _6 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Entry(())
+        }
+        5 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Item(())
+        }
+        6 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Item(())
+        }
+        7 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Items(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Item(())
+        }
+        8 => {
+        let _1 = match values.next() { Some(Value::Items(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Item(v)) => v, _ => unreachable!() };
+        Value::Items(())
+        }
+        9 => {
+        let _1 = match values.next() { Some(Value::Item(v)) => v, _ => unreachable!() };
+        Value::Items(_1)
+        }
+        _ => unreachable!(),
+        }
+    }
+
+    fn output(&mut self, value: Value) {
+        match value {
+        Value::File(v) => v,
+        _ => unreachable!(),
+        }
+    }
+}
+/// Recognises the tokens of the grammar, taking the longest match and otherwise the token with priority.
+pub static LEXER: yaw::runtime::lexer::Lexer<TokenType> = yaw::runtime::lexer::Lexer {
+    modes: &[
+        yaw::runtime::lexer::Mode {
+            transitions: &[
+                &[('\t', '\n', 1), (' ', ' ', 1), ('#', '#', 2), (',', ',', 3), ('0', '9', 4), (':', ':', 5), ('=', '=', 6), ('[', '[', 7), (']', ']', 8), ('_', '_', 9), ('a', 'z', 9)],
+                &[('\t', '\n', 1), (' ', ' ', 1)],
+                &[('\0', '\t', 2), ('\u{b}', '\u{10ffff}', 2)],
+                &[],
+                &[('0', '9', 4)],
+                &[],
+                &[],
+                &[],
+                &[],
+                &[('_', '_', 9), ('a', 'z', 9)],
+            ],
+            accept: &[
+                &[],
+                &[TokenType::Whitespace],
+                &[TokenType::Comment],
+                &[TokenType::Comma],
+                &[TokenType::Number],
+                &[TokenType::Colon],
+                &[TokenType::Equals],
+                &[TokenType::LBracket],
+                &[TokenType::RBracket],
+                &[TokenType::Identifier],
+            ],
+        },
+    ],
+    actions: &[],
+    trivia: &[TokenType::Whitespace, TokenType::Comment],
+    eof: TokenType::Eof,
+};
+
+pub fn lex(source: &str) -> Result<Vec<Token<TokenType>>, yaw::runtime::lexer::LexError> {
+    LEXER.tokenize(source)
+}
+
+/// Lexes `source` while parsing, preferring the tokens the parser expects.
+pub fn scan(source: &str) -> yaw::runtime::lexer::Scanner<'_, TokenType> {
+    LEXER.scan(source)
+}
@@ -0,0 +1,255 @@
+// @generated by yaw 0.1.0, do not edit.
+// yaw-hash: f446bf3a44568ea6
+// yaw-messages-hash: fabc63d9dfa08650
+pub const GRAMMAR_HASH: u64 = 0xf446bf3a44568ea6;
+const _: () = yaw::build::assert_fresh(GRAMMAR_HASH, include_str!("lexed.yaw"));
+pub const MESSAGES_HASH: u64 = 0xfabc63d9dfa08650;
+const _: () = yaw::build::assert_fresh(MESSAGES_HASH, include_str!("lexed.messages"));
+
+use yaw::runtime::{Parse, ParseState, Step, Token, TokenSource};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TokenType {
+    Comment,
+    Eof,
+    Equals,
+    Identifier,
+    Let,
+    Number,
+    Plus,
+    Semicolon,
+    Whitespace,
+}
+
+impl std::fmt::Display for TokenType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            TokenType::Comment => "Comment",
+            TokenType::Eof => "Eof",
+            TokenType::Equals => "=",
+            TokenType::Identifier => "Identifier",
+            TokenType::Let => "let",
+            TokenType::Number => "Number",
+            TokenType::Plus => "+",
+            TokenType::Semicolon => ";",
+            TokenType::Whitespace => "Whitespace",
+        })
+    }
+}
+
+pub fn is_trivia(kind: &TokenType) -> bool {
+    matches!(kind, TokenType::Whitespace | TokenType::Comment)
+}
+
+pub enum Value {
+    Terminal(Token<TokenType>),
+    Program(()),
+    Statements(()),
+    Statement(()),
+    Expr(()),
+    Atom(()),
+}
+
+pub struct Parser<I = std::vec::IntoIter<Token<TokenType>>>(pub ParseState<I, TokenType, Value>);
+
+const EXPECTED: [&[TokenType]; 13] = [
+    &[TokenType::Eof, TokenType::Let],
+    &[TokenType::Eof, TokenType::Let],
+    &[TokenType::Eof, TokenType::Let],
+    &[TokenType::Identifier],
+    &[TokenType::Equals],
+    &[TokenType::Identifier, TokenType::Number],
+    &[TokenType::Plus, TokenType::Semicolon],
+    &[TokenType::Plus, TokenType::Semicolon],
+    &[TokenType::Plus, TokenType::Semicolon],
+    &[TokenType::Plus, TokenType::Semicolon],
+    &[TokenType::Eof, TokenType::Let],
+    &[TokenType::Identifier, TokenType::Number],
+    &[TokenType::Plus, TokenType::Semicolon],
+];
+
+impl<I: TokenSource<TokenType>> Parse<I, TokenType> for Parser<I> {
+    type Value = Value;
+    type Output = ();
+
+    fn parse_state_mut(&mut self) -> &mut ParseState<I, TokenType, Value> {
+        &mut self.0
+    }
+
+    fn parse_state(&self) -> &ParseState<I, TokenType, Value> {
+        &self.0
+    }
+
+    fn expected_terminals(&self, state: usize) -> &'static [TokenType] {
+        EXPECTED[state]
+    }
+
+    fn eof(&self) -> TokenType {
+        TokenType::Eof
+    }
+
+    fn error_terminal(&self) -> Option<TokenType> {
+        None
+    }
+
+    fn step(&self, state: usize, kind: TokenType) -> Option<Step> {
+        match (state, kind) {
+        (0, TokenType::Eof) => Some(Step::Reduce(2, 0)),
+        (0, TokenType::Let) => Some(Step::Reduce(2, 0)),
+        (1, TokenType::Eof) => Some(Step::Accept(0, 1)),
+        (1, TokenType::Let) => Some(Step::Shift(3)),
+        (2, TokenType::Eof) => Some(Step::Reduce(1, 2)),
+        (2, TokenType::Let) => Some(Step::Reduce(1, 2)),
+        (3, TokenType::Identifier) => Some(Step::Shift(4)),
+        (4, TokenType::Equals) => Some(Step::Shift(5)),
+        (5, TokenType::Identifier) => Some(Step::Shift(9)),
+        (5, TokenType::Number) => Some(Step::Shift(8)),
+        (6, TokenType::Plus) => Some(Step::Shift(11)),
+        (6, TokenType::Semicolon) => Some(Step::Shift(10)),
+        (7, TokenType::Plus) => Some(Step::Reduce(5, 1)),
+        (7, TokenType::Semicolon) => Some(Step::Reduce(5, 1)),
+        (8, TokenType::Plus) => Some(Step::Reduce(6, 1)),
+        (8, TokenType::Semicolon) => Some(Step::Reduce(6, 1)),
+        (9, TokenType::Plus) => Some(Step::Reduce(7, 1)),
+        (9, TokenType::Semicolon) => Some(Step::Reduce(7, 1)),
+        (10, TokenType::Eof) => Some(Step::Reduce(3, 5)),
+        (10, TokenType::Let) => Some(Step::Reduce(3, 5)),
+        (11, TokenType::Identifier) => Some(Step::Shift(9)),
+        (11, TokenType::Number) => Some(Step::Shift(8)),
+        (12, TokenType::Plus) => Some(Step::Reduce(4, 3)),
+        (12, TokenType::Semicolon) => Some(Step::Reduce(4, 3)),
+        _ => None,
+        }
+    }
+
+    fn goto_state(&self, state: usize, rule: usize) -> usize {
+        match (rule, state) {
+        (1, 0) => 1,
+        (2, 0) => 1,
+        (3, 1) => 2,
+        (4, 5) => 6,
+        (5, 5) => 6,
+        (6, 5) => 7,
+        (6, 11) => 12,
+        (7, 5) => 7,
+        (7, 11) => 12,
+        _ => panic!("unkown goto"),
+        }
+    }
+
+    fn message(&self, state: usize) -> Option<&'static str> {
+        match state {
+        3 => Some("A `let` statement needs a variable name, as in `let x = 1;`."),
+        5 => Some("Expected a number or a variable after `=`."),
+        _ => None,
+        }
+    }
+
+    fn token_value(&mut self, token: Token<TokenType>) -> Value {
+        Value::Terminal(token)
+    }
+
+    #[allow(unused_variables, unused_braces, clippy::just_underscores_and_digits, clippy::let_unit_value)]
+    fn reduce_values(&mut self, rule: usize, values: Vec<Value>) -> Value {
+        let mut values = values.into_iter();
+        match rule {
+        0 => {
+        let _1 = match values.next() { Some(Value::Statements(v)) => v, _ => unreachable!() };
+        Value::Program(_1)
+        }
+        1 => {
+        let _1 = match values.next() { Some(Value::Statements(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Statement(v)) => v, _ => unreachable!() };
+        Value::Statements(())
+        }
+        2 => {
+        Value::Statements(())
+        }
+        3 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _4 = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        let _5 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Statement(())
+        }
+        4 => {
+        let _1 = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Atom(v)) => v, _ => unreachable!() };
+        Value::Expr(())
+        }
+        5 => {
+        let _1 = match values.next() { Some(Value::Atom(v)) => v, _ => unreachable!() };
+        Value::Expr(_1)
+        }
+        6 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Atom(())
+        }
+        7 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Atom(())
+        }
+        _ => unreachable!(),
+        }
+    }
+
+    fn output(&mut self, value: Value) {
+        match value {
+        Value::Program(v) => v,
+        _ => unreachable!(),
+        }
+    }
+}
+/// Recognises the tokens of the grammar, taking the longest match and otherwise the token with priority.
+pub static LEXER: yaw::runtime::lexer::Lexer<TokenType> = yaw::runtime::lexer::Lexer {
+    modes: &[
+        yaw::runtime::lexer::Mode {
+            transitions: &[
+                &[('\t', '\n', 1), (' ', ' ', 1), ('+', '+', 2), ('/', '/', 3), ('0', '9', 4), (';', ';', 5), ('=', '=', 6), ('_', '_', 7), ('a', 'k', 7), ('l', 'l', 8), ('m', 'z', 7)],
+                &[('\t', '\n', 1), (' ', ' ', 1)],
+                &[],
+                &[('/', '/', 9)],
+                &[('.', '.', 10), ('0', '9', 4)],
+                &[],
+                &[],
+                &[('0', '9', 7), ('_', '_', 7), ('a', 'z', 7)],
+                &[('0', '9', 7), ('_', '_', 7), ('a', 'd', 7), ('e', 'e', 11), ('f', 'z', 7)],
+                &[('\0', '\t', 9), ('\u{b}', '\u{10ffff}', 9)],
+                &[('0', '9', 12)],
+                &[('0', '9', 7), ('_', '_', 7), ('a', 's', 7), ('t', 't', 13), ('u', 'z', 7)],
+                &[('0', '9', 12)],
+                &[('0', '9', 7), ('_', '_', 7), ('a', 'z', 7)],
+            ],
+            accept: &[
+                &[],
+                &[TokenType::Whitespace],
+                &[TokenType::Plus],
+                &[],
+                &[TokenType::Number],
+                &[TokenType::Semicolon],
+                &[TokenType::Equals],
+                &[TokenType::Identifier],
+                &[TokenType::Identifier],
+                &[TokenType::Comment],
+                &[],
+                &[TokenType::Identifier],
+                &[TokenType::Number],
+                &[TokenType::Let, TokenType::Identifier],
+            ],
+        },
+    ],
+    actions: &[],
+    trivia: &[TokenType::Whitespace, TokenType::Comment],
+    eof: TokenType::Eof,
+};
+
+pub fn lex(source: &str) -> Result<Vec<Token<TokenType>>, yaw::runtime::lexer::LexError> {
+    LEXER.tokenize(source)
+}
+
+/// Lexes `source` while parsing, preferring the tokens the parser expects.
+pub fn scan(source: &str) -> yaw::runtime::lexer::Scanner<'_, TokenType> {
+    LEXER.scan(source)
+}
@@ -0,0 +1,259 @@
+// @generated by yaw 0.1.0, do not edit.
+// yaw-hash: 0ec4bf03fe05a340
+pub const GRAMMAR_HASH: u64 = 0x0ec4bf03fe05a340;
+const _: () = yaw::build::assert_fresh(GRAMMAR_HASH, include_str!("lossless.yaw"));
+
+use yaw::runtime::{Parse, ParseState, Step, Token, TokenSource, cst};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TokenType {
+    Comment,
+    Eof,
+    Equals,
+    Identifier,
+    LParen,
+    Number,
+    Plus,
+    RParen,
+    Semicolon,
+    Whitespace,
+}
+
+impl std::fmt::Display for TokenType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            TokenType::Comment => "Comment",
+            TokenType::Eof => "Eof",
+            TokenType::Equals => "Equals",
+            TokenType::Identifier => "Identifier",
+            TokenType::LParen => "LParen",
+            TokenType::Number => "Number",
+            TokenType::Plus => "Plus",
+            TokenType::RParen => "RParen",
+            TokenType::Semicolon => "Semicolon",
+            TokenType::Whitespace => "Whitespace",
+        })
+    }
+}
+
+pub fn is_trivia(kind: &TokenType) -> bool {
+    matches!(kind, TokenType::Whitespace | TokenType::Comment)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    File,
+    Statements,
+    Statement,
+    Expr,
+    Atom,
+    Error,
+    Missing,
+}
+
+impl cst::RecoveryKinds for NodeKind {
+    fn error() -> Self {
+        NodeKind::Error
+    }
+
+    fn missing() -> Self {
+        NodeKind::Missing
+    }
+}
+
+pub type Value = Vec<cst::GreenElement<TokenType, NodeKind>>;
+
+pub struct Parser<'s, I = std::vec::IntoIter<Token<TokenType>>>(pub ParseState<I, TokenType, Value>, pub &'s str);
+
+const EXPECTED: [&[TokenType]; 23] = [
+    &[TokenType::Eof, TokenType::Identifier],
+    &[TokenType::Eof, TokenType::Identifier],
+    &[TokenType::Eof, TokenType::Identifier],
+    &[TokenType::Equals],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Plus, TokenType::Semicolon],
+    &[TokenType::Plus, TokenType::Semicolon],
+    &[TokenType::Plus, TokenType::Semicolon],
+    &[TokenType::Plus, TokenType::Semicolon],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Eof, TokenType::Identifier],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Plus, TokenType::RParen],
+    &[TokenType::Plus, TokenType::RParen],
+    &[TokenType::Plus, TokenType::RParen],
+    &[TokenType::Plus, TokenType::RParen],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Plus, TokenType::Semicolon],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Plus, TokenType::Semicolon],
+    &[TokenType::Plus, TokenType::RParen],
+    &[TokenType::Plus, TokenType::RParen],
+    &[TokenType::Plus, TokenType::RParen],
+];
+
+const FOLLOWS: [&[(usize, &[TokenType])]; 23] = [
+    &[(1, &[TokenType::Eof, TokenType::Identifier])],
+    &[(2, &[TokenType::Eof, TokenType::Identifier])],
+    &[],
+    &[],
+    &[(5, &[TokenType::Plus, TokenType::RParen, TokenType::Semicolon]), (6, &[TokenType::Plus, TokenType::RParen, TokenType::Semicolon])],
+    &[],
+    &[],
+    &[],
+    &[],
+    &[(12, &[TokenType::Plus, TokenType::RParen, TokenType::Semicolon]), (13, &[TokenType::Plus, TokenType::RParen, TokenType::Semicolon])],
+    &[],
+    &[(17, &[TokenType::Plus, TokenType::RParen, TokenType::Semicolon])],
+    &[],
+    &[],
+    &[],
+    &[],
+    &[(13, &[TokenType::Plus, TokenType::RParen, TokenType::Semicolon]), (20, &[TokenType::Plus, TokenType::RParen, TokenType::Semicolon])],
+    &[],
+    &[(21, &[TokenType::Plus, TokenType::RParen, TokenType::Semicolon])],
+    &[],
+    &[],
+    &[],
+    &[],
+];
+
+impl<'s, I: TokenSource<TokenType>> Parse<I, TokenType> for Parser<'s, I> {
+    type Value = Value;
+    type Output = cst::SyntaxNode<TokenType, NodeKind>;
+
+    fn parse_state_mut(&mut self) -> &mut ParseState<I, TokenType, Value> {
+        &mut self.0
+    }
+
+    fn parse_state(&self) -> &ParseState<I, TokenType, Value> {
+        &self.0
+    }
+
+    fn expected_terminals(&self, state: usize) -> &'static [TokenType] {
+        EXPECTED[state]
+    }
+
+    fn eof(&self) -> TokenType {
+        TokenType::Eof
+    }
+
+    fn error_terminal(&self) -> Option<TokenType> {
+        None
+    }
+
+    fn step(&self, state: usize, kind: TokenType) -> Option<Step> {
+        match (state, kind) {
+        (0, TokenType::Eof) => Some(Step::Reduce(2, 0)),
+        (0, TokenType::Identifier) => Some(Step::Reduce(2, 0)),
+        (1, TokenType::Eof) => Some(Step::Accept(0, 1)),
+        (1, TokenType::Identifier) => Some(Step::Shift(3)),
+        (2, TokenType::Eof) => Some(Step::Reduce(1, 2)),
+        (2, TokenType::Identifier) => Some(Step::Reduce(1, 2)),
+        (3, TokenType::Equals) => Some(Step::Shift(4)),
+        (4, TokenType::Identifier) => Some(Step::Shift(8)),
+        (4, TokenType::LParen) => Some(Step::Shift(9)),
+        (4, TokenType::Number) => Some(Step::Shift(7)),
+        (5, TokenType::Plus) => Some(Step::Shift(11)),
+        (5, TokenType::Semicolon) => Some(Step::Shift(10)),
+        (6, TokenType::Plus) => Some(Step::Reduce(5, 1)),
+        (6, TokenType::Semicolon) => Some(Step::Reduce(5, 1)),
+        (7, TokenType::Plus) => Some(Step::Reduce(6, 1)),
+        (7, TokenType::Semicolon) => Some(Step::Reduce(6, 1)),
+        (8, TokenType::Plus) => Some(Step::Reduce(7, 1)),
+        (8, TokenType::Semicolon) => Some(Step::Reduce(7, 1)),
+        (9, TokenType::Identifier) => Some(Step::Shift(15)),
+        (9, TokenType::LParen) => Some(Step::Shift(16)),
+        (9, TokenType::Number) => Some(Step::Shift(14)),
+        (10, TokenType::Eof) => Some(Step::Reduce(3, 4)),
+        (10, TokenType::Identifier) => Some(Step::Reduce(3, 4)),
+        (11, TokenType::Identifier) => Some(Step::Shift(8)),
+        (11, TokenType::LParen) => Some(Step::Shift(9)),
+        (11, TokenType::Number) => Some(Step::Shift(7)),
+        (12, TokenType::Plus) => Some(Step::Shift(18)),
+        (12, TokenType::RParen) => Some(Step::Shift(19)),
+        (13, TokenType::Plus) => Some(Step::Reduce(5, 1)),
+        (13, TokenType::RParen) => Some(Step::Reduce(5, 1)),
+        (14, TokenType::Plus) => Some(Step::Reduce(6, 1)),
+        (14, TokenType::RParen) => Some(Step::Reduce(6, 1)),
+        (15, TokenType::Plus) => Some(Step::Reduce(7, 1)),
+        (15, TokenType::RParen) => Some(Step::Reduce(7, 1)),
+        (16, TokenType::Identifier) => Some(Step::Shift(15)),
+        (16, TokenType::LParen) => Some(Step::Shift(16)),
+        (16, TokenType::Number) => Some(Step::Shift(14)),
+        (17, TokenType::Plus) => Some(Step::Reduce(4, 3)),
+        (17, TokenType::Semicolon) => Some(Step::Reduce(4, 3)),
+        (18, TokenType::Identifier) => Some(Step::Shift(15)),
+        (18, TokenType::LParen) => Some(Step::Shift(16)),
+        (18, TokenType::Number) => Some(Step::Shift(14)),
+        (19, TokenType::Plus) => Some(Step::Reduce(8, 3)),
+        (19, TokenType::Semicolon) => Some(Step::Reduce(8, 3)),
+        (20, TokenType::Plus) => Some(Step::Shift(18)),
+        (20, TokenType::RParen) => Some(Step::Shift(22)),
+        (21, TokenType::Plus) => Some(Step::Reduce(4, 3)),
+        (21, TokenType::RParen) => Some(Step::Reduce(4, 3)),
+        (22, TokenType::Plus) => Some(Step::Reduce(8, 3)),
+        (22, TokenType::RParen) => Some(Step::Reduce(8, 3)),
+        _ => None,
+        }
+    }
+
+    fn goto_state(&self, state: usize, rule: usize) -> usize {
+        match (rule, state) {
+        (1, 0) => 1,
+        (2, 0) => 1,
+        (3, 1) => 2,
+        (4, 4) => 5,
+        (4, 9) => 12,
+        (4, 16) => 20,
+        (5, 4) => 5,
+        (5, 9) => 12,
+        (5, 16) => 20,
+        (6, 4) => 6,
+        (6, 9) => 13,
+        (6, 11) => 17,
+        (6, 16) => 13,
+        (6, 18) => 21,
+        (7, 4) => 6,
+        (7, 9) => 13,
+        (7, 11) => 17,
+        (7, 16) => 13,
+        (7, 18) => 21,
+        (8, 4) => 6,
+        (8, 9) => 13,
+        (8, 11) => 17,
+        (8, 16) => 13,
+        (8, 18) => 21,
+        _ => panic!("unkown goto"),
+        }
+    }
+
+    fn follow_gotos(&self, state: usize) -> &'static [(usize, &'static [TokenType])] {
+        FOLLOWS[state]
+    }
+
+    fn token_value(&mut self, token: Token<TokenType>) -> Value {
+        let trivia = self.0.take_trivia();
+        cst::token_elements(self.1, trivia, token)
+    }
+
+    fn reduce_values(&mut self, rule: usize, values: Vec<Value>) -> Value {
+        let kind = match rule {
+        0 => NodeKind::File,
+        1 => NodeKind::Statements,
+        2 => NodeKind::Statements,
+        3 => NodeKind::Statement,
+        4 => NodeKind::Expr,
+        5 => NodeKind::Expr,
+        6 => NodeKind::Atom,
+        7 => NodeKind::Atom,
+        8 => NodeKind::Atom,
+        _ => unreachable!(),
+        };
+        cst::node_elements(kind, values, is_trivia)
+    }
+
+    fn output(&mut self, value: Value) -> cst::SyntaxNode<TokenType, NodeKind> {
+        let trivia = self.0.take_trivia();
+        cst::root(self.1, value, trivia)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,348 @@
+// @generated by yaw 0.1.0, do not edit.
+// yaw-hash: 28dc293df50bfe39
+pub const GRAMMAR_HASH: u64 = 0x28dc293df50bfe39;
+const _: () = yaw::build::assert_fresh(GRAMMAR_HASH, include_str!("modes.yaw"));
+
+use yaw::runtime::{Parse, ParseState, Step, Token, TokenSource};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TokenType {
+    Comment,
+    EndQuote,
+    Eof,
+    HeredocBody,
+    HeredocStart,
+    Identifier,
+    InterpolationStart,
+    Quote,
+    RBrace,
+    Text,
+    Whitespace,
+}
+
+impl std::fmt::Display for TokenType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            TokenType::Comment => "/*",
+            TokenType::EndQuote => "\"",
+            TokenType::Eof => "Eof",
+            TokenType::HeredocBody => "HeredocBody",
+            TokenType::HeredocStart => "HeredocStart",
+            TokenType::Identifier => "Identifier",
+            TokenType::InterpolationStart => "${",
+            TokenType::Quote => "\"",
+            TokenType::RBrace => "}",
+            TokenType::Text => "Text",
+            TokenType::Whitespace => "Whitespace",
+        })
+    }
+}
+
+pub fn is_trivia(kind: &TokenType) -> bool {
+    matches!(kind, TokenType::Whitespace | TokenType::Comment)
+}
+
+pub enum Value {
+    Terminal(Token<TokenType>),
+    Program(()),
+    Items(()),
+    Item(()),
+    String(()),
+    Parts(()),
+    Part(()),
+    Heredoc(()),
+}
+
+pub struct Parser<I = std::vec::IntoIter<Token<TokenType>>>(pub ParseState<I, TokenType, Value>);
+
+const EXPECTED: [&[TokenType]; 24] = [
+    &[TokenType::Eof, TokenType::HeredocStart, TokenType::Identifier, TokenType::Quote],
+    &[TokenType::Eof, TokenType::HeredocStart, TokenType::Identifier, TokenType::Quote],
+    &[TokenType::Eof, TokenType::HeredocStart, TokenType::Identifier, TokenType::Quote],
+    &[TokenType::Eof, TokenType::HeredocStart, TokenType::Identifier, TokenType::Quote],
+    &[TokenType::Eof, TokenType::HeredocStart, TokenType::Identifier, TokenType::Quote],
+    &[TokenType::Eof, TokenType::HeredocStart, TokenType::Identifier, TokenType::Quote],
+    &[TokenType::EndQuote, TokenType::InterpolationStart, TokenType::Text],
+    &[TokenType::HeredocBody],
+    &[TokenType::EndQuote, TokenType::InterpolationStart, TokenType::Text],
+    &[TokenType::Eof, TokenType::HeredocStart, TokenType::Identifier, TokenType::Quote],
+    &[TokenType::Eof, TokenType::HeredocStart, TokenType::Identifier, TokenType::Quote],
+    &[TokenType::EndQuote, TokenType::InterpolationStart, TokenType::Text],
+    &[TokenType::EndQuote, TokenType::InterpolationStart, TokenType::Text],
+    &[TokenType::HeredocStart, TokenType::Identifier, TokenType::Quote],
+    &[TokenType::RBrace],
+    &[TokenType::RBrace],
+    &[TokenType::RBrace],
+    &[TokenType::EndQuote, TokenType::InterpolationStart, TokenType::Text],
+    &[TokenType::RBrace],
+    &[TokenType::HeredocBody],
+    &[TokenType::EndQuote, TokenType::InterpolationStart, TokenType::Text],
+    &[TokenType::EndQuote, TokenType::InterpolationStart, TokenType::Text],
+    &[TokenType::RBrace],
+    &[TokenType::RBrace],
+];
+
+impl<I: TokenSource<TokenType>> Parse<I, TokenType> for Parser<I> {
+    type Value = Value;
+    type Output = ();
+
+    fn parse_state_mut(&mut self) -> &mut ParseState<I, TokenType, Value> {
+        &mut self.0
+    }
+
+    fn parse_state(&self) -> &ParseState<I, TokenType, Value> {
+        &self.0
+    }
+
+    fn expected_terminals(&self, state: usize) -> &'static [TokenType] {
+        EXPECTED[state]
+    }
+
+    fn eof(&self) -> TokenType {
+        TokenType::Eof
+    }
+
+    fn error_terminal(&self) -> Option<TokenType> {
+        None
+    }
+
+    fn step(&self, state: usize, kind: TokenType) -> Option<Step> {
+        match (state, kind) {
+        (0, TokenType::Eof) => Some(Step::Reduce(2, 0)),
+        (0, TokenType::HeredocStart) => Some(Step::Reduce(2, 0)),
+        (0, TokenType::Identifier) => Some(Step::Reduce(2, 0)),
+        (0, TokenType::Quote) => Some(Step::Reduce(2, 0)),
+        (1, TokenType::Eof) => Some(Step::Accept(0, 1)),
+        (1, TokenType::HeredocStart) => Some(Step::Shift(7)),
+        (1, TokenType::Identifier) => Some(Step::Shift(3)),
+        (1, TokenType::Quote) => Some(Step::Shift(6)),
+        (2, TokenType::Eof) => Some(Step::Reduce(1, 2)),
+        (2, TokenType::HeredocStart) => Some(Step::Reduce(1, 2)),
+        (2, TokenType::Identifier) => Some(Step::Reduce(1, 2)),
+        (2, TokenType::Quote) => Some(Step::Reduce(1, 2)),
+        (3, TokenType::Eof) => Some(Step::Reduce(3, 1)),
+        (3, TokenType::HeredocStart) => Some(Step::Reduce(3, 1)),
+        (3, TokenType::Identifier) => Some(Step::Reduce(3, 1)),
+        (3, TokenType::Quote) => Some(Step::Reduce(3, 1)),
+        (4, TokenType::Eof) => Some(Step::Reduce(4, 1)),
+        (4, TokenType::HeredocStart) => Some(Step::Reduce(4, 1)),
+        (4, TokenType::Identifier) => Some(Step::Reduce(4, 1)),
+        (4, TokenType::Quote) => Some(Step::Reduce(4, 1)),
+        (5, TokenType::Eof) => Some(Step::Reduce(5, 1)),
+        (5, TokenType::HeredocStart) => Some(Step::Reduce(5, 1)),
+        (5, TokenType::Identifier) => Some(Step::Reduce(5, 1)),
+        (5, TokenType::Quote) => Some(Step::Reduce(5, 1)),
+        (6, TokenType::EndQuote) => Some(Step::Reduce(8, 0)),
+        (6, TokenType::InterpolationStart) => Some(Step::Reduce(8, 0)),
+        (6, TokenType::Text) => Some(Step::Reduce(8, 0)),
+        (7, TokenType::HeredocBody) => Some(Step::Shift(9)),
+        (8, TokenType::EndQuote) => Some(Step::Shift(10)),
+        (8, TokenType::InterpolationStart) => Some(Step::Shift(13)),
+        (8, TokenType::Text) => Some(Step::Shift(12)),
+        (9, TokenType::Eof) => Some(Step::Reduce(11, 2)),
+        (9, TokenType::HeredocStart) => Some(Step::Reduce(11, 2)),
+        (9, TokenType::Identifier) => Some(Step::Reduce(11, 2)),
+        (9, TokenType::Quote) => Some(Step::Reduce(11, 2)),
+        (10, TokenType::Eof) => Some(Step::Reduce(6, 3)),
+        (10, TokenType::HeredocStart) => Some(Step::Reduce(6, 3)),
+        (10, TokenType::Identifier) => Some(Step::Reduce(6, 3)),
+        (10, TokenType::Quote) => Some(Step::Reduce(6, 3)),
+        (11, TokenType::EndQuote) => Some(Step::Reduce(7, 2)),
+        (11, TokenType::InterpolationStart) => Some(Step::Reduce(7, 2)),
+        (11, TokenType::Text) => Some(Step::Reduce(7, 2)),
+        (12, TokenType::EndQuote) => Some(Step::Reduce(9, 1)),
+        (12, TokenType::InterpolationStart) => Some(Step::Reduce(9, 1)),
+        (12, TokenType::Text) => Some(Step::Reduce(9, 1)),
+        (13, TokenType::HeredocStart) => Some(Step::Shift(19)),
+        (13, TokenType::Identifier) => Some(Step::Shift(14)),
+        (13, TokenType::Quote) => Some(Step::Shift(17)),
+        (14, TokenType::RBrace) => Some(Step::Reduce(3, 1)),
+        (15, TokenType::RBrace) => Some(Step::Reduce(4, 1)),
+        (16, TokenType::RBrace) => Some(Step::Reduce(5, 1)),
+        (17, TokenType::EndQuote) => Some(Step::Reduce(8, 0)),
+        (17, TokenType::InterpolationStart) => Some(Step::Reduce(8, 0)),
+        (17, TokenType::Text) => Some(Step::Reduce(8, 0)),
+        (18, TokenType::RBrace) => Some(Step::Shift(21)),
+        (19, TokenType::HeredocBody) => Some(Step::Shift(22)),
+        (20, TokenType::EndQuote) => Some(Step::Shift(23)),
+        (20, TokenType::InterpolationStart) => Some(Step::Shift(13)),
+        (20, TokenType::Text) => Some(Step::Shift(12)),
+        (21, TokenType::EndQuote) => Some(Step::Reduce(10, 3)),
+        (21, TokenType::InterpolationStart) => Some(Step::Reduce(10, 3)),
+        (21, TokenType::Text) => Some(Step::Reduce(10, 3)),
+        (22, TokenType::RBrace) => Some(Step::Reduce(11, 2)),
+        (23, TokenType::RBrace) => Some(Step::Reduce(6, 3)),
+        _ => None,
+        }
+    }
+
+    fn goto_state(&self, state: usize, rule: usize) -> usize {
+        match (rule, state) {
+        (1, 0) => 1,
+        (2, 0) => 1,
+        (3, 1) => 2,
+        (3, 13) => 18,
+        (4, 1) => 2,
+        (4, 13) => 18,
+        (5, 1) => 2,
+        (5, 13) => 18,
+        (6, 1) => 4,
+        (6, 13) => 15,
+        (7, 6) => 8,
+        (7, 17) => 20,
+        (8, 6) => 8,
+        (8, 17) => 20,
+        (9, 8) => 11,
+        (9, 20) => 11,
+        (10, 8) => 11,
+        (10, 20) => 11,
+        (11, 1) => 5,
+        (11, 13) => 16,
+        _ => panic!("unkown goto"),
+        }
+    }
+
+    fn token_value(&mut self, token: Token<TokenType>) -> Value {
+        Value::Terminal(token)
+    }
+
+    #[allow(unused_variables, unused_braces, clippy::just_underscores_and_digits, clippy::let_unit_value)]
+    fn reduce_values(&mut self, rule: usize, values: Vec<Value>) -> Value {
+        let mut values = values.into_iter();
+        match rule {
+        0 => {
+        let _1 = match values.next() { Some(Value::Items(v)) => v, _ => unreachable!() };
+        Value::Program(_1)
+        }
+        1 => {
+        let _1 = match values.next() { Some(Value::Items(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Item(v)) => v, _ => unreachable!() };
+        Value::Items(())
+        }
+        2 => {
+        Value::Items(())
+        }
+        3 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Item(())
+        }
+        4 => {
+        let _1 = match values.next() { Some(Value::String(v)) => v, _ => unreachable!() };
+        Value::Item(_1)
+        }
+        5 => {
+        let _1 = match values.next() { Some(Value::Heredoc(v)) => v, _ => unreachable!() };
+        Value::Item(_1)
+        }
+        6 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Parts(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::String(())
+        }
+        7 => {
+        let _1 = match values.next() { Some(Value::Parts(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Part(v)) => v, _ => unreachable!() };
+        Value::Parts(())
+        }
+        8 => {
+        Value::Parts(())
+        }
+        9 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Part(())
+        }
+        10 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Item(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Part(())
+        }
+        11 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Heredoc(())
+        }
+        _ => unreachable!(),
+        }
+    }
+
+    fn output(&mut self, value: Value) {
+        match value {
+        Value::Program(v) => v,
+        _ => unreachable!(),
+        }
+    }
+}
+/// The modes of the lexer, which can be entered from lexer callbacks.
+pub mod mode {
+    pub const INITIAL: usize = 0;
+    pub const STRING: usize = 1;
+}
+
+/// Recognises the tokens of the grammar, taking the longest match and otherwise the token with priority.
+pub static LEXER: yaw::runtime::lexer::Lexer<TokenType> = yaw::runtime::lexer::Lexer {
+    modes: &[
+        yaw::runtime::lexer::Mode {
+            transitions: &[
+                &[('\n', '\n', 1), (' ', ' ', 1), ('"', '"', 2), ('/', '/', 3), ('<', '<', 4), ('a', 'z', 5), ('}', '}', 6)],
+                &[('\n', '\n', 1), (' ', ' ', 1)],
+                &[],
+                &[('*', '*', 7)],
+                &[('<', '<', 8)],
+                &[('a', 'z', 5)],
+                &[],
+                &[],
+                &[('A', 'Z', 9)],
+                &[('\n', '\n', 10), ('A', 'Z', 9)],
+                &[],
+            ],
+            accept: &[
+                &[],
+                &[TokenType::Whitespace],
+                &[TokenType::Quote],
+                &[],
+                &[],
+                &[TokenType::Identifier],
+                &[TokenType::RBrace],
+                &[TokenType::Comment],
+                &[],
+                &[],
+                &[TokenType::HeredocStart],
+            ],
+        },
+        yaw::runtime::lexer::Mode {
+            transitions: &[
+                &[('\0', '!', 1), ('"', '"', 2), ('#', '#', 1), ('$', '$', 3), ('%', '\u{10ffff}', 1)],
+                &[('\0', '!', 1), ('#', '#', 1), ('%', '\u{10ffff}', 1)],
+                &[],
+                &[('{', '{', 4)],
+                &[],
+            ],
+            accept: &[
+                &[],
+                &[TokenType::Text],
+                &[TokenType::EndQuote],
+                &[],
+                &[TokenType::InterpolationStart],
+            ],
+        },
+    ],
+    actions: &[
+        (TokenType::Quote, yaw::runtime::lexer::ModeAction::Push(1)),
+        (TokenType::RBrace, yaw::runtime::lexer::ModeAction::Pop),
+        (TokenType::InterpolationStart, yaw::runtime::lexer::ModeAction::Push(0)),
+        (TokenType::EndQuote, yaw::runtime::lexer::ModeAction::Pop),
+    ],
+    trivia: &[TokenType::Whitespace, TokenType::Comment],
+    eof: TokenType::Eof,
+};
+
+pub fn lex(source: &str) -> Result<Vec<Token<TokenType>>, yaw::runtime::lexer::LexError> {
+    LEXER.tokenize(source)
+}
+
+/// Lexes `source` while parsing, preferring the tokens the parser expects.
+pub fn scan(source: &str) -> yaw::runtime::lexer::Scanner<'_, TokenType> {
+    LEXER.scan(source)
+}
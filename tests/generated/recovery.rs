@@ -0,0 +1,209 @@
+// @generated by yaw 0.1.0, do not edit.
+// yaw-hash: c1bf850cd30f0cdc
+pub const GRAMMAR_HASH: u64 = 0xc1bf850cd30f0cdc;
+const _: () = yaw::build::assert_fresh(GRAMMAR_HASH, include_str!("recovery.yaw"));
+
+use yaw::runtime::{Parse, ParseState, Step, Token, TokenSource};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TokenType {
+    Eof,
+    Equals,
+    Error,
+    Identifier,
+    Let,
+    Number,
+    Semicolon,
+    Whitespace,
+}
+
+impl std::fmt::Display for TokenType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            TokenType::Eof => "Eof",
+            TokenType::Equals => "=",
+            TokenType::Error => "error",
+            TokenType::Identifier => "Identifier",
+            TokenType::Let => "let",
+            TokenType::Number => "Number",
+            TokenType::Semicolon => ";",
+            TokenType::Whitespace => "Whitespace",
+        })
+    }
+}
+
+pub fn is_trivia(kind: &TokenType) -> bool {
+    matches!(kind, TokenType::Whitespace)
+}
+
+pub enum Value {
+    Terminal(Token<TokenType>),
+    Program(Vec<super::Statement>),
+    Statements(Vec<super::Statement>),
+    Statement(super::Statement),
+}
+
+pub struct Parser<I = std::vec::IntoIter<Token<TokenType>>>(pub ParseState<I, TokenType, Value>);
+
+const EXPECTED: [&[TokenType]; 10] = [
+    &[TokenType::Eof, TokenType::Error, TokenType::Let],
+    &[TokenType::Eof, TokenType::Error, TokenType::Let],
+    &[TokenType::Eof, TokenType::Error, TokenType::Let],
+    &[TokenType::Identifier],
+    &[TokenType::Semicolon],
+    &[TokenType::Equals],
+    &[TokenType::Eof, TokenType::Error, TokenType::Let],
+    &[TokenType::Number],
+    &[TokenType::Semicolon],
+    &[TokenType::Eof, TokenType::Error, TokenType::Let],
+];
+
+impl<I: TokenSource<TokenType>> Parse<I, TokenType> for Parser<I> {
+    type Value = Value;
+    type Output = Vec<super::Statement>;
+
+    fn parse_state_mut(&mut self) -> &mut ParseState<I, TokenType, Value> {
+        &mut self.0
+    }
+
+    fn parse_state(&self) -> &ParseState<I, TokenType, Value> {
+        &self.0
+    }
+
+    fn expected_terminals(&self, state: usize) -> &'static [TokenType] {
+        EXPECTED[state]
+    }
+
+    fn eof(&self) -> TokenType {
+        TokenType::Eof
+    }
+
+    fn error_terminal(&self) -> Option<TokenType> {
+        Some(TokenType::Error)
+    }
+
+    fn step(&self, state: usize, kind: TokenType) -> Option<Step> {
+        match (state, kind) {
+        (0, TokenType::Eof) => Some(Step::Reduce(2, 0)),
+        (0, TokenType::Error) => Some(Step::Reduce(2, 0)),
+        (0, TokenType::Let) => Some(Step::Reduce(2, 0)),
+        (1, TokenType::Eof) => Some(Step::Accept(0, 1)),
+        (1, TokenType::Error) => Some(Step::Shift(4)),
+        (1, TokenType::Let) => Some(Step::Shift(3)),
+        (2, TokenType::Eof) => Some(Step::Reduce(1, 2)),
+        (2, TokenType::Error) => Some(Step::Reduce(1, 2)),
+        (2, TokenType::Let) => Some(Step::Reduce(1, 2)),
+        (3, TokenType::Identifier) => Some(Step::Shift(5)),
+        (4, TokenType::Semicolon) => Some(Step::Shift(6)),
+        (5, TokenType::Equals) => Some(Step::Shift(7)),
+        (6, TokenType::Eof) => Some(Step::Reduce(4, 2)),
+        (6, TokenType::Error) => Some(Step::Reduce(4, 2)),
+        (6, TokenType::Let) => Some(Step::Reduce(4, 2)),
+        (7, TokenType::Number) => Some(Step::Shift(8)),
+        (8, TokenType::Semicolon) => Some(Step::Shift(9)),
+        (9, TokenType::Eof) => Some(Step::Reduce(3, 5)),
+        (9, TokenType::Error) => Some(Step::Reduce(3, 5)),
+        (9, TokenType::Let) => Some(Step::Reduce(3, 5)),
+        _ => None,
+        }
+    }
+
+    fn goto_state(&self, state: usize, rule: usize) -> usize {
+        match (rule, state) {
+        (1, 0) => 1,
+        (2, 0) => 1,
+        (3, 1) => 2,
+        (4, 1) => 2,
+        _ => panic!("unkown goto"),
+        }
+    }
+
+    fn token_value(&mut self, token: Token<TokenType>) -> Value {
+        Value::Terminal(token)
+    }
+
+    #[allow(unused_variables, unused_braces, clippy::just_underscores_and_digits, clippy::let_unit_value)]
+    fn reduce_values(&mut self, rule: usize, values: Vec<Value>) -> Value {
+        let mut values = values.into_iter();
+        match rule {
+        0 => {
+        let _1 = match values.next() { Some(Value::Statements(v)) => v, _ => unreachable!() };
+        Value::Program(_1)
+        }
+        1 => {
+        let list = match values.next() { Some(Value::Statements(v)) => v, _ => unreachable!() };
+        let statement = match values.next() { Some(Value::Statement(v)) => v, _ => unreachable!() };
+        Value::Statements({
+                let mut list = list;
+                list.push(statement);
+                list
+            })
+        }
+        2 => {
+        Value::Statements({ Vec::new() })
+        }
+        3 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let name = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _4 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _5 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Statement({ super::Statement::Let(name.span) })
+        }
+        4 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Statement({ super::Statement::Invalid(_1.span) })
+        }
+        _ => unreachable!(),
+        }
+    }
+
+    fn output(&mut self, value: Value) -> Vec<super::Statement> {
+        match value {
+        Value::Program(v) => v,
+        _ => unreachable!(),
+        }
+    }
+}
+/// Recognises the tokens of the grammar, taking the longest match and otherwise the token with priority.
+pub static LEXER: yaw::runtime::lexer::Lexer<TokenType> = yaw::runtime::lexer::Lexer {
+    modes: &[
+        yaw::runtime::lexer::Mode {
+            transitions: &[
+                &[('\n', '\n', 1), (' ', ' ', 1), ('0', '9', 2), (';', ';', 3), ('=', '=', 4), ('a', 'k', 5), ('l', 'l', 6), ('m', 'z', 5)],
+                &[('\n', '\n', 1), (' ', ' ', 1)],
+                &[('0', '9', 2)],
+                &[],
+                &[],
+                &[('a', 'z', 5)],
+                &[('a', 'd', 5), ('e', 'e', 7), ('f', 'z', 5)],
+                &[('a', 's', 5), ('t', 't', 8), ('u', 'z', 5)],
+                &[('a', 'z', 5)],
+            ],
+            accept: &[
+                &[],
+                &[TokenType::Whitespace],
+                &[TokenType::Number],
+                &[TokenType::Semicolon],
+                &[TokenType::Equals],
+                &[TokenType::Identifier],
+                &[TokenType::Identifier],
+                &[TokenType::Identifier],
+                &[TokenType::Let, TokenType::Identifier],
+            ],
+        },
+    ],
+    actions: &[],
+    trivia: &[TokenType::Whitespace],
+    eof: TokenType::Eof,
+};
+
+pub fn lex(source: &str) -> Result<Vec<Token<TokenType>>, yaw::runtime::lexer::LexError> {
+    LEXER.tokenize(source)
+}
+
+/// Lexes `source` while parsing, preferring the tokens the parser expects.
+pub fn scan(source: &str) -> yaw::runtime::lexer::Scanner<'_, TokenType> {
+    LEXER.scan(source)
+}
@@ -0,0 +1,466 @@
+// @generated by yaw 0.1.0, do not edit.
+// yaw-hash: 9ddfcf20440f9baa
+pub const GRAMMAR_HASH: u64 = 0x9ddfcf20440f9baa;
+const _: () = yaw::build::assert_fresh(GRAMMAR_HASH, include_str!("tree.yaw"));
+
+use yaw::runtime::{Parse, ParseState, Step, Token, TokenSource};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TokenType {
+    Comma,
+    Eof,
+    Equals,
+    Identifier,
+    LParen,
+    Number,
+    Plus,
+    Print,
+    RParen,
+    Semicolon,
+}
+
+impl std::fmt::Display for TokenType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(match self {
+            TokenType::Comma => "Comma",
+            TokenType::Eof => "Eof",
+            TokenType::Equals => "Equals",
+            TokenType::Identifier => "Identifier",
+            TokenType::LParen => "LParen",
+            TokenType::Number => "Number",
+            TokenType::Plus => "Plus",
+            TokenType::Print => "Print",
+            TokenType::RParen => "RParen",
+            TokenType::Semicolon => "Semicolon",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Program {
+    pub statements: Statements,
+}
+
+pub type Statements = Vec<Statement>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement {
+    Assign {
+        name: Token<TokenType>,
+        value: Expr,
+    }
+    ,
+    Print {
+        print: Token<TokenType>,
+        expr: Expr,
+        semicolon: Token<TokenType>,
+    }
+    ,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Add {
+        lhs: Box<Expr>,
+        rhs: Box<Atom>,
+    }
+    ,
+    Atom(Box<Atom>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Atom {
+    Number(Token<TokenType>),
+    Identifier(Token<TokenType>),
+    Group {
+        l_paren: Token<TokenType>,
+        expr: Box<Expr>,
+        r_paren: Token<TokenType>,
+    }
+    ,
+    Call {
+        function: Token<TokenType>,
+        args: Args,
+    }
+    ,
+}
+
+pub type Args = Vec<Expr>;
+
+pub enum Value {
+    Terminal(Token<TokenType>),
+    Program(Program),
+    Statements(Statements),
+    Statement(Statement),
+    Expr(Expr),
+    Atom(Atom),
+    Args(Args),
+}
+
+pub struct Parser<I = std::vec::IntoIter<Token<TokenType>>>(pub ParseState<I, TokenType, Value>);
+
+const EXPECTED: [&[TokenType]; 46] = [
+    &[TokenType::Eof, TokenType::Identifier, TokenType::Print],
+    &[TokenType::Eof, TokenType::Identifier, TokenType::Print],
+    &[TokenType::Eof, TokenType::Identifier, TokenType::Print],
+    &[TokenType::Equals],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Plus, TokenType::Semicolon],
+    &[TokenType::Plus, TokenType::Semicolon],
+    &[TokenType::Plus, TokenType::Semicolon],
+    &[TokenType::LParen, TokenType::Plus, TokenType::Semicolon],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Plus, TokenType::Semicolon],
+    &[TokenType::Eof, TokenType::Identifier, TokenType::Print],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Plus, TokenType::RParen],
+    &[TokenType::Plus, TokenType::RParen],
+    &[TokenType::Plus, TokenType::RParen],
+    &[TokenType::LParen, TokenType::Plus, TokenType::RParen],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Eof, TokenType::Identifier, TokenType::Print],
+    &[TokenType::Plus, TokenType::Semicolon],
+    &[TokenType::Comma, TokenType::Plus, TokenType::RParen],
+    &[TokenType::Comma, TokenType::Plus, TokenType::RParen],
+    &[TokenType::Comma, TokenType::Plus, TokenType::RParen],
+    &[TokenType::Comma, TokenType::LParen, TokenType::Plus, TokenType::RParen],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Comma, TokenType::RParen],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Plus, TokenType::Semicolon],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Plus, TokenType::RParen],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Plus, TokenType::RParen],
+    &[TokenType::Plus, TokenType::Semicolon],
+    &[TokenType::Identifier, TokenType::LParen, TokenType::Number],
+    &[TokenType::Plus, TokenType::RParen],
+    &[TokenType::Comma, TokenType::RParen],
+    &[TokenType::Plus, TokenType::RParen],
+    &[TokenType::Comma, TokenType::Plus, TokenType::RParen],
+    &[TokenType::Comma, TokenType::RParen],
+    &[TokenType::Comma, TokenType::Plus, TokenType::RParen],
+    &[TokenType::Comma, TokenType::Plus, TokenType::RParen],
+    &[TokenType::Plus, TokenType::RParen],
+    &[TokenType::Comma, TokenType::Plus, TokenType::RParen],
+];
+
+impl<I: TokenSource<TokenType>> Parse<I, TokenType> for Parser<I> {
+    type Value = Value;
+    type Output = Program;
+
+    fn parse_state_mut(&mut self) -> &mut ParseState<I, TokenType, Value> {
+        &mut self.0
+    }
+
+    fn parse_state(&self) -> &ParseState<I, TokenType, Value> {
+        &self.0
+    }
+
+    fn expected_terminals(&self, state: usize) -> &'static [TokenType] {
+        EXPECTED[state]
+    }
+
+    fn eof(&self) -> TokenType {
+        TokenType::Eof
+    }
+
+    fn error_terminal(&self) -> Option<TokenType> {
+        None
+    }
+
+    fn step(&self, state: usize, kind: TokenType) -> Option<Step> {
+        match (state, kind) {
+        (0, TokenType::Eof) => Some(Step::Reduce(2, 0)),
+        (0, TokenType::Identifier) => Some(Step::Reduce(2, 0)),
+        (0, TokenType::Print) => Some(Step::Reduce(2, 0)),
+        (1, TokenType::Eof) => Some(Step::Accept(0, 1)),
+        (1, TokenType::Identifier) => Some(Step::Shift(3)),
+        (1, TokenType::Print) => Some(Step::Shift(4)),
+        (2, TokenType::Eof) => Some(Step::Reduce(1, 2)),
+        (2, TokenType::Identifier) => Some(Step::Reduce(1, 2)),
+        (2, TokenType::Print) => Some(Step::Reduce(1, 2)),
+        (3, TokenType::Equals) => Some(Step::Shift(5)),
+        (4, TokenType::Identifier) => Some(Step::Shift(9)),
+        (4, TokenType::LParen) => Some(Step::Shift(10)),
+        (4, TokenType::Number) => Some(Step::Shift(8)),
+        (5, TokenType::Identifier) => Some(Step::Shift(9)),
+        (5, TokenType::LParen) => Some(Step::Shift(10)),
+        (5, TokenType::Number) => Some(Step::Shift(8)),
+        (6, TokenType::Plus) => Some(Step::Shift(13)),
+        (6, TokenType::Semicolon) => Some(Step::Shift(12)),
+        (7, TokenType::Plus) => Some(Step::Reduce(6, 1)),
+        (7, TokenType::Semicolon) => Some(Step::Reduce(6, 1)),
+        (8, TokenType::Plus) => Some(Step::Reduce(7, 1)),
+        (8, TokenType::Semicolon) => Some(Step::Reduce(7, 1)),
+        (9, TokenType::LParen) => Some(Step::Shift(14)),
+        (9, TokenType::Plus) => Some(Step::Reduce(8, 1)),
+        (9, TokenType::Semicolon) => Some(Step::Reduce(8, 1)),
+        (10, TokenType::Identifier) => Some(Step::Shift(18)),
+        (10, TokenType::LParen) => Some(Step::Shift(19)),
+        (10, TokenType::Number) => Some(Step::Shift(17)),
+        (11, TokenType::Plus) => Some(Step::Shift(13)),
+        (11, TokenType::Semicolon) => Some(Step::Shift(20)),
+        (12, TokenType::Eof) => Some(Step::Reduce(4, 3)),
+        (12, TokenType::Identifier) => Some(Step::Reduce(4, 3)),
+        (12, TokenType::Print) => Some(Step::Reduce(4, 3)),
+        (13, TokenType::Identifier) => Some(Step::Shift(9)),
+        (13, TokenType::LParen) => Some(Step::Shift(10)),
+        (13, TokenType::Number) => Some(Step::Shift(8)),
+        (14, TokenType::Identifier) => Some(Step::Shift(25)),
+        (14, TokenType::LParen) => Some(Step::Shift(26)),
+        (14, TokenType::Number) => Some(Step::Shift(24)),
+        (15, TokenType::Plus) => Some(Step::Shift(28)),
+        (15, TokenType::RParen) => Some(Step::Shift(29)),
+        (16, TokenType::Plus) => Some(Step::Reduce(6, 1)),
+        (16, TokenType::RParen) => Some(Step::Reduce(6, 1)),
+        (17, TokenType::Plus) => Some(Step::Reduce(7, 1)),
+        (17, TokenType::RParen) => Some(Step::Reduce(7, 1)),
+        (18, TokenType::LParen) => Some(Step::Shift(30)),
+        (18, TokenType::Plus) => Some(Step::Reduce(8, 1)),
+        (18, TokenType::RParen) => Some(Step::Reduce(8, 1)),
+        (19, TokenType::Identifier) => Some(Step::Shift(18)),
+        (19, TokenType::LParen) => Some(Step::Shift(19)),
+        (19, TokenType::Number) => Some(Step::Shift(17)),
+        (20, TokenType::Eof) => Some(Step::Reduce(3, 4)),
+        (20, TokenType::Identifier) => Some(Step::Reduce(3, 4)),
+        (20, TokenType::Print) => Some(Step::Reduce(3, 4)),
+        (21, TokenType::Plus) => Some(Step::Reduce(5, 3)),
+        (21, TokenType::Semicolon) => Some(Step::Reduce(5, 3)),
+        (22, TokenType::Comma) => Some(Step::Reduce(12, 1)),
+        (22, TokenType::Plus) => Some(Step::Shift(32)),
+        (22, TokenType::RParen) => Some(Step::Reduce(12, 1)),
+        (23, TokenType::Comma) => Some(Step::Reduce(6, 1)),
+        (23, TokenType::Plus) => Some(Step::Reduce(6, 1)),
+        (23, TokenType::RParen) => Some(Step::Reduce(6, 1)),
+        (24, TokenType::Comma) => Some(Step::Reduce(7, 1)),
+        (24, TokenType::Plus) => Some(Step::Reduce(7, 1)),
+        (24, TokenType::RParen) => Some(Step::Reduce(7, 1)),
+        (25, TokenType::Comma) => Some(Step::Reduce(8, 1)),
+        (25, TokenType::LParen) => Some(Step::Shift(33)),
+        (25, TokenType::Plus) => Some(Step::Reduce(8, 1)),
+        (25, TokenType::RParen) => Some(Step::Reduce(8, 1)),
+        (26, TokenType::Identifier) => Some(Step::Shift(18)),
+        (26, TokenType::LParen) => Some(Step::Shift(19)),
+        (26, TokenType::Number) => Some(Step::Shift(17)),
+        (27, TokenType::Comma) => Some(Step::Shift(36)),
+        (27, TokenType::RParen) => Some(Step::Shift(35)),
+        (28, TokenType::Identifier) => Some(Step::Shift(18)),
+        (28, TokenType::LParen) => Some(Step::Shift(19)),
+        (28, TokenType::Number) => Some(Step::Shift(17)),
+        (29, TokenType::Plus) => Some(Step::Reduce(9, 3)),
+        (29, TokenType::Semicolon) => Some(Step::Reduce(9, 3)),
+        (30, TokenType::Identifier) => Some(Step::Shift(25)),
+        (30, TokenType::LParen) => Some(Step::Shift(26)),
+        (30, TokenType::Number) => Some(Step::Shift(24)),
+        (31, TokenType::Plus) => Some(Step::Shift(28)),
+        (31, TokenType::RParen) => Some(Step::Shift(39)),
+        (32, TokenType::Identifier) => Some(Step::Shift(25)),
+        (32, TokenType::LParen) => Some(Step::Shift(26)),
+        (32, TokenType::Number) => Some(Step::Shift(24)),
+        (33, TokenType::Identifier) => Some(Step::Shift(25)),
+        (33, TokenType::LParen) => Some(Step::Shift(26)),
+        (33, TokenType::Number) => Some(Step::Shift(24)),
+        (34, TokenType::Plus) => Some(Step::Shift(28)),
+        (34, TokenType::RParen) => Some(Step::Shift(42)),
+        (35, TokenType::Plus) => Some(Step::Reduce(10, 4)),
+        (35, TokenType::Semicolon) => Some(Step::Reduce(10, 4)),
+        (36, TokenType::Identifier) => Some(Step::Shift(25)),
+        (36, TokenType::LParen) => Some(Step::Shift(26)),
+        (36, TokenType::Number) => Some(Step::Shift(24)),
+        (37, TokenType::Plus) => Some(Step::Reduce(5, 3)),
+        (37, TokenType::RParen) => Some(Step::Reduce(5, 3)),
+        (38, TokenType::Comma) => Some(Step::Shift(36)),
+        (38, TokenType::RParen) => Some(Step::Shift(44)),
+        (39, TokenType::Plus) => Some(Step::Reduce(9, 3)),
+        (39, TokenType::RParen) => Some(Step::Reduce(9, 3)),
+        (40, TokenType::Comma) => Some(Step::Reduce(5, 3)),
+        (40, TokenType::Plus) => Some(Step::Reduce(5, 3)),
+        (40, TokenType::RParen) => Some(Step::Reduce(5, 3)),
+        (41, TokenType::Comma) => Some(Step::Shift(36)),
+        (41, TokenType::RParen) => Some(Step::Shift(45)),
+        (42, TokenType::Comma) => Some(Step::Reduce(9, 3)),
+        (42, TokenType::Plus) => Some(Step::Reduce(9, 3)),
+        (42, TokenType::RParen) => Some(Step::Reduce(9, 3)),
+        (43, TokenType::Comma) => Some(Step::Reduce(11, 3)),
+        (43, TokenType::Plus) => Some(Step::Shift(32)),
+        (43, TokenType::RParen) => Some(Step::Reduce(11, 3)),
+        (44, TokenType::Plus) => Some(Step::Reduce(10, 4)),
+        (44, TokenType::RParen) => Some(Step::Reduce(10, 4)),
+        (45, TokenType::Comma) => Some(Step::Reduce(10, 4)),
+        (45, TokenType::Plus) => Some(Step::Reduce(10, 4)),
+        (45, TokenType::RParen) => Some(Step::Reduce(10, 4)),
+        _ => None,
+        }
+    }
+
+    fn goto_state(&self, state: usize, rule: usize) -> usize {
+        match (rule, state) {
+        (1, 0) => 1,
+        (2, 0) => 1,
+        (3, 1) => 2,
+        (4, 1) => 2,
+        (5, 4) => 6,
+        (5, 5) => 11,
+        (5, 10) => 15,
+        (5, 14) => 22,
+        (5, 19) => 31,
+        (5, 26) => 34,
+        (5, 30) => 22,
+        (5, 33) => 22,
+        (5, 36) => 43,
+        (6, 4) => 6,
+        (6, 5) => 11,
+        (6, 10) => 15,
+        (6, 14) => 22,
+        (6, 19) => 31,
+        (6, 26) => 34,
+        (6, 30) => 22,
+        (6, 33) => 22,
+        (6, 36) => 43,
+        (7, 4) => 7,
+        (7, 5) => 7,
+        (7, 10) => 16,
+        (7, 13) => 21,
+        (7, 14) => 23,
+        (7, 19) => 16,
+        (7, 26) => 16,
+        (7, 28) => 37,
+        (7, 30) => 23,
+        (7, 32) => 40,
+        (7, 33) => 23,
+        (7, 36) => 23,
+        (8, 4) => 7,
+        (8, 5) => 7,
+        (8, 10) => 16,
+        (8, 13) => 21,
+        (8, 14) => 23,
+        (8, 19) => 16,
+        (8, 26) => 16,
+        (8, 28) => 37,
+        (8, 30) => 23,
+        (8, 32) => 40,
+        (8, 33) => 23,
+        (8, 36) => 23,
+        (9, 4) => 7,
+        (9, 5) => 7,
+        (9, 10) => 16,
+        (9, 13) => 21,
+        (9, 14) => 23,
+        (9, 19) => 16,
+        (9, 26) => 16,
+        (9, 28) => 37,
+        (9, 30) => 23,
+        (9, 32) => 40,
+        (9, 33) => 23,
+        (9, 36) => 23,
+        (10, 4) => 7,
+        (10, 5) => 7,
+        (10, 10) => 16,
+        (10, 13) => 21,
+        (10, 14) => 23,
+        (10, 19) => 16,
+        (10, 26) => 16,
+        (10, 28) => 37,
+        (10, 30) => 23,
+        (10, 32) => 40,
+        (10, 33) => 23,
+        (10, 36) => 23,
+        (11, 14) => 27,
+        (11, 30) => 38,
+        (11, 33) => 41,
+        (12, 14) => 27,
+        (12, 30) => 38,
+        (12, 33) => 41,
+        _ => panic!("unkown goto"),
+        }
+    }
+
+    fn token_value(&mut self, token: Token<TokenType>) -> Value {
+        Value::Terminal(token)
+    }
+
+    #[allow(unused_variables, unused_braces, clippy::just_underscores_and_digits, clippy::let_unit_value)]
+    fn reduce_values(&mut self, rule: usize, values: Vec<Value>) -> Value {
+        let mut values = values.into_iter();
+        match rule {
+        0 => {
+        let _1 = match values.next() { Some(Value::Statements(v)) => v, _ => unreachable!() };
+        Value::Program(Program { statements: _1 })
+        }
+        1 => {
+        let _1 = match values.next() { Some(Value::Statements(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Statement(v)) => v, _ => unreachable!() };
+        Value::Statements({ let mut list = _1; list.push(_2); list })
+        }
+        2 => {
+        Value::Statements(Vec::new())
+        }
+        3 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        let _4 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Statement(Statement::Assign { name: _1, value: _3 })
+        }
+        4 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Statement(Statement::Print { print: _1, expr: _2, semicolon: _3 })
+        }
+        5 => {
+        let _1 = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Atom(v)) => v, _ => unreachable!() };
+        Value::Expr(Expr::Add { lhs: Box::new(_1), rhs: Box::new(_3) })
+        }
+        6 => {
+        let _1 = match values.next() { Some(Value::Atom(v)) => v, _ => unreachable!() };
+        Value::Expr(Expr::Atom(Box::new(_1)))
+        }
+        7 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Atom(Atom::Number(_1))
+        }
+        8 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Atom(Atom::Identifier(_1))
+        }
+        9 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Atom(Atom::Group { l_paren: _1, expr: Box::new(_2), r_paren: _3 })
+        }
+        10 => {
+        let _1 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Args(v)) => v, _ => unreachable!() };
+        let _4 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        Value::Atom(Atom::Call { function: _1, args: _3 })
+        }
+        11 => {
+        let _1 = match values.next() { Some(Value::Args(v)) => v, _ => unreachable!() };
+        let _2 = match values.next() { Some(Value::Terminal(v)) => v, _ => unreachable!() };
+        let _3 = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        Value::Args({ let mut list = _1; list.push(_3); list })
+        }
+        12 => {
+        let _1 = match values.next() { Some(Value::Expr(v)) => v, _ => unreachable!() };
+        Value::Args(vec![_1])
+        }
+        _ => unreachable!(),
+        }
+    }
+
+    fn output(&mut self, value: Value) -> Program {
+        match value {
+        Value::Program(v) => v,
+        _ => unreachable!(),
+        }
+    }
+}
\ No newline at end of file
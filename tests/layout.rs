@@ -0,0 +1,189 @@
+use yaw::runtime::layout::{IndentError, Layout, LayoutTokens};
+use yaw::runtime::lexer::Scanner;
+use yaw::runtime::{Parse, ParseState, Token, TokenSource};
+
+#[rustfmt::skip]
+#[path = "generated/indented.rs"]
+mod parser;
+
+use parser::{is_trivia, lex, scan, Parser, TokenType};
+
+const KINDS: LayoutTokens<TokenType> = LayoutTokens {
+    indent: TokenType::Indent,
+    dedent: TokenType::Dedent,
+    newline: TokenType::Newline,
+    eof: TokenType::Eof,
+};
+
+fn layout(source: &str) -> Layout<'_, Scanner<'_, TokenType>, TokenType> {
+    Layout::new(scan(source), source, KINDS)
+        .with_trivia(is_trivia)
+        .with_brackets(&[(TokenType::LBracket, TokenType::RBracket)])
+}
+
+/// The kinds of the tokens that are not trivia, or the error the layout stopped at.
+fn kinds<I: TokenSource<TokenType>>(
+    mut layout: Layout<'_, I, TokenType>,
+) -> Result<Vec<TokenType>, IndentError> {
+    let mut kinds = Vec::new();
+    while let Some(token) = layout.next_token(&[]) {
+        if !is_trivia(&token.kind) {
+            kinds.push(token.kind);
+        }
+    }
+    layout.error().map_or(Ok(kinds), Err)
+}
+
+#[test]
+fn inserts_indents_and_dedents() {
+    use TokenType::*;
+
+    let source = "a:\n  b = 1\n\n  # A comment.\n  c:\n    d = [1,\n 2]\ne = f";
+    assert_eq!(
+        kinds(layout(source)).unwrap(),
+        [
+            Identifier, Colon, Newline, Indent, Identifier, Equals, Number, Newline, Identifier,
+            Colon, Newline, Indent, Identifier, Equals, LBracket, Number, Comma, Number, RBracket,
+            Newline, Dedent, Dedent, Identifier, Equals, Identifier, Newline, Eof
+        ]
+    );
+}
+
+#[test]
+fn inserts_empty_tokens() {
+    let source = "a:\n  b = c\n";
+    // Any source of tokens can be laid out, such as the tokens lexed up front.
+    let tokens = lex(source).unwrap().into_iter();
+    let mut layout = Layout::new(tokens, source, KINDS).with_trivia(is_trivia);
+    let tokens = std::iter::from_fn(|| layout.next_token(&[])).collect::<Vec<_>>();
+    let spans = tokens
+        .iter()
+        .filter(|t| {
+            matches!(
+                t.kind,
+                TokenType::Newline | TokenType::Indent | TokenType::Dedent
+            )
+        })
+        .map(|t| (t.kind, t.span))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        spans,
+        [
+            (TokenType::Newline, (2, 2)),
+            (TokenType::Indent, (5, 5)),
+            (TokenType::Newline, (10, 10)),
+            (TokenType::Dedent, (11, 11)),
+        ]
+    );
+}
+
+#[test]
+fn joins_lines_in_brackets_when_configured() {
+    let source = "a = [b,\n  c]\n";
+    assert!(
+        Parser(ParseState::new(layout(source)).with_trivia(is_trivia))
+            .parse()
+            .is_ok()
+    );
+
+    let unjoined = Layout::new(scan(source), source, KINDS).with_trivia(is_trivia);
+    assert!(kinds(unjoined).unwrap().contains(&TokenType::Indent));
+}
+
+#[test]
+fn continues_lines_through_comments_spanning_lines() {
+    use TokenType::*;
+
+    // The grammar only has line comments, so the tokens of a block comment are put together by hand.
+    let pieces = [
+        (Identifier, "a"),
+        (Whitespace, " "),
+        (Equals, "="),
+        (Whitespace, " "),
+        (Comment, "/* spanning\n   lines */"),
+        (Whitespace, " "),
+        (Number, "1"),
+        (Comment, " /* ending\n */"),
+        (Whitespace, "\n"),
+        (Identifier, "b"),
+        (Equals, "="),
+        (Number, "2"),
+        (Eof, ""),
+    ];
+    let source = pieces.iter().map(|(_, text)| *text).collect::<String>();
+    let mut end = 0;
+    let tokens = pieces
+        .iter()
+        .map(|&(kind, text)| {
+            end += text.len();
+            Token {
+                span: (end - text.len(), end),
+                kind,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let layout = Layout::new(tokens.clone().into_iter(), &source, KINDS).with_trivia(is_trivia);
+    assert_eq!(
+        kinds(layout).unwrap(),
+        [Identifier, Equals, Number, Newline, Identifier, Equals, Number, Newline, Eof]
+    );
+    let layout = Layout::new(tokens.into_iter(), &source, KINDS).with_trivia(is_trivia);
+    assert!(Parser(ParseState::new(layout).with_trivia(is_trivia))
+        .parse()
+        .is_ok());
+}
+
+#[test]
+fn parses_blocks() {
+    let source = "server:\n  host = local\n  ports:\n    http = 80\nname = x";
+    let mut parser = Parser(ParseState::new(layout(source)).with_trivia(is_trivia));
+    assert!(parser.parse().is_ok());
+
+    let source = "server:\nhost = local\n";
+    let mut parser = Parser(ParseState::new(layout(source)).with_trivia(is_trivia));
+    assert!(parser.parse().is_err());
+}
+
+#[test]
+fn starts_at_the_indentation_of_the_first_line() {
+    use TokenType::*;
+
+    let source = "  a:\n    b = 1\n  c = 2\n";
+    assert_eq!(
+        kinds(layout(source)).unwrap(),
+        [
+            Identifier, Colon, Newline, Indent, Identifier, Equals, Number, Newline, Dedent,
+            Identifier, Equals, Number, Newline, Eof
+        ]
+    );
+    let mut parser = Parser(ParseState::new(layout(source)).with_trivia(is_trivia));
+    assert!(parser.parse().is_ok());
+
+    assert_eq!(
+        kinds(layout("  a = 1\nb = 2\n")).unwrap_err(),
+        IndentError::UnmatchedDedent { offset: 8 }
+    );
+}
+
+#[test]
+fn rejects_inconsistent_indentation() {
+    assert_eq!(
+        kinds(layout("a:\n    b = 1\n  c = 2\n")).unwrap_err(),
+        IndentError::UnmatchedDedent { offset: 15 }
+    );
+    // A tab and eight spaces are the same width, but not when a tab is a single column.
+    assert_eq!(
+        kinds(layout("a:\n\tb = 1\n        c = 2\n")).unwrap_err(),
+        IndentError::AmbiguousTabs { offset: 18 }
+    );
+
+    let source = "a:\n    b = 1\n  c = 2\n";
+    let mut parser = Parser(ParseState::new(layout(source)).with_trivia(is_trivia));
+    assert!(parser.parse().is_err());
+    assert_eq!(
+        parser.0.tokens().error().unwrap().to_string(),
+        "inconsistent indentation at offset 15, the dedent does not match any outer indentation"
+    );
+}
@@ -0,0 +1,138 @@
+use yaw::runtime::lexer::LexError;
+use yaw::runtime::{Parse, ParseState, Token};
+
+#[rustfmt::skip]
+#[path = "generated/lexed.rs"]
+mod parser;
+
+use parser::{is_trivia, lex, scan, Parser, TokenType};
+
+fn kinds(source: &str) -> Vec<TokenType> {
+    lex(source).unwrap().iter().map(|t| t.kind).collect()
+}
+
+#[test]
+fn takes_the_longest_match() {
+    use TokenType::*;
+
+    assert_eq!(kinds("let"), [Let, Eof]);
+    assert_eq!(kinds("lets"), [Identifier, Eof]);
+    assert_eq!(
+        kinds("x=1.5+y2"),
+        [Identifier, Equals, Number, Plus, Identifier, Eof]
+    );
+}
+
+#[test]
+fn produces_spans() {
+    let source = "let x = 12; // twelve\n";
+
+    assert_eq!(
+        lex(source).unwrap(),
+        [
+            Token {
+                span: (0, 3),
+                kind: TokenType::Let
+            },
+            Token {
+                span: (3, 4),
+                kind: TokenType::Whitespace
+            },
+            Token {
+                span: (4, 5),
+                kind: TokenType::Identifier
+            },
+            Token {
+                span: (5, 6),
+                kind: TokenType::Whitespace
+            },
+            Token {
+                span: (6, 7),
+                kind: TokenType::Equals
+            },
+            Token {
+                span: (7, 8),
+                kind: TokenType::Whitespace
+            },
+            Token {
+                span: (8, 10),
+                kind: TokenType::Number
+            },
+            Token {
+                span: (10, 11),
+                kind: TokenType::Semicolon
+            },
+            Token {
+                span: (11, 12),
+                kind: TokenType::Whitespace
+            },
+            Token {
+                span: (12, 21),
+                kind: TokenType::Comment
+            },
+            Token {
+                span: (21, 22),
+                kind: TokenType::Whitespace
+            },
+            Token {
+                span: (22, 22),
+                kind: TokenType::Eof
+            },
+        ]
+    );
+}
+
+#[test]
+fn reports_unmatched_input() {
+    assert_eq!(
+        lex("let x = 1 * 2;").unwrap_err(),
+        LexError {
+            offset: 10,
+            found: '*'
+        }
+    );
+    // A number cannot end in a dot, and the dot does not start a token by itself.
+    assert_eq!(
+        lex("1.").unwrap_err(),
+        LexError {
+            offset: 1,
+            found: '.'
+        }
+    );
+}
+
+#[test]
+fn feeds_the_parser() {
+    let source = "let x = 1;\nlet y = x + 2.5; // done";
+    let state = ParseState::new(lex(source).unwrap().into_iter()).with_trivia(is_trivia);
+
+    assert!(Parser(state).parse().is_ok());
+
+    let state = ParseState::new(scan(source)).with_trivia(is_trivia);
+    assert!(Parser(state).parse().is_ok());
+}
+
+#[test]
+fn reports_hand_written_messages() {
+    let error = |source| {
+        let state = ParseState::new(scan(source)).with_trivia(is_trivia);
+        Parser(state).parse().unwrap_err()
+    };
+
+    assert_eq!(
+        error("let = 1;").to_string(),
+        "A `let` statement needs a variable name, as in `let x = 1;`."
+    );
+    // The same state is reached from other inputs than the sentences in the file.
+    let after_equals = error("let x = 1; let y = ;");
+    assert_eq!(
+        after_equals.message,
+        Some("Expected a number or a variable after `=`.")
+    );
+    assert_eq!(after_equals.found.unwrap().kind, TokenType::Semicolon);
+    // States without a message keep the generic one.
+    assert_eq!(
+        error("let x = 1").to_string(),
+        "expected one of `+`, `;` but found end of input"
+    );
+}
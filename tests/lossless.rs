@@ -0,0 +1,437 @@
+use std::sync::Arc;
+use yaw::runtime::cst::{SyntaxElement, SyntaxNode};
+use yaw::runtime::incremental::{Edit, Tree};
+use yaw::runtime::{tolerant, Feed, Parse, ParseError, ParseState, Token};
+
+#[rustfmt::skip]
+#[path = "generated/lossless.rs"]
+mod parser;
+
+use parser::{is_trivia, NodeKind, Parser, TokenType};
+
+fn tokenize(source: &str) -> Vec<Token<TokenType>> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let kind = match c {
+            '+' => TokenType::Plus,
+            '=' => TokenType::Equals,
+            ';' => TokenType::Semicolon,
+            '(' => TokenType::LParen,
+            ')' => TokenType::RParen,
+            '/' => {
+                while chars.next_if(|&(_, c)| c != '\n').is_some() {}
+                TokenType::Comment
+            }
+            ' ' | '\n' => {
+                while chars.next_if(|&(_, c)| c == ' ' || c == '\n').is_some() {}
+                TokenType::Whitespace
+            }
+            '0'..='9' => {
+                while chars.next_if(|&(_, c)| c.is_ascii_digit()).is_some() {}
+                TokenType::Number
+            }
+            _ => {
+                while chars.next_if(|&(_, c)| c.is_ascii_lowercase()).is_some() {}
+                TokenType::Identifier
+            }
+        };
+        let end = chars.peek().map_or(source.len(), |&(i, _)| i);
+        tokens.push(Token {
+            span: (start, end),
+            kind,
+        });
+    }
+
+    tokens.push(Token {
+        span: (source.len(), source.len()),
+        kind: TokenType::Eof,
+    });
+    tokens
+}
+
+/// The tokens of `tokenize` with an empty comment before every semicolon, so that two tokens start at
+/// the same offset.
+fn tokenize_with_empty_comments(source: &str) -> Vec<Token<TokenType>> {
+    let mut tokens = Vec::new();
+    for token in tokenize(source) {
+        if token.kind == TokenType::Semicolon {
+            tokens.push(Token {
+                span: (token.span.0, token.span.0),
+                kind: TokenType::Comment,
+            });
+        }
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn parse(source: &str) -> SyntaxNode<TokenType, NodeKind> {
+    let state = ParseState::new(tokenize(source).into_iter()).with_trivia(is_trivia);
+    Parser(state, source).parse().unwrap()
+}
+
+fn parse_tolerant(source: &str) -> (SyntaxNode<TokenType, NodeKind>, Vec<ParseError<TokenType>>) {
+    let state = ParseState::new(tokenize(source).into_iter()).with_trivia(is_trivia);
+    tolerant::parse(&mut Parser(state, source))
+}
+
+type Lexer = fn(&str) -> Vec<Token<TokenType>>;
+
+fn parse_tree(source: &str) -> Result<Tree<TokenType, NodeKind>, ParseError<TokenType>> {
+    parse_tree_with(source, tokenize)
+}
+
+fn parse_tree_with(
+    source: &str,
+    lex: Lexer,
+) -> Result<Tree<TokenType, NodeKind>, ParseError<TokenType>> {
+    let state = ParseState::new(std::iter::empty()).with_trivia(is_trivia);
+    Tree::parse(&mut Parser(state, source), lex(source).into_iter())
+}
+
+/// Reparses `tree` after `edit`, returning the new text with the new tree.
+fn reparse(
+    tree: &Tree<TokenType, NodeKind>,
+    edit: &Edit,
+) -> (
+    String,
+    Result<Tree<TokenType, NodeKind>, ParseError<TokenType>>,
+) {
+    reparse_with(tree, edit, tokenize)
+}
+
+fn reparse_with(
+    tree: &Tree<TokenType, NodeKind>,
+    edit: &Edit,
+    lex: Lexer,
+) -> (
+    String,
+    Result<Tree<TokenType, NodeKind>, ParseError<TokenType>>,
+) {
+    let source = edit.apply(&tree.root().text());
+    let state = ParseState::new(std::iter::empty()).with_trivia(is_trivia);
+    let reparsed = tree.reparse(&mut Parser(state, &source), edit, |text| {
+        lex(text).into_iter()
+    });
+    (source, reparsed)
+}
+
+/// The kinds and texts of the nodes of a tree in preorder, leaving out the complete statements.
+fn outline(root: &SyntaxNode<TokenType, NodeKind>) -> Vec<(NodeKind, String)> {
+    root.descendants()
+        .into_iter()
+        .map(|n| (n.kind(), n.text()))
+        .filter(|(kind, _)| !matches!(kind, NodeKind::File | NodeKind::Statements))
+        .collect()
+}
+
+const SOURCE: &str = "// two statements\nx = 1 + (y);\n\nz=2; // done\n";
+
+#[test]
+fn round_trips_the_text() {
+    let root = parse(SOURCE);
+
+    assert_eq!(root.kind(), NodeKind::File);
+    assert_eq!(root.text(), SOURCE);
+    assert_eq!(root.text_range(), 0..SOURCE.len());
+    assert_eq!(parse("").text(), "");
+    assert_eq!(parse("  ").text(), "  ");
+}
+
+#[test]
+fn keeps_the_trivia_of_fed_tokens() {
+    let mut parser = Parser(
+        ParseState::new(std::iter::empty()).with_trivia(is_trivia),
+        SOURCE,
+    );
+    let mut tokens = tokenize(SOURCE);
+    tokens.pop();
+
+    for token in tokens {
+        assert_eq!(parser.feed(token), Feed::NeedMore);
+    }
+    match parser.finish() {
+        Feed::Accepted(root) => assert_eq!(root.text(), SOURCE),
+        feed => panic!("unexpected {:?}", feed),
+    }
+}
+
+#[test]
+fn keeps_every_token() {
+    let root = parse(SOURCE);
+    let tokens = root.tokens();
+
+    assert_eq!(
+        tokens.iter().map(|t| t.kind()).collect::<Vec<_>>()[..4],
+        [
+            TokenType::Comment,
+            TokenType::Whitespace,
+            TokenType::Identifier,
+            TokenType::Whitespace
+        ]
+    );
+    assert_eq!(tokens.iter().map(|t| t.text()).collect::<String>(), SOURCE);
+    assert_eq!(tokens.last().unwrap().text(), "\n");
+}
+
+#[test]
+fn answers_range_queries() {
+    let root = parse(SOURCE);
+
+    // The `y` in `(y)` is an identifier inside an atom, inside the parenthesised expression.
+    let y = root.token_at_offset(27).unwrap();
+    assert_eq!(
+        (y.kind(), y.text(), y.text_range()),
+        (TokenType::Identifier, "y", 27..28)
+    );
+    assert_eq!(
+        y.parent().ancestors().map(|n| n.kind()).collect::<Vec<_>>(),
+        [
+            NodeKind::Atom,
+            NodeKind::Expr,
+            NodeKind::Atom,
+            NodeKind::Expr,
+            NodeKind::Statement,
+            NodeKind::Statements,
+            NodeKind::Statements,
+            NodeKind::File
+        ]
+    );
+
+    // `1 + (y)` is exactly an expression, while `+ (` is only covered by it.
+    match root.covering_element(22..29) {
+        Some(SyntaxElement::Node(node)) => {
+            assert_eq!(
+                (node.kind(), node.text()),
+                (NodeKind::Expr, "1 + (y)".into())
+            )
+        }
+        other => panic!("expected an expression, found {:?}", other),
+    }
+    let covering = root.covering_element(24..27).unwrap().into_node().unwrap();
+    assert_eq!(covering.kind(), NodeKind::Expr);
+    assert_eq!(covering.text_range(), 22..29);
+    assert_eq!(root.covering_element(0..100), None);
+
+    let statements = root
+        .descendants()
+        .into_iter()
+        .filter(|n| n.kind() == NodeKind::Statement)
+        .map(|n| n.text())
+        .collect::<Vec<_>>();
+    assert_eq!(statements, ["x = 1 + (y);", "z=2;"]);
+}
+
+#[test]
+fn parses_broken_input_tolerantly() {
+    let (root, errors) = parse_tolerant(SOURCE);
+    assert_eq!(root.text(), SOURCE);
+    assert!(errors.is_empty());
+
+    // A missing semicolon is inserted, and the second of two `=`, which cannot follow anything, is
+    // skipped.
+    let source = "a = 1\nb = 2;\nc = = 3;";
+    let (root, errors) = parse_tolerant(source);
+    assert_eq!(root.text(), source);
+    assert_eq!(errors.len(), 2);
+
+    let missing = root
+        .descendants()
+        .into_iter()
+        .find(|n| n.kind() == NodeKind::Missing)
+        .unwrap();
+    assert_eq!(missing.text_range(), 6..6);
+    assert_eq!(missing.tokens()[0].kind(), TokenType::Semicolon);
+    assert_eq!(missing.parent().unwrap().text(), "a = 1\n");
+    let error = root
+        .descendants()
+        .into_iter()
+        .find(|n| n.kind() == NodeKind::Error)
+        .unwrap();
+    assert_eq!((error.text(), error.text_range()), ("=".into(), 17..18));
+    assert_eq!(error.parent().unwrap().kind(), NodeKind::Statement);
+}
+
+#[test]
+fn wraps_unfinished_statements_in_errors() {
+    // A statement missing up to three tokens at the end is completed.
+    let source = "x = 1;\ny = (1 +";
+    let (root, errors) = parse_tolerant(source);
+    assert_eq!(root.text(), source);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        outline(&root)
+            .into_iter()
+            .filter(|(kind, _)| matches!(kind, NodeKind::Statement | NodeKind::Missing))
+            .collect::<Vec<_>>(),
+        [
+            (NodeKind::Statement, "x = 1;".into()),
+            (NodeKind::Statement, "y = (1 +".into()),
+            (NodeKind::Missing, "".into()),
+            (NodeKind::Missing, "".into()),
+            (NodeKind::Missing, "".into())
+        ]
+    );
+
+    // Otherwise the end of input may follow the first statement, so the second one is an error.
+    let source = "x = 1;\ny = ((1 + ";
+    let (root, errors) = parse_tolerant(source);
+    assert_eq!(root.text(), source);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        outline(&root)
+            .into_iter()
+            .filter(|(kind, _)| matches!(kind, NodeKind::Statement | NodeKind::Error))
+            .collect::<Vec<_>>(),
+        [
+            (NodeKind::Statement, "x = 1;".into()),
+            (NodeKind::Error, "y = ((1 +".into())
+        ]
+    );
+
+    // Tokens that cannot follow anything are skipped, even before the first statement.
+    let source = ") ) x = 1; )";
+    let (root, errors) = parse_tolerant(source);
+    assert_eq!(root.text(), source);
+    assert_eq!(errors.len(), 2);
+    assert_eq!(
+        root.child_nodes().map(|n| n.kind()).collect::<Vec<_>>()[0],
+        NodeKind::Error
+    );
+}
+
+#[test]
+fn resynchronises_on_follow_sets() {
+    // The semicolon can follow the expression of the statement, so the unclosed parenthesis stands in
+    // for it and the statement ends at the semicolon.
+    let source = "x = (; y = 2;";
+    let (root, errors) = parse_tolerant(source);
+    assert_eq!(root.text(), source);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        outline(&root)
+            .into_iter()
+            .filter(|(kind, _)| matches!(kind, NodeKind::Statement | NodeKind::Error))
+            .collect::<Vec<_>>(),
+        [
+            (NodeKind::Statement, "x = (;".into()),
+            (NodeKind::Error, "(".into()),
+            (NodeKind::Statement, "y = 2;".into())
+        ]
+    );
+    let error = root
+        .descendants()
+        .into_iter()
+        .find(|n| n.kind() == NodeKind::Error)
+        .unwrap();
+    assert_eq!(error.text_range(), 4..5);
+
+    // A statement can follow another one, so an unfinished one ends before the next statement.
+    let source = "x = ((1 y = 2;";
+    let (root, errors) = parse_tolerant(source);
+    assert_eq!(root.text(), source);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        outline(&root)
+            .into_iter()
+            .filter(|(kind, _)| matches!(kind, NodeKind::Statement | NodeKind::Error))
+            .collect::<Vec<_>>(),
+        [
+            (NodeKind::Error, "x = ((1".into()),
+            (NodeKind::Statement, "y = 2;".into())
+        ]
+    );
+}
+
+#[test]
+fn reuses_unchanged_statements() {
+    let source = "a = 1;\nb = 2 + c; // two\nc = (d);\n";
+    let tree = parse_tree(source).unwrap();
+    let statements = |tree: &Tree<TokenType, NodeKind>| {
+        tree.root()
+            .descendants()
+            .into_iter()
+            .filter(|n| n.kind() == NodeKind::Statement)
+            .map(|n| Arc::clone(n.green()))
+            .collect::<Vec<_>>()
+    };
+
+    let edit = Edit {
+        range: 11..12,
+        text: "20 + 3".into(),
+    };
+    let (source, reparsed) = reparse(&tree, &edit);
+    let reparsed = reparsed.unwrap();
+    let full = parse_tree(&source).unwrap();
+    assert_eq!(
+        reparsed.root().text(),
+        "a = 1;\nb = 20 + 3 + c; // two\nc = (d);\n"
+    );
+    assert_eq!(reparsed.root().green(), full.root().green());
+    assert_eq!(reparsed.tokens(), full.tokens());
+
+    let (old, new) = (statements(&tree), statements(&reparsed));
+    assert!(Arc::ptr_eq(&old[0], &new[0]));
+    assert!(!Arc::ptr_eq(&old[1], &new[1]));
+    assert!(Arc::ptr_eq(&old[2], &new[2]));
+
+    // An edit that breaks the text fails like a full parse.
+    let edit = Edit {
+        range: 19..20,
+        text: String::new(),
+    };
+    let (source, reparsed) = reparse(&tree, &edit);
+    assert_eq!(reparsed.err(), parse_tree(&source).err());
+}
+
+#[test]
+fn reparses_like_a_full_parse() {
+    check_reparses(tokenize);
+}
+
+#[test]
+fn reparses_zero_width_tokens_like_a_full_parse() {
+    check_reparses(tokenize_with_empty_comments);
+}
+
+/// Makes random edits to a tree, checking that each reparse gives the same tree as a full parse.
+fn check_reparses(lex: Lexer) {
+    const SNIPPETS: [&str; 12] = [
+        "", "x", "1", " ", "+", ";", "=", "(", ")", "y = 2;", "\n// c\n", "ab",
+    ];
+    // A linear congruential generator, so the edits are the same in every run.
+    let mut seed = 7u64;
+    let mut random = |bound: usize| {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (seed >> 33) as usize % bound
+    };
+
+    let mut tree = parse_tree_with(SOURCE, lex).unwrap();
+    let mut reparsed_count = 0;
+    for _ in 0..500 {
+        let len = tree.root().text().len();
+        let start = random(len + 1);
+        let end = (start + random(4)).min(len);
+        let edit = Edit {
+            range: start..end,
+            text: SNIPPETS[random(SNIPPETS.len())].into(),
+        };
+
+        let (source, reparsed) = reparse_with(&tree, &edit, lex);
+        match (reparsed, parse_tree_with(&source, lex)) {
+            (Ok(reparsed), Ok(full)) => {
+                assert_eq!(reparsed.root().text(), source);
+                assert_eq!(reparsed.root().green(), full.root().green(), "{:?}", edit);
+                assert_eq!(reparsed.tokens(), full.tokens());
+                tree = reparsed;
+                reparsed_count += 1;
+            }
+            (reparsed, full) => assert_eq!(reparsed.err(), full.err(), "{:?}", edit),
+        }
+    }
+    assert!(reparsed_count > 50);
+}
@@ -0,0 +1,146 @@
+use yaw::runtime::lexer::Matched;
+use yaw::runtime::{Parse, ParseState, Token};
+
+#[rustfmt::skip]
+#[path = "generated/modes.rs"]
+mod parser;
+
+use parser::{is_trivia, lex, mode, scan, Parser, TokenType};
+
+/// Extends a comment to its matching `*/`, counting the comments nested in it.
+fn nested_comment(matched: &mut Matched<TokenType>) {
+    let rest = matched.rest();
+    let (mut depth, mut len) = (1, 0);
+
+    while depth > 0 && len < rest.len() {
+        if rest[len..].starts_with("/*") {
+            depth += 1;
+            len += 2;
+        } else if rest[len..].starts_with("*/") {
+            depth -= 1;
+            len += 2;
+        } else {
+            len += rest[len..].chars().next().unwrap().len_utf8();
+        }
+    }
+    matched.consume(len);
+}
+
+/// Drops a comment after consuming it.
+fn skipped_comment(matched: &mut Matched<TokenType>) {
+    nested_comment(matched);
+    matched.skip();
+}
+
+/// Takes the lines up to the line holding the delimiter as the body of a heredoc, dropping that line.
+fn heredoc(matched: &mut Matched<TokenType>) {
+    let (start, end) = matched.span();
+    let delimiter = matched.text()[2..].trim_end();
+    let rest = matched.rest();
+
+    let body = rest
+        .split_inclusive('\n')
+        .take_while(|line| line.trim_end() != delimiter)
+        .map(str::len)
+        .sum::<usize>();
+    let terminator = rest[body..]
+        .split_inclusive('\n')
+        .next()
+        .map_or(0, str::len);
+
+    matched.skip();
+    matched.emit(Token {
+        span: (start, end),
+        kind: TokenType::HeredocStart,
+    });
+    matched.emit(Token {
+        span: (end, end + body),
+        kind: TokenType::HeredocBody,
+    });
+    matched.consume(body + terminator);
+}
+
+fn kinds(tokens: Vec<Token<TokenType>>) -> Vec<TokenType> {
+    tokens.iter().map(|t| t.kind).collect()
+}
+
+#[test]
+fn interpolates_strings() {
+    use TokenType::*;
+
+    assert_eq!(
+        kinds(lex(r#""a ${ "b${c}" } d""#).unwrap()),
+        [
+            Quote,
+            Text,
+            InterpolationStart,
+            Whitespace,
+            Quote,
+            Text,
+            InterpolationStart,
+            Identifier,
+            RBrace,
+            EndQuote,
+            Whitespace,
+            RBrace,
+            Text,
+            EndQuote,
+            Eof
+        ]
+    );
+    // Spaces and braces are text within a string.
+    assert_eq!(
+        kinds(lex(r#""x }""#).unwrap()),
+        [Quote, Text, EndQuote, Eof]
+    );
+    assert_eq!((mode::INITIAL, mode::STRING), (0, 1));
+}
+
+#[test]
+fn nests_comments() {
+    let source = "a /* b /* c */ d */ e";
+    let tokens = scan(source)
+        .with_callback(TokenType::Comment, nested_comment)
+        .tokenize()
+        .unwrap();
+
+    assert_eq!(
+        tokens[2],
+        Token {
+            span: (2, 19),
+            kind: TokenType::Comment
+        }
+    );
+    assert_eq!(tokens[4].kind, TokenType::Identifier);
+}
+
+#[test]
+fn emits_heredocs() {
+    let source = "a <<END\nline \"one\"\nline two\nEND\nb";
+    let tokens = scan(source)
+        .with_callback(TokenType::HeredocStart, heredoc)
+        .tokenize()
+        .unwrap();
+    let texts = tokens
+        .iter()
+        .map(|t| &source[t.span.0..t.span.1])
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        texts,
+        ["a", " ", "<<END\n", "line \"one\"\nline two\n", "b", ""]
+    );
+    assert_eq!(tokens[3].kind, TokenType::HeredocBody);
+}
+
+#[test]
+fn parses_with_callbacks() {
+    let source = "x /* /* */ */ \"y ${<<EOF\n}\nEOF\n}\" z";
+    let tokens = scan(source)
+        .with_callback(TokenType::Comment, skipped_comment)
+        .with_callback(TokenType::HeredocStart, heredoc);
+    let mut parser = Parser(ParseState::new(tokens).with_trivia(is_trivia));
+
+    assert!(parser.parse().is_ok());
+    assert!(parser.0.tokens().error().is_none());
+}
@@ -0,0 +1,80 @@
+use yaw::runtime::{Parse, ParseState, Token};
+
+#[rustfmt::skip]
+#[path = "generated/recovery.rs"]
+mod parser;
+
+use parser::{is_trivia, lex, scan, Parser, TokenType};
+
+#[derive(Debug, PartialEq)]
+pub enum Statement {
+    Let((usize, usize)),
+    /// A statement with a syntax error, with the empty span of the error token.
+    Invalid((usize, usize)),
+}
+
+fn parser(source: &str) -> Parser<yaw::runtime::lexer::Scanner<'_, TokenType>> {
+    Parser(ParseState::new(scan(source)).with_trivia(is_trivia))
+}
+
+#[test]
+fn collects_all_errors() {
+    //            0         1         2         3
+    //            0123456789012345678901234567890123456
+    let source = "let a = 1; let = 2; let b 3; let c = 4;";
+    let (output, errors) = parser(source).parse_recovering();
+
+    assert_eq!(
+        output.unwrap(),
+        [
+            Statement::Let((4, 5)),
+            Statement::Invalid((15, 15)),
+            Statement::Invalid((26, 26)),
+            Statement::Let((33, 34)),
+        ]
+    );
+    assert_eq!(
+        errors.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+        [
+            "expected `Identifier` but found `=`",
+            "expected `=` but found `Number`"
+        ]
+    );
+    assert_eq!(
+        errors[0].found,
+        Some(Token {
+            span: (15, 16),
+            kind: TokenType::Equals
+        })
+    );
+}
+
+#[test]
+fn reports_errors_close_together_once() {
+    // The second `=` is discarded without an error, since no token was shifted after the first.
+    let (output, errors) = parser("let = = 2; let x = 1;").parse_recovering();
+
+    assert_eq!(output.unwrap().len(), 2);
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn gives_up_at_the_end_of_input() {
+    let (output, errors) = parser("let a = 1; let b").parse_recovering();
+
+    assert_eq!(output, None);
+    assert_eq!(errors[0].to_string(), "expected `=` but found end of input");
+    assert_eq!(
+        parser("let a = 1;").parse_recovering(),
+        (Some(vec![Statement::Let((4, 5))]), Vec::new())
+    );
+}
+
+#[test]
+fn stops_at_the_first_error_without_recovery() {
+    let tokens = lex("let = 2; let a = 1;").unwrap().into_iter();
+    let error = Parser(ParseState::new(tokens).with_trivia(is_trivia))
+        .parse()
+        .unwrap_err();
+    assert_eq!(error.expected, [TokenType::Identifier]);
+}
@@ -0,0 +1,116 @@
+use yaw::runtime::{Parse, ParseError, ParseState, Token};
+
+#[rustfmt::skip]
+#[path = "generated/tree.rs"]
+mod parser;
+
+use parser::{Atom, Expr, Parser, Program, Statement, TokenType};
+
+fn tokenize(source: &str) -> Vec<Token<TokenType>> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let kind = match c {
+            '+' => TokenType::Plus,
+            ',' => TokenType::Comma,
+            '=' => TokenType::Equals,
+            ';' => TokenType::Semicolon,
+            '(' => TokenType::LParen,
+            ')' => TokenType::RParen,
+            ' ' => continue,
+            '0'..='9' => {
+                while let Some((_, '0'..='9')) = chars.peek() {
+                    chars.next();
+                }
+                TokenType::Number
+            }
+            _ => {
+                while let Some((_, 'a'..='z')) = chars.peek() {
+                    chars.next();
+                }
+                match &source[start..chars.peek().map_or(source.len(), |&(i, _)| i)] {
+                    "print" => TokenType::Print,
+                    _ => TokenType::Identifier,
+                }
+            }
+        };
+        let end = chars.peek().map_or(source.len(), |&(i, _)| i);
+        tokens.push(Token {
+            span: (start, end),
+            kind,
+        });
+    }
+
+    tokens.push(Token {
+        span: (source.len(), source.len()),
+        kind: TokenType::Eof,
+    });
+    tokens
+}
+
+fn parse(source: &str) -> Result<Program, ParseError<TokenType>> {
+    Parser(ParseState::new(tokenize(source).into_iter())).parse()
+}
+
+fn token(kind: TokenType, start: usize, end: usize) -> Token<TokenType> {
+    Token {
+        span: (start, end),
+        kind,
+    }
+}
+
+#[test]
+fn builds_the_tree() {
+    //                  0         1         2
+    //                  0123456789012345678901234567
+    let program = parse("x = 1 + f(2, y); print (x);").unwrap();
+
+    let value = Expr::Add {
+        lhs: Box::new(Expr::Atom(Box::new(Atom::Number(token(
+            TokenType::Number,
+            4,
+            5,
+        ))))),
+        rhs: Box::new(Atom::Call {
+            function: token(TokenType::Identifier, 8, 9),
+            args: vec![
+                Expr::Atom(Box::new(Atom::Number(token(TokenType::Number, 10, 11)))),
+                Expr::Atom(Box::new(Atom::Identifier(token(
+                    TokenType::Identifier,
+                    13,
+                    14,
+                )))),
+            ],
+        }),
+    };
+    let print = Statement::Print {
+        print: token(TokenType::Print, 17, 22),
+        expr: Expr::Atom(Box::new(Atom::Group {
+            l_paren: token(TokenType::LParen, 23, 24),
+            expr: Box::new(Expr::Atom(Box::new(Atom::Identifier(token(
+                TokenType::Identifier,
+                24,
+                25,
+            ))))),
+            r_paren: token(TokenType::RParen, 25, 26),
+        })),
+        semicolon: token(TokenType::Semicolon, 26, 27),
+    };
+
+    assert_eq!(
+        program.statements,
+        vec![
+            Statement::Assign {
+                name: token(TokenType::Identifier, 0, 1),
+                value,
+            },
+            print,
+        ]
+    );
+}
+
+#[test]
+fn builds_empty_lists() {
+    assert_eq!(parse("").unwrap().statements, Vec::new());
+}